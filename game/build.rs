@@ -0,0 +1,192 @@
+//! Compiles the shaders in `src/shaders` to SPIR-V and generates
+//! `$OUT_DIR/shaders.rs`, which `src/shaders.rs` includes.
+//!
+//! `*.vert` and `*.frag` are compiled with `glslc`, `*.slang` with `slangc`,
+//! using the same flags as `glslc.sh` and `slangc.sh`. When a compiler is not
+//! on `PATH` the committed `glsl_*.spv`/`slang_*.spv` next to the source is used
+//! instead. Compile errors fail the build. A committed `.spv` that differs from
+//! the compiler's output only warns, since another compiler version may emit
+//! different bytes for the same source.
+//!
+//! GLSL entry points are always `main`; a Slang entry point is the function
+//! following its `[shader("...")]` attribute.
+
+use std::{
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const SHADER_DIR: &str = "src/shaders";
+
+struct Stage {
+    source: PathBuf,
+    /// `glsl_vertex`, `slang_fragment2`, ...
+    name: String,
+    stage: &'static str,
+    entry_point: String,
+}
+
+impl Stage {
+    fn compiler(&self) -> &'static str {
+        if self.name.starts_with("glsl_") {
+            "glslc"
+        } else {
+            "slangc"
+        }
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={SHADER_DIR}");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let mut generated = String::new();
+
+    for stage in find_stages() {
+        println!("cargo:rerun-if-changed={}", stage.source.display());
+        let committed = stage.source.with_file_name(format!("{}.spv", stage.name));
+        println!("cargo:rerun-if-changed={}", committed.display());
+
+        let output = out_dir.join(format!("{}.spv", stage.name));
+        if !compile(&stage, &output) {
+            if !committed.is_file() {
+                panic!(
+                    "Can't build {}: {} is not on PATH and {} is not committed. Install {} or commit the .spv.",
+                    stage.source.display(),
+                    stage.compiler(),
+                    committed.display(),
+                    stage.compiler()
+                );
+            }
+            fs::copy(&committed, &output).unwrap();
+        } else if fs::read(&committed).ok() != fs::read(&output).ok() {
+            // Builds without the compiler would use the committed binary.
+            println!(
+                "cargo:warning={} is missing or differs from the {} output for {}. Run {}.sh and commit the result if the source changed.",
+                committed.display(),
+                stage.compiler(),
+                stage.source.display(),
+                stage.compiler()
+            );
+        }
+
+        generated.push_str(&format!(
+            "/// Compiled from `{}`.\npub const {}: CompiledShader<{}> = CompiledShader::new(include_bytes!({:?}), {:?});\n",
+            stage.source.display(),
+            stage.name.to_uppercase(),
+            stage.stage,
+            output.display().to_string(),
+            stage.entry_point,
+        ));
+    }
+
+    fs::write(out_dir.join("shaders.rs"), generated).unwrap();
+}
+
+fn find_stages() -> Vec<Stage> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(SHADER_DIR)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter_map(|source| {
+            let stem = source.file_stem()?.to_str()?.to_string();
+            let (prefix, stage, entry_point) = match source.extension()?.to_str()? {
+                "vert" => ("glsl", "Vertex", "main".to_string()),
+                "frag" => ("glsl", "Fragment", "main".to_string()),
+                "slang" => {
+                    let (stage, entry_point) = slang_entry_point(&source)?;
+                    ("slang", stage, entry_point)
+                }
+                _ => return None,
+            };
+            Some(Stage {
+                name: format!("{prefix}_{stem}"),
+                source,
+                stage,
+                entry_point,
+            })
+        })
+        .collect()
+}
+
+/// Reads the stage from the `[shader("...")]` attribute and the entry point
+/// from the function declared on the next line, as in
+/// `float4 main(uint vid : SV_VertexID) : SV_Position`.
+fn slang_entry_point(source: &Path) -> Option<(&'static str, String)> {
+    let code = fs::read_to_string(source).ok()?;
+    let mut lines = code
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"));
+    let stage = lines.find_map(|line| match line {
+        "[shader(\"vertex\")]" => Some("Vertex"),
+        "[shader(\"fragment\")]" => Some("Fragment"),
+        _ => None,
+    })?;
+
+    let name = lines
+        .next()
+        .and_then(|declaration| {
+            declaration[..declaration.find('(')?]
+                .split_whitespace()
+                .last()
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "{}: expected a function after [shader(...)]",
+                source.display()
+            )
+        });
+    Some((stage, name.to_string()))
+}
+
+/// Returns `false` when the compiler isn't installed and panics on compile
+/// errors.
+fn compile(stage: &Stage, output: &Path) -> bool {
+    let mut command = if stage.compiler() == "glslc" {
+        let mut command = Command::new("glslc");
+        command
+            .arg("--target-env=opengl4.5")
+            .arg(format!(
+                "-fshader-stage={}",
+                if stage.stage == "Vertex" {
+                    "vert"
+                } else {
+                    "frag"
+                }
+            ))
+            .arg(&stage.source);
+        command
+    } else {
+        let mut command = Command::new("slangc");
+        command
+            .arg(&stage.source)
+            .args(["-target", "spirv"])
+            .args(["-profile", "spirv_1_4"])
+            .arg("-emit-spirv-directly")
+            .arg("-fvk-use-entrypoint-name")
+            .args(["-entry", &stage.entry_point]);
+        command
+    };
+    command.arg("-o").arg(output);
+
+    let result = match command.output() {
+        Ok(result) => result,
+        Err(e) if e.kind() == ErrorKind::NotFound => return false,
+        Err(e) => panic!("Failed to run {:?}: {e}", command.get_program()),
+    };
+    if !result.status.success() {
+        panic!(
+            "Failed to compile {}:\n{}",
+            stage.source.display(),
+            String::from_utf8_lossy(&result.stderr)
+        );
+    }
+    true
+}
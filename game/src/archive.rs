@@ -1,70 +1,659 @@
-use anyhow::Result;
-use std::{cell::RefCell, collections::HashMap, ffi::OsStr, fs, io::Read, path::Path};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::OsStr,
+    fs,
+    io::{self, Read, Seek},
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError, RwLock, RwLockReadGuard},
+    time::SystemTime,
+};
+use thiserror::Error;
 use zip::ZipArchive;
 
+mod manifest;
+
+pub use manifest::{Checksum, Manifest, ManifestEntry};
+
+#[derive(Error, Debug)]
+pub enum ArchiveError {
+    #[error("Resource not found: \"{0}\"")]
+    ResourceNotFound(String),
+
+    #[error("Invalid path \"{path}\": {reason}")]
+    InvalidPath { path: String, reason: &'static str },
+
+    #[error("Failed to read \"{path}\": {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Invalid archive \"{path}\": {source}")]
+    Zip {
+        path: String,
+        #[source]
+        source: zip::result::ZipError,
+    },
+
+    #[error("Invalid manifest, line {line}: {message}")]
+    InvalidManifest { line: usize, message: String },
+
+    #[error("Pack \"{path}\" ({checksum}) is not in the manifest")]
+    NotInManifest { path: String, checksum: Checksum },
+
+    #[error("Pack \"{name}\" ({checksum}) from the manifest is not loaded")]
+    MissingPack { name: String, checksum: Checksum },
+
+    #[error("Loose files can't be mounted in pure mode: \"{0}\"")]
+    LooseFilesInPureMode(String),
+}
+
+type ArchiveResult<T> = Result<T, ArchiveError>;
+
+enum Source {
+    Directory(PathBuf),
+    Archive {
+        zip: Mutex<ZipArchive<fs::File>>,
+        checksum: Checksum,
+        /// Size and modification time the checksum was computed for.
+        stamp: (u64, Option<SystemTime>),
+    },
+}
+
+/// A directory or pack mounted at `mount_point`.
+struct Mount {
+    /// Index into [`EngineArchive::layers`].
+    layer: usize,
+    /// The directory, or the pack file.
+    path: PathBuf,
+    /// Normalized, and either empty or ending in `/`.
+    mount_point: String,
+    /// Normalized path (including the mount point) to the name inside the
+    /// directory or pack.
+    entries: BTreeMap<String, String>,
+    source: Source,
+}
+
+impl Mount {
+    fn directory(layer: usize, path: &Path, mount_point: String) -> ArchiveResult<Self> {
+        let mut entries = BTreeMap::new();
+        index_directory(path, "", &mut |relative| {
+            let is_pack = Path::new(relative)
+                .extension()
+                .is_some_and(EngineArchive::validate_extension);
+            if !is_pack && let Ok(normalized) = normalize(relative) {
+                entries.insert(format!("{mount_point}{normalized}"), relative.to_string());
+            }
+        })
+        .map_err(|source| io_error(path, source))?;
+
+        Ok(Mount {
+            layer,
+            path: path.to_path_buf(),
+            mount_point,
+            entries,
+            source: Source::Directory(path.to_path_buf()),
+        })
+    }
+
+    /// Reuses the checksum of `previous`, the same pack mounted before, if the
+    /// file hasn't changed since.
+    fn archive(
+        layer: usize,
+        path: &Path,
+        mount_point: String,
+        previous: Option<&Source>,
+    ) -> ArchiveResult<Self> {
+        let mut file = fs::File::open(path).map_err(|source| io_error(path, source))?;
+        let metadata = file.metadata().map_err(|source| io_error(path, source))?;
+        let stamp = (metadata.len(), metadata.modified().ok());
+        let checksum = match previous {
+            Some(&Source::Archive {
+                checksum,
+                stamp: previous_stamp,
+                ..
+            }) if stamp.1.is_some() && previous_stamp == stamp => checksum,
+            _ => {
+                let checksum = Checksum::of(&mut file).map_err(|source| io_error(path, source))?;
+                file.rewind().map_err(|source| io_error(path, source))?;
+                checksum
+            }
+        };
+
+        let archive = ZipArchive::new(file).map_err(|source| ArchiveError::Zip {
+            path: path.display().to_string(),
+            source,
+        })?;
+
+        let entries = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .filter_map(|name| {
+                let normalized = normalize(name).ok()?;
+                Some((format!("{mount_point}{normalized}"), name.to_string()))
+            })
+            .collect();
+
+        Ok(Mount {
+            layer,
+            path: path.to_path_buf(),
+            mount_point,
+            entries,
+            source: Source::Archive {
+                zip: Mutex::new(archive),
+                checksum,
+                stamp,
+            },
+        })
+    }
+
+    fn read(&self, name: &str) -> ArchiveResult<Vec<u8>> {
+        match &self.source {
+            Source::Directory(dir) => {
+                let path = dir.join(name);
+                fs::read(&path).map_err(|source| io_error(&path, source))
+            }
+            Source::Archive { zip, .. } => {
+                let mut archive = zip.lock().unwrap_or_else(PoisonError::into_inner);
+                let mut file = archive.by_name(name).map_err(|source| ArchiveError::Zip {
+                    path: format!("{}:{name}", self.path.display()),
+                    source,
+                })?;
+                let mut bytes = Vec::with_capacity(file.size() as usize);
+                file.read_to_end(&mut bytes)
+                    .map_err(|source| io_error(&self.path, source))?;
+                Ok(bytes)
+            }
+        }
+    }
+}
+
+/// Where a resolved file comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin<'a> {
+    /// The game directory of the layer.
+    pub layer: &'a Path,
+    /// The pack, or the loose file.
+    pub file: PathBuf,
+    pub archived: bool,
+}
+
+impl std::fmt::Display for Origin<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.layer.display(), self.file.display())
+    }
+}
+
+/// The virtual file system assets are loaded through.
+///
+/// Directories and packs (`.pak`, `.pk2` to `.pk5`, all zip files) are mounted
+/// on top of each other, and a path resolves to the most recently mounted
+/// source that has it.
+///
+/// [`Self::new`] builds a search path out of game directories, like Quake's
+/// `fs_game`: the base game first, then mods and patches. Each layer mounts
+/// its packs in sorted order, so later packs override earlier ones, and then
+/// the directory itself, so loose files override every pack of the layer.
+/// Later layers override earlier ones. Only packs directly inside a game
+/// directory are mounted; packs in its subdirectories are neither mounted nor
+/// visible as loose files.
+///
+/// Every pack is checksummed when mounted. [`Self::manifest`] lists them, and
+/// [`Self::set_pure`] restricts the archive to the packs of a manifest.
+///
+/// The archive is `Sync`, so background loaders can read through it while the
+/// main thread does. Reads from the same pack are serialized.
+///
+/// Paths are normalized before lookup: `\` becomes `/`, case is ignored and
+/// `.` segments are dropped. Paths containing `..` are rejected. Every mount is
+/// indexed when it's mounted, so files added on disk later are found after
+/// [`Self::refresh`].
 pub struct EngineArchive {
-    base: &'static Path,
-    archives: HashMap<String, RefCell<ZipArchive<std::fs::File>>>,
-    file_archive_map: HashMap<String, String>,
+    /// Game directories, highest priority last.
+    layers: Vec<PathBuf>,
+    /// Highest priority last. Locked for writing only by [`Self::refresh`].
+    mounts: RwLock<Vec<Mount>>,
+    /// Set in pure mode.
+    pure: Option<Manifest>,
 }
 
 impl EngineArchive {
-    pub fn new(base: &'static str) -> Result<Self> {
-        let path = Path::new(base);
-        let paths = path.read_dir()?;
-        let mut archives = HashMap::new();
-        let mut file_archive_map = HashMap::new();
-
-        for path in paths {
-            let path = path?.path();
-            let Some(extension) = path.extension() else {
-                continue;
-            };
-            if !Self::validate_extension(extension) {
-                continue;
+    /// Mounts each game directory of `search_path` on top of the previous
+    /// ones.
+    pub fn new(search_path: &[PathBuf]) -> anyhow::Result<Self> {
+        anyhow::ensure!(!search_path.is_empty(), "The search path is empty");
+        let mut archive = EngineArchive {
+            layers: Vec::new(),
+            mounts: RwLock::new(Vec::new()),
+            pure: None,
+        };
+        for directory in search_path {
+            archive.add_layer(directory)?;
+        }
+        Ok(archive)
+    }
+
+    /// Adds a game directory above every existing layer: its packs in sorted
+    /// order, then its loose files unless in pure mode.
+    pub fn add_layer(&mut self, directory: &Path) -> anyhow::Result<()> {
+        let mut packs = Vec::new();
+        for entry in directory.read_dir()? {
+            let path = entry?.path();
+            if path.extension().is_some_and(Self::validate_extension) && path.is_file() {
+                packs.push(path);
             }
-            let metadata = fs::metadata(&path)?;
-            if !metadata.is_file() {
+        }
+        packs.sort();
+
+        self.layers.push(directory.to_path_buf());
+        for pack in packs {
+            self.mount_archive(&pack, "")?;
+        }
+        if self.pure.is_none() {
+            self.mount_directory(directory, "")?;
+        }
+        Ok(())
+    }
+
+    /// Mounts a pack above everything mounted so far, as part of the top
+    /// layer. Its files appear under `mount_point`, which may be empty.
+    pub fn mount_archive(&mut self, path: &Path, mount_point: &str) -> ArchiveResult<()> {
+        let mount_point = normalize_mount_point(mount_point)?;
+        let mount = Mount::archive(self.top_layer(), path, mount_point, None)?;
+        self.check_pure(&mount)?;
+        self.mounts_mut().push(mount);
+        Ok(())
+    }
+
+    /// Mounts a directory of loose files above everything mounted so far, as
+    /// part of the top layer.
+    pub fn mount_directory(&mut self, path: &Path, mount_point: &str) -> ArchiveResult<()> {
+        let mount = Mount::directory(self.top_layer(), path, normalize_mount_point(mount_point)?)?;
+        self.check_pure(&mount)?;
+        self.mounts_mut().push(mount);
+        Ok(())
+    }
+
+    pub fn load(&self, path: &str) -> ArchiveResult<Vec<u8>> {
+        self.with_resolved(path, |mount, name| mount.read(name))?
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.with_resolved(path, |_, _| ()).is_ok()
+    }
+
+    /// Modification time of the file that provides `path`: the pack that
+    /// contains it, or the loose file.
+    pub fn modified(&self, path: &str) -> Option<SystemTime> {
+        let file = self.origin(path).ok()?.file;
+        fs::metadata(file).and_then(|m| m.modified()).ok()
+    }
+
+    /// The pack or loose file that provides `path`, and its layer.
+    pub fn origin(&self, path: &str) -> ArchiveResult<Origin<'_>> {
+        self.with_resolved(path, |mount, name| self.mount_origin(mount, name))
+    }
+
+    /// Every file with the source it resolves to, sorted by path.
+    pub fn origins(&self) -> Vec<(String, Origin<'_>)> {
+        let mounts = self.mounts();
+        all_paths(&mounts)
+            .into_iter()
+            .filter_map(|path| {
+                let (mount, name) = mounts
+                    .iter()
+                    .rev()
+                    .find_map(|mount| Some((mount, mount.entries.get(path)?)))?;
+                Some((path.to_string(), self.mount_origin(mount, name)))
+            })
+            .collect()
+    }
+
+    /// The mounted packs and their checksums.
+    pub fn manifest(&self) -> Manifest {
+        let packs = self
+            .mounts()
+            .iter()
+            .filter_map(|mount| match mount.source {
+                Source::Archive { checksum, .. } => Some(ManifestEntry {
+                    checksum,
+                    name: mount.path.display().to_string(),
+                }),
+                Source::Directory(_) => None,
+            })
+            .collect();
+        Manifest { packs }
+    }
+
+    /// Unmounts loose files, and from now on refuses to mount them or packs
+    /// that aren't in `manifest`. Fails if a mounted pack isn't in `manifest`
+    /// (a modified or corrupted download has a different checksum), or a pack
+    /// of `manifest` isn't mounted.
+    pub fn set_pure(&mut self, manifest: Manifest) -> ArchiveResult<()> {
+        let mounted = self.manifest();
+        if let Some(pack) = mounted
+            .packs
+            .iter()
+            .find(|pack| !manifest.contains(pack.checksum))
+        {
+            return Err(ArchiveError::NotInManifest {
+                path: pack.name.clone(),
+                checksum: pack.checksum,
+            });
+        }
+        if let Some(pack) = manifest
+            .packs
+            .iter()
+            .find(|pack| !mounted.contains(pack.checksum))
+        {
+            return Err(ArchiveError::MissingPack {
+                name: pack.name.clone(),
+                checksum: pack.checksum,
+            });
+        }
+
+        self.mounts_mut()
+            .retain(|mount| matches!(mount.source, Source::Archive { .. }));
+        self.pure = Some(manifest);
+        Ok(())
+    }
+
+    /// Game directories, lowest priority first.
+    pub fn layers(&self) -> &[PathBuf] {
+        &self.layers
+    }
+
+    /// The top game directory. Config files are written here so a mod keeps
+    /// its own.
+    pub fn write_directory(&self) -> &Path {
+        &self.layers[self.top_layer()]
+    }
+
+    /// Files directly inside `directory`, and its subdirectories with a
+    /// trailing `/`, across every mount. Sorted and normalized.
+    pub fn list(&self, directory: &str) -> ArchiveResult<Vec<String>> {
+        let mut prefix = normalize(directory)?;
+        if !prefix.is_empty() {
+            prefix.push('/');
+        }
+
+        let mut listing = BTreeSet::new();
+        for path in all_paths(&self.mounts()) {
+            let Some(rest) = path.strip_prefix(&prefix) else {
                 continue;
+            };
+            match rest.split_once('/') {
+                Some((subdirectory, _)) => listing.insert(format!("{prefix}{subdirectory}/")),
+                None => listing.insert(path.to_string()),
+            };
+        }
+        Ok(listing.into_iter().collect())
+    }
+
+    /// Every file matching `pattern`, sorted. `*` and `?` match within a path
+    /// segment and `**` matches any number of segments.
+    pub fn glob(&self, pattern: &str) -> ArchiveResult<Vec<String>> {
+        let pattern = normalize(pattern)?;
+        let pattern: Vec<&str> = pattern.split('/').collect();
+        Ok(all_paths(&self.mounts())
+            .into_iter()
+            .filter(|path| glob::matches(&pattern, &path.split('/').collect::<Vec<_>>()))
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// Reopens every mount, picking up packs and loose files that changed on
+    /// disk. Only packs that changed are checksummed again. Blocks reads until
+    /// it's done.
+    pub fn refresh(&self) -> ArchiveResult<()> {
+        let mut current = self.mounts.write().unwrap_or_else(PoisonError::into_inner);
+        let mut mounts = Vec::with_capacity(current.len());
+        for mount in current.iter() {
+            let mount_point = mount.mount_point.clone();
+            let mount = match mount.source {
+                Source::Directory(_) => Mount::directory(mount.layer, &mount.path, mount_point)?,
+                Source::Archive { .. } => {
+                    Mount::archive(mount.layer, &mount.path, mount_point, Some(&mount.source))?
+                }
+            };
+            self.check_pure(&mount)?;
+            mounts.push(mount);
+        }
+        *current = mounts;
+        Ok(())
+    }
+
+    fn check_pure(&self, mount: &Mount) -> ArchiveResult<()> {
+        let Some(manifest) = &self.pure else {
+            return Ok(());
+        };
+        match mount.source {
+            Source::Directory(_) => Err(ArchiveError::LooseFilesInPureMode(
+                mount.path.display().to_string(),
+            )),
+            Source::Archive { checksum, .. } if !manifest.contains(checksum) => {
+                Err(ArchiveError::NotInManifest {
+                    path: mount.path.display().to_string(),
+                    checksum,
+                })
             }
+            Source::Archive { .. } => Ok(()),
+        }
+    }
+
+    fn top_layer(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    fn mount_origin(&self, mount: &Mount, name: &str) -> Origin<'_> {
+        let (file, archived) = match &mount.source {
+            Source::Directory(dir) => (dir.join(name), false),
+            Source::Archive { .. } => (mount.path.clone(), true),
+        };
+        Origin {
+            layer: &self.layers[mount.layer],
+            file,
+            archived,
+        }
+    }
 
-            let archive_path = path.display().to_string();
-            let file = fs::File::open(&archive_path)?;
-            let archive = ZipArchive::new(file)?;
-            for name in archive.file_names() {
-                file_archive_map.insert(name.to_string(), archive_path.clone());
+    /// Calls `f` with the mount that provides `path` and the name inside it.
+    fn with_resolved<R>(&self, path: &str, f: impl FnOnce(&Mount, &str) -> R) -> ArchiveResult<R> {
+        let normalized = normalize(path)?;
+        let mounts = self.mounts();
+        let (mount, name) = mounts
+            .iter()
+            .rev()
+            .find_map(|mount| Some((mount, mount.entries.get(&normalized)?)))
+            .ok_or(ArchiveError::ResourceNotFound(normalized))?;
+        Ok(f(mount, name))
+    }
+
+    fn mounts(&self) -> RwLockReadGuard<'_, Vec<Mount>> {
+        self.mounts.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn mounts_mut(&mut self) -> &mut Vec<Mount> {
+        self.mounts
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn validate_extension(extension: &OsStr) -> bool {
+        ["pak", "pk2", "pk3", "pk4", "pk5"]
+            .iter()
+            .any(|valid| extension.eq_ignore_ascii_case(valid))
+    }
+}
+
+/// Lowercases, turns `\` into `/` and drops empty and `.` segments.
+pub fn normalize(path: &str) -> ArchiveResult<String> {
+    let lowercase = path.replace('\\', "/").to_lowercase();
+    let mut segments = Vec::new();
+    for segment in lowercase.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                return Err(ArchiveError::InvalidPath {
+                    path: path.to_string(),
+                    reason: "`..` is not allowed",
+                });
             }
-            archives.insert(archive_path, RefCell::new(archive));
+            segment => segments.push(segment),
         }
+    }
+    Ok(segments.join("/"))
+}
 
-        Ok(Self {
-            base: path,
-            archives,
-            file_archive_map,
-        })
+fn normalize_mount_point(mount_point: &str) -> ArchiveResult<String> {
+    let mut normalized = normalize(mount_point)?;
+    if !normalized.is_empty() {
+        normalized.push('/');
     }
+    Ok(normalized)
+}
+
+/// Deduplicated and sorted.
+fn all_paths(mounts: &[Mount]) -> BTreeSet<&str> {
+    mounts
+        .iter()
+        .flat_map(|mount| mount.entries.keys())
+        .map(String::as_str)
+        .collect()
+}
 
-    pub fn load(&self, path: &str) -> Result<Vec<u8>> {
-        if let Some(archive) = self.file_archive_map.get(path) {
-            let mut archive = self.archives.get(archive).unwrap().borrow_mut();
-            let mut file = archive.by_name(path)?;
-            let mut bytes = Vec::with_capacity(file.size() as usize);
-            file.read_to_end(&mut bytes)?;
-            Ok(bytes)
+fn index_directory(dir: &Path, relative: &str, add: &mut impl FnMut(&str)) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let relative = if relative.is_empty() {
+            name
         } else {
-            let path_buf = self.base.join(path);
-            let metadata = fs::metadata(&path_buf)?;
-            if !metadata.is_file() {
-                anyhow::bail!("File cannot be a directory: {}", path_buf.display());
-            }
-            Ok(fs::read(path_buf)?)
+            format!("{relative}/{name}")
+        };
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            index_directory(&entry.path(), &relative, add)?;
+        } else if file_type.is_file() {
+            add(&relative);
         }
     }
+    Ok(())
+}
 
-    fn validate_extension(extension: &OsStr) -> bool {
-        extension == OsStr::new("pak")
-            || extension == OsStr::new("pk2")
-            || extension == OsStr::new("pk3")
-            || extension == OsStr::new("pk4")
+fn io_error(path: &Path, source: io::Error) -> ArchiveError {
+    ArchiveError::Io {
+        path: path.display().to_string(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh game directory with `files` in it.
+    fn game_directory(name: &str, files: &[&str]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("archive_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, file).unwrap();
+        }
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn paths_are_normalized() {
+        assert_eq!(
+            normalize("Textures\\Wall.PNG").unwrap(),
+            "textures/wall.png"
+        );
+        assert_eq!(normalize("/./maps//E1M1.bsp/").unwrap(), "maps/e1m1.bsp");
+        assert_eq!(normalize("").unwrap(), "");
+        for path in ["..", "maps/../../config.cfg", "maps\\..\\x"] {
+            assert!(
+                matches!(normalize(path), Err(ArchiveError::InvalidPath { .. })),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn files_are_listed_and_globbed_across_mounts() {
+        let base = game_directory(
+            "listing_base",
+            &["Textures/Wall.png", "textures/floor.png", "maps/e1m1.bsp"],
+        );
+        let extra = game_directory("listing_extra", &["textures/sky/day.png", "readme.txt"]);
+        let mut archive = EngineArchive::new(std::slice::from_ref(&base)).unwrap();
+        archive.mount_directory(&extra, "").unwrap();
+
+        assert!(archive.exists("TEXTURES\\WALL.PNG"));
+        assert!(archive.exists("readme.txt"));
+        assert!(!archive.exists("textures"));
+        assert!(!archive.exists("../readme.txt"));
+
+        assert_eq!(
+            archive.list("").unwrap(),
+            ["maps/", "readme.txt", "textures/"]
+        );
+        assert_eq!(
+            archive.list("Textures").unwrap(),
+            ["textures/floor.png", "textures/sky/", "textures/wall.png"]
+        );
+        assert_eq!(
+            archive.glob("textures/*.png").unwrap(),
+            ["textures/floor.png", "textures/wall.png"]
+        );
+        assert_eq!(
+            archive.glob("**/*.PNG").unwrap(),
+            [
+                "textures/floor.png",
+                "textures/sky/day.png",
+                "textures/wall.png"
+            ]
+        );
+
+        fs::remove_dir_all(base).unwrap();
+        fs::remove_dir_all(extra).unwrap();
+    }
+
+    #[test]
+    fn new_loose_files_keep_their_case() {
+        let dir = game_directory("loose_case", &[]);
+        let archive = EngineArchive::new(std::slice::from_ref(&dir)).unwrap();
+
+        fs::create_dir_all(dir.join("Sounds")).unwrap();
+        fs::write(dir.join("Sounds/Door.WAV"), "creak").unwrap();
+        assert!(!archive.exists("sounds/door.wav"));
+
+        archive.refresh().unwrap();
+        assert_eq!(archive.load("sounds/door.wav").unwrap(), b"creak");
+        assert_eq!(
+            archive.origin("SOUNDS/DOOR.wav").unwrap().file,
+            dir.join("Sounds/Door.WAV")
+        );
+        assert!(!archive.exists("sounds/window.wav"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn packs_in_subdirectories_are_ignored() {
+        let dir = game_directory("nested_pack", &["maps/extra.pk3", "maps/e1m1.bsp"]);
+        let archive = EngineArchive::new(std::slice::from_ref(&dir)).unwrap();
+
+        assert!(!archive.exists("maps/extra.pk3"));
+        assert_eq!(archive.list("maps").unwrap(), ["maps/e1m1.bsp"]);
+        assert!(archive.manifest().packs.is_empty());
+
+        fs::remove_dir_all(dir).unwrap();
     }
 }
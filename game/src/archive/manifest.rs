@@ -0,0 +1,144 @@
+//! Checksums of the packs a game runs with.
+//!
+//! A manifest lists one pack per line in the format of `sha256sum`:
+//!
+//! ```text
+//! 3f7a…c210  base/pak0.pk3
+//! 91bd…04ee  mymod/pak0.pk3
+//! ```
+//!
+//! Packs are matched by checksum; the names are informational.
+
+use super::{ArchiveError, ArchiveResult};
+use sha2::{Digest, Sha256};
+use std::{fmt, io, str::FromStr};
+
+/// SHA-256 of a pack file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checksum([u8; 32]);
+
+impl Checksum {
+    pub fn of(reader: &mut impl io::Read) -> io::Result<Self> {
+        let mut hasher = Sha256::new();
+        io::copy(reader, &mut hasher)?;
+        Ok(Checksum(hasher.finalize().into()))
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl FromStr for Checksum {
+    type Err = &'static str;
+
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        if hex.len() != 64 {
+            return Err("expected 64 hex digits");
+        }
+        // `from_str_radix` alone would also take a sign, as in `+f`.
+        if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err("invalid hex digit");
+        }
+        let mut bytes = [0; 32];
+        for (byte, digits) in bytes.iter_mut().zip(hex.as_bytes().chunks(2)) {
+            let digits = std::str::from_utf8(digits).expect("hex digits are ASCII");
+            *byte = u8::from_str_radix(digits, 16).expect("checked to be hex digits");
+        }
+        Ok(Checksum(bytes))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub checksum: Checksum,
+    /// The pack's path when the manifest was written.
+    pub name: String,
+}
+
+/// The packs an [`super::EngineArchive`] has mounted, lowest priority first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub packs: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn contains(&self, checksum: Checksum) -> bool {
+        self.packs.iter().any(|pack| pack.checksum == checksum)
+    }
+
+    pub fn parse(text: &str) -> ArchiveResult<Self> {
+        let mut packs = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: &str| ArchiveError::InvalidManifest {
+                line: index + 1,
+                message: message.to_string(),
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (checksum, name) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| error("expected `<sha256>  <pack>`"))?;
+            packs.push(ManifestEntry {
+                checksum: checksum.parse().map_err(error)?,
+                // `sha256sum` marks binary mode with a `*`.
+                name: name.trim_start().trim_start_matches('*').to_string(),
+            });
+        }
+        Ok(Manifest { packs })
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pack in &self.packs {
+            writeln!(f, "{}  {}", pack.checksum, pack.name)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_read_back() {
+        let checksum = Checksum::of(&mut &b"pak0"[..]).unwrap();
+        let hex = checksum.to_string();
+        assert_eq!(hex.parse(), Ok(checksum));
+        assert_eq!(hex.to_uppercase().parse(), Ok(checksum));
+
+        let manifest = Manifest::parse(&format!("# base\n{hex} *base/pak0.pk3\n")).unwrap();
+        assert_eq!(
+            manifest.packs,
+            [ManifestEntry {
+                checksum,
+                name: "base/pak0.pk3".to_string(),
+            }]
+        );
+        assert_eq!(Manifest::parse(&manifest.to_string()).unwrap(), manifest);
+    }
+
+    #[test]
+    fn checksums_are_only_hex_digits() {
+        let digits = "0".repeat(62);
+        for hex in [
+            format!("+f{digits}"),
+            format!("-1{digits}"),
+            format!("0x{digits}"),
+        ] {
+            assert_eq!(hex.parse::<Checksum>(), Err("invalid hex digit"), "{hex}");
+        }
+        assert_eq!(
+            format!("é{}", "0".repeat(62)).parse::<Checksum>(),
+            Err("invalid hex digit")
+        );
+        assert_eq!("abc".parse::<Checksum>(), Err("expected 64 hex digits"));
+    }
+}
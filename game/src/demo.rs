@@ -0,0 +1,415 @@
+//! The fly-camera demo: a textured cube, mouse look and WASD movement.
+//!
+//! `` ` `` opens a console for typing commands; they and their output go to
+//! stdout.
+
+use crate::{
+    Texture, TextureCreateInfo,
+    archive::{ArchiveError, EngineArchive},
+    engine::{
+        Context, Game,
+        actions::{ActionMap, AxisBinding, AxisSource, MouseAxis, Trigger},
+        assets::{Asset, AssetLoader},
+        input::{GamepadAxis, GamepadButton},
+    },
+    scene::{
+        Scene, SceneCreateInfo,
+        camera::{Camera, CameraCreateInfo, CameraType},
+        mesh::{Mesh, Polygon, Vertex},
+        model::{Model, ModelCreateInfo},
+    },
+    shader_program::{
+        PermutationCache, PermutationCacheCreateInfo, ProgramCache, ShaderProgram, ShaderSource,
+    },
+    shaders::{self, CompiledShader},
+    window::KWindow,
+};
+use anyhow::{Result, anyhow};
+use nalgebra_glm::{self as glm};
+use sdl2::keyboard::Scancode;
+use std::{path::PathBuf, rc::Rc, time::Duration};
+
+pub struct FlyCamera {
+    scene: Scene,
+    actions: ActionMap,
+    /// Multiplies [`Self::SPEED`]; changed with the `speed` axis.
+    speed_scale: f32,
+    /// The command being typed while the console is open.
+    console: Option<String>,
+    /// Toggled with the `slang` command.
+    slang_triangle: Option<SlangTriangle>,
+}
+
+/// A triangle drawn by the Slang-compiled `vertex2.slang`/`fragment2.slang`
+/// pair. Its positions come from `SV_VertexID`, so the vertex array is empty.
+struct SlangTriangle {
+    program: Rc<ShaderProgram>,
+    vertex_array: gl::VertexArray,
+}
+
+impl FlyCamera {
+    /// Units per second.
+    const SPEED: f32 = 5.0;
+    /// Degrees per pixel of mouse motion.
+    const SENSITIVITY: f32 = 0.022 * 2.2;
+    /// Degrees per tick with a stick fully deflected.
+    const STICK_LOOK_SPEED: f32 = 3.0;
+    const BINDINGS: &str = "bindings.cfg";
+
+    fn default_bindings() -> ActionMap {
+        let key = |scancode| AxisBinding::new(AxisSource::Trigger(Trigger::Key(scancode)));
+        let inverted = |scancode| AxisBinding {
+            invert: true,
+            ..key(scancode)
+        };
+        let mouse = |axis| AxisBinding {
+            scale: Self::SENSITIVITY,
+            invert: true,
+            ..AxisBinding::new(AxisSource::Mouse(axis))
+        };
+        let pad = |axis, scale| AxisBinding {
+            scale,
+            ..AxisBinding::new(AxisSource::Gamepad(axis))
+        };
+
+        let mut actions = ActionMap::new();
+        actions
+            .bind_action("quit", &[Trigger::Key(Scancode::Escape)])
+            .bind_action("quit", &[Trigger::GamepadButton(GamepadButton::Back)])
+            .bind_action("console", &[Trigger::Key(Scancode::Grave)])
+            .bind_action("sprint", &[Trigger::Key(Scancode::LShift)])
+            .bind_action(
+                "sprint",
+                &[Trigger::GamepadButton(GamepadButton::LeftStick)],
+            )
+            .bind_axis("move_forward", key(Scancode::W))
+            .bind_axis("move_forward", inverted(Scancode::S))
+            .bind_axis("move_right", key(Scancode::D))
+            .bind_axis("move_right", inverted(Scancode::A))
+            .bind_axis("move_up", key(Scancode::Space))
+            .bind_axis("move_up", inverted(Scancode::LAlt))
+            .bind_axis("look_yaw", mouse(MouseAxis::X))
+            .bind_axis("look_pitch", mouse(MouseAxis::Y))
+            .bind_axis(
+                "speed",
+                AxisBinding::new(AxisSource::Mouse(MouseAxis::Wheel)),
+            )
+            .bind_axis("move_forward", pad(GamepadAxis::LeftY, -1.0))
+            .bind_axis("move_right", pad(GamepadAxis::LeftX, 1.0))
+            .bind_axis("move_up", pad(GamepadAxis::TriggerRight, 1.0))
+            .bind_axis("move_up", pad(GamepadAxis::TriggerLeft, -1.0))
+            .bind_axis(
+                "look_yaw",
+                pad(GamepadAxis::RightX, -Self::STICK_LOOK_SPEED),
+            )
+            .bind_axis(
+                "look_pitch",
+                pad(GamepadAxis::RightY, -Self::STICK_LOOK_SPEED),
+            );
+        actions
+    }
+
+    fn set_console_open(&mut self, window: &KWindow, open: bool) {
+        window.set_text_input(open);
+        window.set_relative_mouse_mode(!open);
+        self.console = open.then(String::new);
+    }
+
+    /// Edits the command while the console is open. Return runs it and Escape
+    /// closes the console.
+    fn update_console(&mut self, context: &Context) {
+        let input = context.input;
+        let Some(command) = &mut self.console else {
+            return;
+        };
+        command.push_str(input.text_input());
+        // The input method uses these keys itself while it is composing.
+        if input.composition().is_some() {
+            return;
+        }
+
+        if input.was_key_pressed(Scancode::Backspace) || input.was_key_repeated(Scancode::Backspace)
+        {
+            command.pop();
+        }
+        if input.was_key_pressed(Scancode::Return) {
+            let command = std::mem::take(command);
+            self.run_command(context.archive, command.trim());
+        } else if input.was_key_pressed(Scancode::Escape) {
+            self.set_console_open(context.window, false);
+        }
+    }
+
+    fn run_command(&mut self, archive: &EngineArchive, command: &str) {
+        println!("> {command}");
+        match command.split_whitespace().collect::<Vec<_>>()[..] {
+            [] => {}
+            ["speed", scale] => match scale.parse::<f32>() {
+                Ok(scale) => self.speed_scale = scale.clamp(0.1, 10.0),
+                Err(_) => println!("`{scale}` is not a number"),
+            },
+            ["position"] => {
+                let position = self.scene.camera.position();
+                println!("{:.2} {:.2} {:.2}", position.x, position.y, position.z);
+            }
+            // Like `ls`, a file lists as itself.
+            ["ls", path] if archive.exists(path) => println!("{path}"),
+            ["ls"] => print_paths(archive.list("")),
+            ["ls", directory] => print_paths(archive.list(directory)),
+            ["find", pattern] => print_paths(archive.glob(pattern)),
+            ["slang"] if self.slang_triangle.is_some() => self.slang_triangle = None,
+            ["slang"] => {
+                match create_shader_program(&shaders::SLANG_VERTEX2, &shaders::SLANG_FRAGMENT2) {
+                    Ok(program) => {
+                        self.slang_triangle = Some(SlangTriangle {
+                            program,
+                            vertex_array: gl::VertexArray::create1(),
+                        })
+                    }
+                    Err(e) => println!("{e}"),
+                }
+            }
+            _ => println!(
+                "Unknown command; try `speed <scale>`, `position`, `ls [directory]`, `find <pattern>` or `slang`"
+            ),
+        }
+    }
+}
+
+impl Game for FlyCamera {
+    fn init(context: &mut Context) -> Result<Self> {
+        let shader_program = match &mut context.shader_reloader {
+            Some(reloader) => reloader.load(
+                context.archive,
+                ShaderSource::File(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders/vertex.vert").into(),
+                ),
+                ShaderSource::File(
+                    concat!(env!("CARGO_MANIFEST_DIR"), "/src/shaders/fragment.frag").into(),
+                ),
+            )?,
+            None => create_shader_program(&shaders::GLSL_VERTEX, &shaders::GLSL_FRAGMENT)
+                .map_err(|e| anyhow!(e))?,
+        };
+
+        context.window.set_relative_mouse_mode(true);
+        // SDL starts with text input on; only the console wants it.
+        context.window.set_text_input(false);
+
+        let actions = ActionMap::load_or_create(
+            &context.archive.write_directory().join(Self::BINDINGS),
+            Self::default_bindings(),
+        )?;
+
+        Ok(FlyCamera {
+            scene: load_scene(context.archive, context.assets, shader_program)?,
+            actions,
+            speed_scale: 1.0,
+            console: None,
+            slang_triangle: None,
+        })
+    }
+
+    fn update(&mut self, context: &mut Context, delta_time: f32) {
+        self.scene.begin_tick();
+
+        let input = context.input;
+        if self.actions.was_action_pressed(input, "console") {
+            self.set_console_open(context.window, self.console.is_none());
+            return;
+        }
+        if self.console.is_some() {
+            self.update_console(context);
+            return;
+        }
+        if self.actions.was_action_pressed(input, "quit") {
+            context.exit();
+        }
+
+        let camera = &mut self.scene.camera;
+        camera.rotate(
+            self.actions.axis(input, "look_yaw"),
+            self.actions.axis(input, "look_pitch"),
+        );
+
+        let right = camera
+            .direction()
+            .cross(&glm::vec3(0.0, 1.0, 0.0))
+            .normalize();
+        let movement = camera.direction() * self.actions.axis(input, "move_forward")
+            + right * self.actions.axis(input, "move_right")
+            + glm::vec3(0.0, 1.0, 0.0) * self.actions.axis(input, "move_up");
+        if self.actions.was_action_pressed(input, "sprint") {
+            for id in context.gamepads.ids() {
+                context
+                    .gamepads
+                    .rumble(id, 0.2, 0.4, Duration::from_millis(120));
+            }
+        }
+        self.speed_scale =
+            (self.speed_scale * 1.1f32.powf(self.actions.axis(input, "speed"))).clamp(0.1, 10.0);
+        let mut speed = Self::SPEED * self.speed_scale;
+        if self.actions.is_action_down(input, "sprint") {
+            speed *= 2.0;
+        }
+        camera.translate(movement * delta_time * speed);
+    }
+
+    fn render(&self, alpha: f32) {
+        self.scene.render(alpha);
+        if let Some(triangle) = &self.slang_triangle {
+            triangle.program.r#use();
+            triangle.vertex_array.bind();
+            gl::draw_arrays(gl::DrawMode::Triangles, 0, 3);
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.scene.resize(width, height);
+    }
+}
+
+fn print_paths(paths: Result<Vec<String>, ArchiveError>) {
+    match paths {
+        Ok(paths) => paths.iter().for_each(|path| println!("{path}")),
+        Err(e) => println!("{e}"),
+    }
+}
+
+fn cube_mesh() -> Mesh {
+    let vertices = vec![
+        // Front
+        Vertex {
+            position: [-1.0, -1.0, 1.0],
+            color: [0.0, 0.0, 1.0],
+            tex_coords: [0.0, 1.0],
+        },
+        Vertex {
+            position: [-1.0, 1.0, 1.0],
+            color: [0.0, 1.0, 1.0],
+            tex_coords: [0.0, 0.0],
+        },
+        Vertex {
+            position: [1.0, 1.0, 1.0],
+            color: [1.0, 1.0, 1.0],
+            tex_coords: [1.0, 0.0],
+        },
+        Vertex {
+            position: [1.0, -1.0, 1.0],
+            color: [1.0, 0.0, 1.0],
+            tex_coords: [1.0, 1.0],
+        },
+        Vertex {
+            position: [-1.0, -1.0, -1.0],
+            color: [0.0, 0.0, 0.0],
+            tex_coords: [0.0, 0.0],
+        },
+        Vertex {
+            position: [-1.0, 1.0, -1.0],
+            color: [0.0, 1.0, 0.0],
+            tex_coords: [0.0, 1.0],
+        },
+        Vertex {
+            position: [1.0, 1.0, -1.0],
+            color: [1.0, 1.0, 0.0],
+            tex_coords: [1.0, 1.0],
+        },
+        Vertex {
+            position: [1.0, -1.0, -1.0],
+            color: [1.0, 0.0, 0.0],
+            tex_coords: [1.0, 0.0],
+        },
+    ];
+
+    let polygons = vec![
+        Polygon { indices: [0, 1, 2] },
+        Polygon { indices: [2, 3, 0] },
+        Polygon { indices: [4, 5, 6] },
+        Polygon { indices: [6, 7, 4] },
+        Polygon { indices: [0, 4, 7] },
+        Polygon { indices: [7, 3, 0] },
+        Polygon { indices: [1, 5, 6] },
+        Polygon { indices: [6, 2, 1] },
+        Polygon { indices: [1, 0, 4] },
+        Polygon { indices: [4, 5, 1] },
+        Polygon { indices: [3, 2, 6] },
+        Polygon { indices: [6, 7, 3] },
+    ];
+
+    Mesh::new(vertices, polygons)
+}
+
+fn load_scene(
+    archive: &EngineArchive,
+    assets: &mut AssetLoader,
+    shader_program: Rc<ShaderProgram>,
+) -> Result<Scene> {
+    let main_texture = assets.load_texture(
+        "container2.png",
+        Rc::new(Texture::placeholder()),
+        |rgba_image| TextureCreateInfo {
+            rgba_image,
+            internal_format: gl::BaseInternalFormat::RGBA,
+            mip_level: 0,
+            wrap_s: gl::TextureWrapMode::Repeat,
+            wrap_t: gl::TextureWrapMode::Repeat,
+            min_filter: gl::InterpolationMode::Linear,
+            mag_filter: gl::InterpolationMode::Nearest,
+            mipmap_interpolation: Some(gl::InterpolationMode::Linear),
+        },
+    );
+
+    // The built-in cube, unless the game directory has a mesh to replace it.
+    let cube = Rc::new(cube_mesh());
+    let mesh = if archive.exists("cube.obj") {
+        assets.load_mesh("cube.obj", cube)
+    } else {
+        Asset::ready(cube)
+    };
+
+    let model = Model::new(ModelCreateInfo {
+        mesh,
+        model_matrix: glm::identity(),
+        shader_program,
+        texture: main_texture,
+    })?;
+
+    let camera = Camera::from(CameraCreateInfo {
+        camera_type: CameraType::Perspective {
+            fov: 90.0,
+            // Replaced by the real aspect in `resize`.
+            aspect: 16.0 / 9.0,
+        },
+        far: 100.0,
+        near: 0.1,
+        position: glm::vec3(0.0, 0.0, 0.0),
+    });
+
+    let scene = Scene::from(SceneCreateInfo {
+        models: vec![model],
+        camera,
+    });
+
+    Ok(scene)
+}
+
+fn create_shader_program(
+    vertex: &CompiledShader<shaders::Vertex>,
+    fragment: &CompiledShader<shaders::Fragment>,
+) -> Result<Rc<ShaderProgram>, String> {
+    // A per-user directory, so the cache doesn't depend on the working directory.
+    let program_cache = match sdl2::filesystem::pref_path("kengine", "demo") {
+        Ok(dir) => Some(ProgramCache::new(PathBuf::from(dir).join("program_cache"))),
+        Err(e) => {
+            eprintln!("Shader program cache disabled: {e}");
+            None
+        }
+    };
+
+    let permutations = PermutationCache::new(PermutationCacheCreateInfo {
+        vertex,
+        fragment,
+        program_cache,
+    })?;
+    permutations.get(&[])
+}
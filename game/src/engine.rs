@@ -1,36 +1,62 @@
 use crate::{
-    Texture, TextureCreateInfo,
     archive::EngineArchive,
-    scene::{
-        Scene, SceneCreateInfo,
-        camera::{Camera, CameraCreateInfo, CameraType},
-        model::{Model, ModelCreateInfo, Polygon, Vertex},
-    },
-    shader_program::{ShaderCode, ShaderProgram},
-    window,
+    shader_program::{Preprocessor, ShaderReloader},
+    window::{self, VSync},
 };
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use gl::{self, Capability};
-use image::ImageBuffer;
-use nalgebra_glm::{self as glm};
-use sdl2::keyboard::Scancode;
-use std::{rc::Rc, time::Instant};
+use sdl2::{
+    event::{Event, WindowEvent},
+    keyboard::Scancode,
+};
+use std::{io, path::Path, sync::Arc, time::Instant};
 
+pub mod actions;
+pub mod assets;
+pub mod frame_pacing;
+mod game;
+mod gamepad;
 pub mod input;
-
-pub struct KEngine {
+pub mod replay;
+pub mod timestep;
+
+use assets::{AssetLoader, LoaderConfig};
+use frame_pacing::{FrameLimiter, FrameStats, PresentationConfig};
+pub use game::{Context, Game};
+pub use gamepad::Gamepads;
+use replay::{InputRecorder, InputReplay, ReplayError};
+use timestep::{FixedTimestep, TimestepConfig};
+
+// Fields drop in declaration order: GL resources must go before the window
+// that owns the context.
+pub struct KEngine<G: Game> {
+    game: G,
+    /// Holds placeholder textures until their loads finish.
+    assets: AssetLoader,
+    input: input::Input,
+    gamepads: Gamepads,
+    presentation: PresentationConfig,
+    frame_limiter: FrameLimiter,
+    frame_stats: FrameStats,
+    timestep: FixedTimestep,
+    recorder: Option<InputRecorder>,
+    replay: Option<InputReplay>,
+    /// Only set in debug builds, which load GLSL sources instead of SPIR-V.
+    shader_reloader: Option<ShaderReloader>,
+    archive: Arc<EngineArchive>,
     window: window::KWindow,
-    archive: EngineArchive,
-    scene: Scene,
 }
 
-impl KEngine {
-    pub fn new(width: u32, height: u32, title: &str) -> Result<Self> {
-        let window = window::KWindow::new(window::KWindowCreateInfo {
-            title,
-            width,
-            height,
-        });
+impl<G: Game> KEngine<G> {
+    pub fn new(
+        archive: EngineArchive,
+        window_create_info: window::KWindowCreateInfo,
+        mut presentation: PresentationConfig,
+        timestep: TimestepConfig,
+        loader: LoaderConfig,
+    ) -> Result<Self> {
+        let window = window::KWindow::new(window_create_info);
+        presentation.vsync = window.set_vsync(presentation.vsync);
 
         gl::load_fns(|s| window.get_proc_address(s)).unwrap();
         if cfg!(debug_assertions) {
@@ -39,98 +65,208 @@ impl KEngine {
             gl::debug_message_callback(Some(debug_callback), 0);
         }
 
-        let archive = EngineArchive::new("base").expect("Failed to load base archive");
-
-        let shader_program = create_shader_program().map_err(|e| anyhow!(e))?;
-        let shader_program = Rc::new(shader_program);
-
-        let scene = load_scene(&archive, shader_program)?;
-
-        Ok(KEngine {
+        let archive = Arc::new(archive);
+        let mut assets = AssetLoader::new(archive.clone(), loader);
+        // Only debug builds reload shaders; they can add checks under `#ifdef DEBUG`.
+        let mut shader_reloader = cfg!(debug_assertions)
+            .then(|| ShaderReloader::new(Preprocessor::new().define("DEBUG", 1)));
+        let input = input::Input::new();
+        let gamepads = Gamepads::new(window.game_controller());
+        let game = G::init(&mut Context::new(
+            &archive,
+            &mut assets,
+            &input,
+            &gamepads,
+            &window,
+            shader_reloader.as_mut(),
+        ))?;
+
+        let mut engine = KEngine {
+            game,
+            assets,
+            input,
+            gamepads,
+            frame_limiter: FrameLimiter::new(presentation.fps_cap),
+            frame_stats: FrameStats::new(),
+            timestep: FixedTimestep::new(timestep),
+            recorder: None,
+            replay: None,
+            presentation,
+            shader_reloader,
             archive,
             window,
-            scene,
-        })
+        };
+        let (width, height) = engine.window.drawable_size();
+        engine.resize(width, height);
+        Ok(engine)
     }
 
     pub fn run(&mut self) {
         gl::clear_color(0.1, 0.1, 0.1, 1.0);
         gl::enable(Capability::DepthTest);
 
-        self.window.set_relative_mouse_mode(true);
-
-        let mut input = input::Input::new();
         let mut event_pump = self.window.event_pump();
 
         let mut last_frame = Instant::now();
-        let mut delta_time;
         loop {
-            self.draw_frame();
-
-            let events = event_pump.poll_iter();
-            input.update(events);
-            if input.exit {
-                break;
+            if let Some(reloader) = &mut self.shader_reloader {
+                reloader.poll(&self.archive);
             }
 
-            delta_time = last_frame.elapsed().as_secs_f32();
-            last_frame = Instant::now();
-
-            self.process_input(&mut input, delta_time);
+            let now = Instant::now();
+            let mut frame_time = now - last_frame;
+            last_frame = now;
+
+            let mut events: Vec<Event> = event_pump.poll_iter().collect();
+            if let Some(replay) = &mut self.replay {
+                let Some(frame) = replay.next_frame() else {
+                    break;
+                };
+                frame_time = frame.frame_time;
+                events.retain(|event| !replay::is_recorded(event));
+                events.extend(frame.events);
+            }
+            if let Some(recorder) = &mut self.recorder
+                && let Err(e) = recorder.record_frame(frame_time, &events)
+            {
+                eprintln!("Stopped recording input: {e}");
+                self.recorder = None;
+            }
 
-            if input.exit {
+            self.input.update(events.into_iter());
+            if self.input.exit {
                 break;
             }
-        }
-    }
-
-    fn process_input(&mut self, input: &mut input::Input, delta_time: f32) {
-        if input.was_key_pressed(Scancode::Escape) {
-            input.exit = true;
-        }
-
-        let camera = &mut self.scene.camera;
-
-        let mouse_rel = input.mouse_rel();
-        let delta_yaw = -mouse_rel.0 as f32 * 0.022 * 2.2;
-        let delta_pitch = -mouse_rel.1 as f32 * 0.022 * 2.2;
-        let right = camera
-            .direction()
-            .cross(&glm::vec3(0.0, 1.0, 0.0))
-            .normalize();
+            self.gamepads.handle_events(self.input.events());
+            let resized = self.input.events().any(|event| {
+                matches!(
+                    event,
+                    Event::Window {
+                        win_event: WindowEvent::SizeChanged(..),
+                        ..
+                    }
+                )
+            });
+            if resized {
+                let (width, height) = self.window.drawable_size();
+                self.resize(width, height);
+            }
 
-        camera.rotate(delta_yaw, delta_pitch);
+            let mut context = Context::new(
+                &self.archive,
+                &mut self.assets,
+                &self.input,
+                &self.gamepads,
+                &self.window,
+                self.shader_reloader.as_mut(),
+            );
+            for event in self.input.events() {
+                self.game.on_event(&mut context, event);
+            }
+            let mut exit = context.exit_requested();
+
+            let delta_time = self.timestep.delta_time();
+            let mut presentation = self.presentation;
+            for _ in 0..self.timestep.advance(frame_time) {
+                toggle_presentation(&self.input, &mut presentation);
+                let mut context = Context::new(
+                    &self.archive,
+                    &mut self.assets,
+                    &self.input,
+                    &self.gamepads,
+                    &self.window,
+                    self.shader_reloader.as_mut(),
+                );
+                self.game.update(&mut context, delta_time);
+                exit |= context.exit_requested();
+                self.input.end_tick();
+            }
+            if presentation != self.presentation {
+                self.set_presentation(presentation);
+            }
+            if exit {
+                break;
+            }
 
-        if input.is_key_down(Scancode::W) {
-            camera.translate(camera.direction() * delta_time * 5.0);
+            self.assets.upload();
+            self.draw_frame(self.timestep.alpha());
         }
 
-        if input.is_key_down(Scancode::S) {
-            camera.translate(-camera.direction() * delta_time * 5.0);
+        if let Some(recorder) = self.recorder.take()
+            && let Err(e) = recorder.finish()
+        {
+            eprintln!("Failed to finish input recording: {e}");
         }
+    }
 
-        if input.is_key_down(Scancode::A) {
-            camera.translate(-right * delta_time * 5.0);
-        }
+    /// Writes the input of every frame [`Self::run`] processes to `path`.
+    pub fn record_input(&mut self, path: &Path) -> io::Result<()> {
+        self.recorder = Some(InputRecorder::create(path)?);
+        Ok(())
+    }
 
-        if input.is_key_down(Scancode::D) {
-            camera.translate(right * delta_time * 5.0);
-        }
+    /// Makes [`Self::run`] take its input and frame times from a recording
+    /// instead of the devices, and return when the recording ends.
+    pub fn replay_input(&mut self, path: &Path) -> Result<(), ReplayError> {
+        self.replay = Some(InputReplay::load(path)?);
+        Ok(())
+    }
 
-        if input.is_key_down(Scancode::Space) {
-            camera.translate(glm::vec3(0.0, 1.0, 0.0) * delta_time * 5.0);
-        }
+    /// Applies `config` immediately. The vsync mode may be downgraded if the
+    /// driver doesn't support it.
+    pub fn set_presentation(&mut self, mut config: PresentationConfig) {
+        config.vsync = self.window.set_vsync(config.vsync);
+        self.frame_limiter.set_fps_cap(config.fps_cap);
+        self.presentation = config;
+    }
 
-        if input.is_key_down(Scancode::LAlt) {
-            camera.translate(glm::vec3(0.0, -1.0, 0.0) * delta_time * 5.0);
-        }
+    fn resize(&mut self, width: u32, height: u32) {
+        unsafe { gl::viewport(0, 0, width as i32, height as i32) };
+        self.game.resize(width, height);
     }
 
-    fn draw_frame(&self) {
+    fn draw_frame(&mut self, alpha: f32) {
+        gl::flush_deferred_deletes();
         gl::clear(gl::ClearMask::ColorBufferBit);
         gl::clear(gl::ClearMask::DepthBufferBit);
-        self.scene.render();
+        self.game.render(alpha);
+        self.frame_limiter.wait();
         self.window.swap_window();
+
+        if let Some(pacing) = self.frame_stats.record() {
+            let cap = match self.presentation.fps_cap {
+                Some(fps) => format!("cap {fps:.0}"),
+                None => "uncapped".to_string(),
+            };
+            let loading = match self.assets.pending() {
+                0 => String::new(),
+                pending => format!(", loading {pending} assets"),
+            };
+            self.window.set_status(&format!(
+                "{pacing}, vsync {:?}, {cap}{loading}",
+                self.presentation.vsync
+            ));
+        }
+    }
+}
+
+/// F1 cycles the vsync mode and F2 the FPS cap.
+fn toggle_presentation(input: &input::Input, presentation: &mut PresentationConfig) {
+    if input.was_key_pressed(Scancode::F1) {
+        presentation.vsync = match presentation.vsync {
+            VSync::Off => VSync::On,
+            VSync::On => VSync::Adaptive,
+            VSync::Adaptive => VSync::Off,
+        };
+    }
+
+    if input.was_key_pressed(Scancode::F2) {
+        presentation.fps_cap = match presentation.fps_cap {
+            None => Some(30.0),
+            Some(fps) if fps < 60.0 => Some(60.0),
+            Some(fps) if fps < 144.0 => Some(144.0),
+            Some(_) => None,
+        };
     }
 }
 
@@ -144,123 +280,3 @@ fn debug_callback(
 ) {
     eprintln!("{source}: [{type} {severity}] {message}");
 }
-
-fn load_cube(shader_program: Rc<ShaderProgram>, texture: Rc<Texture>) -> Model {
-    let vertices = vec![
-        // Front
-        Vertex {
-            position: [-1.0, -1.0, 1.0],
-            color: [0.0, 0.0, 1.0],
-            tex_coords: [0.0, 1.0],
-        },
-        Vertex {
-            position: [-1.0, 1.0, 1.0],
-            color: [0.0, 1.0, 1.0],
-            tex_coords: [0.0, 0.0],
-        },
-        Vertex {
-            position: [1.0, 1.0, 1.0],
-            color: [1.0, 1.0, 1.0],
-            tex_coords: [1.0, 0.0],
-        },
-        Vertex {
-            position: [1.0, -1.0, 1.0],
-            color: [1.0, 0.0, 1.0],
-            tex_coords: [1.0, 1.0],
-        },
-        Vertex {
-            position: [-1.0, -1.0, -1.0],
-            color: [0.0, 0.0, 0.0],
-            tex_coords: [0.0, 0.0],
-        },
-        Vertex {
-            position: [-1.0, 1.0, -1.0],
-            color: [0.0, 1.0, 0.0],
-            tex_coords: [0.0, 1.0],
-        },
-        Vertex {
-            position: [1.0, 1.0, -1.0],
-            color: [1.0, 1.0, 0.0],
-            tex_coords: [1.0, 1.0],
-        },
-        Vertex {
-            position: [1.0, -1.0, -1.0],
-            color: [1.0, 0.0, 0.0],
-            tex_coords: [1.0, 0.0],
-        },
-    ];
-
-    let polygons = vec![
-        Polygon { indices: [0, 1, 2] },
-        Polygon { indices: [2, 3, 0] },
-        Polygon { indices: [4, 5, 6] },
-        Polygon { indices: [6, 7, 4] },
-        Polygon { indices: [0, 4, 7] },
-        Polygon { indices: [7, 3, 0] },
-        Polygon { indices: [1, 5, 6] },
-        Polygon { indices: [6, 2, 1] },
-        Polygon { indices: [1, 0, 4] },
-        Polygon { indices: [4, 5, 1] },
-        Polygon { indices: [3, 2, 6] },
-        Polygon { indices: [6, 7, 3] },
-    ];
-
-    let create_info = ModelCreateInfo {
-        vertices,
-        polygons,
-        model_matrix: glm::identity(),
-        shader_program,
-        texture,
-    };
-
-    Model::new(create_info)
-}
-
-fn load_scene(archive: &EngineArchive, shader_program: Rc<ShaderProgram>) -> Result<Scene> {
-    let main_texture = Texture::from(TextureCreateInfo {
-        rgba_image: load_image_from_archive(archive, "container2.png")?,
-        internal_format: gl::BaseInternalFormat::RGBA,
-        mip_level: 0,
-        wrap_s: gl::TextureWrapMode::Repeat,
-        wrap_t: gl::TextureWrapMode::Repeat,
-        min_filter: gl::InterpolationMode::Linear,
-        mag_filter: gl::InterpolationMode::Nearest,
-        mipmap_interpolation: Some(gl::InterpolationMode::Linear),
-    });
-    let main_texture = Rc::new(main_texture);
-
-    let model = load_cube(shader_program, main_texture.clone());
-
-    let camera = Camera::from(CameraCreateInfo {
-        camera_type: CameraType::Perspective {
-            fov: 90.0,
-            aspect: 16.0 / 9.0,
-        },
-        far: 100.0,
-        near: 0.1,
-        position: glm::vec3(0.0, 0.0, 0.0),
-    });
-
-    let scene = Scene::from(SceneCreateInfo {
-        models: vec![model],
-        camera,
-    });
-
-    Ok(scene)
-}
-
-fn create_shader_program() -> Result<ShaderProgram, String> {
-    let vertex = include_bytes!("shaders/glsl_vertex.spv");
-    let fragment = include_bytes!("shaders/glsl_fragment.spv");
-
-    ShaderProgram::new(ShaderCode::SPIRV(vertex), ShaderCode::SPIRV(fragment))
-}
-
-fn load_image_from_archive(
-    archive: &EngineArchive,
-    path: &str,
-) -> Result<ImageBuffer<image::Rgba<u8>, Vec<u8>>> {
-    let bytes = archive.load(path)?;
-    let image = image::load_from_memory(&bytes)?;
-    Ok(image.into_rgba8())
-}
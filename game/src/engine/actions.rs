@@ -0,0 +1,477 @@
+//! Named actions and axes bound to physical inputs.
+//!
+//! Bindings are stored one per line:
+//!
+//! ```text
+//! # Comment
+//! action jump = Space
+//! action quick_save = Left Ctrl+S
+//! axis move_forward = W
+//! axis move_forward = S invert
+//! axis look_yaw = MouseX scale=0.0484 invert
+//! axis move_right = Pad leftx
+//! action jump = Pad a
+//! action zoom_in = Left Ctrl+"Keypad +"
+//! ```
+//!
+//! Input names that contain `+` or `=` are written in double quotes.
+//!
+//! Gamepad inputs use SDL's controller names and read from every connected
+//! gamepad.
+//!
+//! An action is active while every input of one of its chords is held. An
+//! axis is the sum of its bindings, each scaled, optionally inverted and
+//! zeroed inside its dead zone.
+
+use crate::engine::input::{GamepadAxis, GamepadButton, Input, MouseButton, Scancode};
+use anyhow::Result;
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("line {line}: {message}")]
+pub struct BindingParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseAxis {
+    X,
+    Y,
+    /// Vertical scroll, positive away from the user.
+    Wheel,
+}
+
+/// A physical input that can be part of a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Key(Scancode),
+    MouseButton(MouseButton),
+    GamepadButton(GamepadButton),
+}
+
+/// Where an axis binding reads its raw value from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AxisSource {
+    /// 1 while the trigger is held, 0 otherwise.
+    Trigger(Trigger),
+    /// Relative motion in pixels, or scroll, since the last tick.
+    Mouse(MouseAxis),
+    /// The gamepad axis furthest from rest, after the gamepad dead zones.
+    Gamepad(GamepadAxis),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisBinding {
+    pub source: AxisSource,
+    pub scale: f32,
+    pub invert: bool,
+    /// Raw values with a smaller magnitude read as 0.
+    pub dead_zone: f32,
+}
+
+impl AxisBinding {
+    pub fn new(source: AxisSource) -> Self {
+        AxisBinding {
+            source,
+            scale: 1.0,
+            invert: false,
+            dead_zone: 0.0,
+        }
+    }
+
+    fn value(&self, input: &Input) -> f32 {
+        let raw = match self.source {
+            AxisSource::Trigger(trigger) => {
+                if trigger.is_down(input) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            AxisSource::Mouse(MouseAxis::X) => input.mouse_rel().0 as f32,
+            AxisSource::Mouse(MouseAxis::Y) => input.mouse_rel().1 as f32,
+            AxisSource::Mouse(MouseAxis::Wheel) => input.mouse_wheel().1,
+            AxisSource::Gamepad(axis) => input
+                .gamepad_ids()
+                .map(|id| input.gamepad_axis(id, axis))
+                .fold(0.0, |a: f32, b: f32| if b.abs() > a.abs() { b } else { a }),
+        };
+        if raw.abs() <= self.dead_zone {
+            return 0.0;
+        }
+        let value = raw * self.scale;
+        if self.invert { -value } else { value }
+    }
+}
+
+impl Trigger {
+    fn is_down(self, input: &Input) -> bool {
+        match self {
+            Trigger::Key(scancode) => input.is_key_down(scancode),
+            Trigger::MouseButton(button) => input.is_mouse_down(button),
+            Trigger::GamepadButton(button) => input
+                .gamepad_ids()
+                .any(|id| input.is_gamepad_button_down(id, button)),
+        }
+    }
+
+    fn was_pressed(self, input: &Input) -> bool {
+        match self {
+            Trigger::Key(scancode) => input.was_key_pressed(scancode),
+            Trigger::MouseButton(button) => input.was_mouse_pressed(button),
+            Trigger::GamepadButton(button) => input
+                .gamepad_ids()
+                .any(|id| input.was_gamepad_button_pressed(id, button)),
+        }
+    }
+}
+
+/// Maps action and axis names to their bindings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionMap {
+    actions: BTreeMap<String, Vec<Vec<Trigger>>>,
+    axes: BTreeMap<String, Vec<AxisBinding>>,
+}
+
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chord that activates `action`. A single-element chord is a
+    /// plain binding.
+    pub fn bind_action(&mut self, action: &str, chord: &[Trigger]) -> &mut Self {
+        assert!(!chord.is_empty(), "a chord needs at least one trigger");
+        self.actions
+            .entry(action.to_string())
+            .or_default()
+            .push(chord.to_vec());
+        self
+    }
+
+    pub fn bind_axis(&mut self, axis: &str, binding: AxisBinding) -> &mut Self {
+        self.axes.entry(axis.to_string()).or_default().push(binding);
+        self
+    }
+
+    pub fn is_action_down(&self, input: &Input, action: &str) -> bool {
+        self.chords(action)
+            .any(|chord| chord.iter().all(|trigger| trigger.is_down(input)))
+    }
+
+    /// Whether a chord of `action` completed this tick: all of its triggers
+    /// are held and at least one was just pressed.
+    pub fn was_action_pressed(&self, input: &Input, action: &str) -> bool {
+        self.chords(action).any(|chord| {
+            chord.iter().all(|trigger| trigger.is_down(input))
+                && chord.iter().any(|trigger| trigger.was_pressed(input))
+        })
+    }
+
+    /// 0 for unbound axes.
+    pub fn axis(&self, input: &Input, axis: &str) -> f32 {
+        self.axes
+            .get(axis)
+            .into_iter()
+            .flatten()
+            .map(|binding| binding.value(input))
+            .sum()
+    }
+
+    fn chords(&self, action: &str) -> impl Iterator<Item = &Vec<Trigger>> {
+        self.actions.get(action).into_iter().flatten()
+    }
+
+    pub fn parse(text: &str) -> Result<Self, BindingParseError> {
+        let mut map = ActionMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| BindingParseError {
+                line: index + 1,
+                message,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (head, binding) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected `action name = ...` or `axis name = ...`".into()))?;
+            let mut head = head.split_whitespace();
+            let (Some(kind), Some(name), None) = (head.next(), head.next(), head.next()) else {
+                return Err(error("expected a binding kind and a name".into()));
+            };
+
+            match kind {
+                "action" => {
+                    let chord = split_chord(binding)
+                        .into_iter()
+                        .map(|trigger| parse_trigger(unquote(trigger.trim())))
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(error)?;
+                    map.bind_action(name, &chord);
+                }
+                "axis" => {
+                    map.bind_axis(name, parse_axis_binding(binding).map_err(error)?);
+                }
+                _ => return Err(error(format!("unknown binding kind `{kind}`"))),
+            }
+        }
+        Ok(map)
+    }
+
+    /// Loads bindings from `path`, or writes `defaults` there and returns them
+    /// when the file doesn't exist yet.
+    pub fn load_or_create(path: &Path, defaults: ActionMap) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Self::parse(&text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                defaults.save(path)?;
+                Ok(defaults)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for ActionMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (action, chords) in &self.actions {
+            for chord in chords {
+                let chord = chord
+                    .iter()
+                    .map(|trigger| quoted(trigger_name(*trigger)))
+                    .collect::<Vec<_>>()
+                    .join("+");
+                writeln!(f, "action {action} = {chord}")?;
+            }
+        }
+        for (axis, bindings) in &self.axes {
+            for binding in bindings {
+                let source = match binding.source {
+                    AxisSource::Trigger(trigger) => quoted(trigger_name(trigger)),
+                    AxisSource::Mouse(MouseAxis::X) => "MouseX".to_string(),
+                    AxisSource::Mouse(MouseAxis::Y) => "MouseY".to_string(),
+                    AxisSource::Mouse(MouseAxis::Wheel) => "MouseWheel".to_string(),
+                    AxisSource::Gamepad(axis) => format!("{GAMEPAD_PREFIX}{}", axis.string()),
+                };
+                write!(f, "axis {axis} = {source}")?;
+                if binding.scale != 1.0 {
+                    write!(f, " scale={}", binding.scale)?;
+                }
+                if binding.dead_zone != 0.0 {
+                    write!(f, " dead_zone={}", binding.dead_zone)?;
+                }
+                if binding.invert {
+                    write!(f, " invert")?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const GAMEPAD_PREFIX: &str = "Pad ";
+
+const MOUSE_BUTTONS: [(&str, MouseButton); 5] = [
+    ("MouseLeft", MouseButton::Left),
+    ("MouseMiddle", MouseButton::Middle),
+    ("MouseRight", MouseButton::Right),
+    ("MouseX1", MouseButton::X1),
+    ("MouseX2", MouseButton::X2),
+];
+
+fn trigger_name(trigger: Trigger) -> String {
+    match trigger {
+        Trigger::Key(scancode) => scancode.name().to_string(),
+        Trigger::MouseButton(button) => MOUSE_BUTTONS
+            .iter()
+            .find(|(_, b)| *b == button)
+            .map_or("MouseUnknown", |(name, _)| name)
+            .to_string(),
+        Trigger::GamepadButton(button) => format!("{GAMEPAD_PREFIX}{}", button.string()),
+    }
+}
+
+/// Quotes names that would otherwise be read as a chord separator or as the
+/// `=` after the binding's name.
+fn quoted(name: String) -> String {
+    if name.contains(['+', '=']) {
+        format!("\"{name}\"")
+    } else {
+        name
+    }
+}
+
+fn unquote(name: &str) -> &str {
+    name.strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+        .unwrap_or(name)
+}
+
+/// Splits a chord on the `+`s outside of quotes.
+fn split_chord(text: &str) -> Vec<&str> {
+    let mut triggers = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (index, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '+' if !quoted => {
+                triggers.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    triggers.push(&text[start..]);
+    triggers
+}
+
+fn parse_trigger(name: &str) -> Result<Trigger, String> {
+    if let Some((_, button)) = MOUSE_BUTTONS.iter().find(|(n, _)| *n == name) {
+        return Ok(Trigger::MouseButton(*button));
+    }
+    if let Some(button) = name.strip_prefix(GAMEPAD_PREFIX) {
+        return GamepadButton::from_string(button)
+            .map(Trigger::GamepadButton)
+            .ok_or_else(|| format!("unknown gamepad button `{button}`"));
+    }
+    Scancode::from_name(name)
+        .map(Trigger::Key)
+        .ok_or_else(|| format!("unknown input `{name}`"))
+}
+
+/// Parses `<source> [scale=<f32>] [dead_zone=<f32>] [invert]`. Key names may
+/// contain spaces ("Left Ctrl"), so options are taken from the end.
+fn parse_axis_binding(text: &str) -> Result<AxisBinding, String> {
+    let mut words: Vec<&str> = text.split_whitespace().collect();
+    let mut scale = 1.0;
+    let mut dead_zone = 0.0;
+    let mut invert = false;
+    while let Some(word) = words.last() {
+        let parse = |value: &str| {
+            value
+                .parse::<f32>()
+                .map_err(|_| format!("invalid number in `{word}`"))
+        };
+        if *word == "invert" {
+            invert = true;
+        } else if let Some(value) = word.strip_prefix("scale=") {
+            scale = parse(value)?;
+        } else if let Some(value) = word.strip_prefix("dead_zone=") {
+            dead_zone = parse(value)?;
+        } else {
+            break;
+        }
+        words.pop();
+    }
+
+    let source = match unquote(&words.join(" ")) {
+        "" => return Err("expected an input".to_string()),
+        "MouseX" => AxisSource::Mouse(MouseAxis::X),
+        "MouseY" => AxisSource::Mouse(MouseAxis::Y),
+        "MouseWheel" => AxisSource::Mouse(MouseAxis::Wheel),
+        name => match name
+            .strip_prefix(GAMEPAD_PREFIX)
+            .and_then(GamepadAxis::from_string)
+        {
+            Some(axis) => AxisSource::Gamepad(axis),
+            None => AxisSource::Trigger(parse_trigger(name)?),
+        },
+    };
+    Ok(AxisBinding {
+        source,
+        scale,
+        invert,
+        dead_zone,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(scancode: Scancode) -> Trigger {
+        Trigger::Key(scancode)
+    }
+
+    #[test]
+    fn bindings_survive_a_save_and_load() {
+        let mut map = ActionMap::new();
+        map.bind_action("jump", &[key(Scancode::Space)])
+            .bind_action("jump", &[Trigger::GamepadButton(GamepadButton::A)])
+            .bind_action("quick_save", &[key(Scancode::LCtrl), key(Scancode::S)])
+            .bind_action("zoom_in", &[key(Scancode::KpPlus)])
+            .bind_action("zoom_in", &[key(Scancode::LCtrl), key(Scancode::Equals)])
+            .bind_action(
+                "zoom_reset",
+                &[key(Scancode::KpEquals), key(Scancode::KpPlus)],
+            )
+            .bind_action("fire", &[Trigger::MouseButton(MouseButton::Left)])
+            .bind_axis(
+                "move_forward",
+                AxisBinding::new(AxisSource::Trigger(key(Scancode::W))),
+            )
+            .bind_axis(
+                "zoom",
+                AxisBinding {
+                    invert: true,
+                    ..AxisBinding::new(AxisSource::Trigger(key(Scancode::KpPlus)))
+                },
+            )
+            .bind_axis(
+                "look_yaw",
+                AxisBinding {
+                    scale: 0.0484,
+                    invert: true,
+                    ..AxisBinding::new(AxisSource::Mouse(MouseAxis::X))
+                },
+            )
+            .bind_axis(
+                "move_right",
+                AxisBinding {
+                    dead_zone: 0.2,
+                    ..AxisBinding::new(AxisSource::Gamepad(GamepadAxis::LeftX))
+                },
+            );
+
+        let text = map.to_string();
+        assert!(text.contains("action zoom_in = \"Keypad +\"\n"), "{text}");
+        assert_eq!(ActionMap::parse(&text), Ok(map));
+    }
+
+    #[test]
+    fn unquoted_equals_is_still_accepted() {
+        let map = ActionMap::parse("action zoom = =\naxis zoom = = invert\n").unwrap();
+
+        let mut expected = ActionMap::new();
+        expected
+            .bind_action("zoom", &[key(Scancode::Equals)])
+            .bind_axis(
+                "zoom",
+                AxisBinding {
+                    invert: true,
+                    ..AxisBinding::new(AxisSource::Trigger(key(Scancode::Equals)))
+                },
+            );
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let error = ActionMap::parse("# bindings\naction jump = Space\naction = \n").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let error = ActionMap::parse("action jump = NoSuchKey").unwrap_err();
+        assert_eq!(error.message, "unknown input `NoSuchKey`");
+    }
+}
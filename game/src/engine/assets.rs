@@ -0,0 +1,335 @@
+//! Background asset loading.
+//!
+//! Worker threads read files through the [`EngineArchive`] and decode them,
+//! images into pixels and meshes into vertices and indices. GL objects can
+//! only be created on the main thread, so the decoded data waits in a queue
+//! that [`AssetLoader::upload`] drains each frame, within a time budget. Until
+//! then an [`Asset`] hands out its placeholder.
+
+use crate::{
+    Texture, TextureCreateInfo,
+    archive::EngineArchive,
+    scene::mesh::{Mesh, MeshData},
+};
+use anyhow::{Result, anyhow};
+use image::RgbaImage;
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    sync::{Arc, Mutex, PoisonError, mpsc},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoaderConfig {
+    pub worker_threads: usize,
+    /// Time per frame spent creating GL objects from decoded assets. At least
+    /// one asset is uploaded per frame, however long it takes.
+    pub upload_budget: Duration,
+}
+
+impl Default for LoaderConfig {
+    fn default() -> Self {
+        // Leave a core for the main thread.
+        let cores = thread::available_parallelism().map_or(2, |cores| cores.get());
+        LoaderConfig {
+            worker_threads: (cores - 1).clamp(1, 4),
+            upload_budget: Duration::from_millis(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadState {
+    Loading,
+    Loaded,
+    /// The placeholder is kept.
+    Failed,
+}
+
+struct Slot<T> {
+    value: Rc<T>,
+    state: LoadState,
+}
+
+/// A shared handle to an asset that may still be loading.
+pub struct Asset<T> {
+    slot: Rc<RefCell<Slot<T>>>,
+}
+
+impl<T> Clone for Asset<T> {
+    fn clone(&self) -> Self {
+        Asset {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<T> Asset<T> {
+    /// An asset that is already loaded.
+    pub fn ready(value: Rc<T>) -> Self {
+        Self::with_state(value, LoadState::Loaded)
+    }
+
+    fn with_state(value: Rc<T>, state: LoadState) -> Self {
+        Asset {
+            slot: Rc::new(RefCell::new(Slot { value, state })),
+        }
+    }
+
+    /// The loaded asset, or the placeholder while loading or after a failure.
+    pub fn get(&self) -> Rc<T> {
+        self.slot.borrow().value.clone()
+    }
+
+    pub fn state(&self) -> LoadState {
+        self.slot.borrow().state
+    }
+}
+
+type Decoded = Box<dyn Any + Send>;
+type DecodeJob = Box<dyn FnOnce(&EngineArchive) -> Result<Decoded> + Send>;
+type UploadJob = Box<dyn FnOnce(Result<Decoded>)>;
+
+/// Loads assets on worker threads and uploads them on the main thread.
+pub struct AssetLoader {
+    /// `None` once the workers are told to stop.
+    jobs: Option<mpsc::Sender<(u64, DecodeJob)>>,
+    /// The upload queue.
+    decoded: mpsc::Receiver<(u64, Result<Decoded>)>,
+    /// Waiting for their decoded data, by job id.
+    uploads: HashMap<u64, UploadJob>,
+    next_job: u64,
+    upload_budget: Duration,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl AssetLoader {
+    pub fn new(archive: Arc<EngineArchive>, config: LoaderConfig) -> Self {
+        let (jobs, job_queue) = mpsc::channel::<(u64, DecodeJob)>();
+        let (decoded_sender, decoded) = mpsc::channel();
+        let job_queue = Arc::new(Mutex::new(job_queue));
+
+        let workers = (0..config.worker_threads.max(1))
+            .map(|index| {
+                let archive = archive.clone();
+                let job_queue = job_queue.clone();
+                let decoded = decoded_sender.clone();
+                thread::Builder::new()
+                    .name(format!("asset loader {index}"))
+                    .spawn(move || {
+                        loop {
+                            // Only hold the lock while waiting, not while decoding.
+                            let job = job_queue
+                                .lock()
+                                .unwrap_or_else(PoisonError::into_inner)
+                                .recv();
+                            let Ok((id, job)) = job else {
+                                break;
+                            };
+                            let result = panic::catch_unwind(AssertUnwindSafe(|| job(&archive)))
+                                .unwrap_or_else(|_| Err(anyhow!("the loader panicked")));
+                            if decoded.send((id, result)).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                    .expect("Failed to spawn an asset loader thread")
+            })
+            .collect();
+
+        AssetLoader {
+            jobs: Some(jobs),
+            decoded,
+            uploads: HashMap::new(),
+            next_job: 0,
+            upload_budget: config.upload_budget,
+            workers,
+        }
+    }
+
+    /// Reads `path` and runs `decode` on a worker thread, then `upload` on the
+    /// main thread during [`Self::upload`]. The asset is `placeholder` until
+    /// then, and stays `placeholder` if either step fails.
+    pub fn load<D, T>(
+        &mut self,
+        path: &str,
+        placeholder: Rc<T>,
+        decode: impl FnOnce(Vec<u8>) -> Result<D> + Send + 'static,
+        upload: impl FnOnce(D) -> Result<T> + 'static,
+    ) -> Asset<T>
+    where
+        D: Send + 'static,
+        T: 'static,
+    {
+        let asset = Asset::with_state(placeholder, LoadState::Loading);
+        let id = self.next_job;
+        self.next_job += 1;
+
+        let slot = asset.slot.clone();
+        let failed_path = path.to_string();
+        self.uploads.insert(
+            id,
+            Box::new(move |decoded: Result<Decoded>| {
+                let uploaded = decoded.and_then(|decoded| {
+                    let decoded = decoded
+                        .downcast::<D>()
+                        .expect("decoded data has the type the job produced");
+                    upload(*decoded)
+                });
+                let mut slot = slot.borrow_mut();
+                match uploaded {
+                    Ok(value) => {
+                        slot.value = Rc::new(value);
+                        slot.state = LoadState::Loaded;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to load {failed_path}: {e:#}");
+                        slot.state = LoadState::Failed;
+                    }
+                }
+            }),
+        );
+
+        let path = path.to_string();
+        let job: DecodeJob = Box::new(move |archive| {
+            let bytes = archive.load(&path)?;
+            Ok(Box::new(decode(bytes)?) as Decoded)
+        });
+        self.jobs
+            .as_ref()
+            .expect("the loader is running")
+            .send((id, job))
+            .expect("the asset loader threads are running");
+
+        asset
+    }
+
+    /// Decodes an image into RGBA8 off-thread and creates the texture from
+    /// `create_info`'s result.
+    pub fn load_texture(
+        &mut self,
+        path: &str,
+        placeholder: Rc<Texture>,
+        create_info: impl FnOnce(RgbaImage) -> TextureCreateInfo + 'static,
+    ) -> Asset<Texture> {
+        self.load(
+            path,
+            placeholder,
+            |bytes| Ok(image::load_from_memory(&bytes)?.into_rgba8()),
+            |rgba_image| Ok(Texture::from(create_info(rgba_image))),
+        )
+    }
+
+    /// Decodes a Wavefront OBJ file (see [`MeshData::from_obj`]) off-thread
+    /// and creates its buffers.
+    pub fn load_mesh(&mut self, path: &str, placeholder: Rc<Mesh>) -> Asset<Mesh> {
+        self.load(
+            path,
+            placeholder,
+            |bytes| MeshData::from_obj(std::str::from_utf8(&bytes)?),
+            |data| Ok(Mesh::new(data.vertices, data.polygons)),
+        )
+    }
+
+    /// Number of assets not uploaded yet.
+    pub fn pending(&self) -> usize {
+        self.uploads.len()
+    }
+
+    /// Uploads decoded assets until the frame's budget is spent.
+    pub fn upload(&mut self) {
+        let start = Instant::now();
+        while let Ok((id, decoded)) = self.decoded.try_recv() {
+            if let Some(upload) = self.uploads.remove(&id) {
+                upload(decoded);
+            }
+            if start.elapsed() >= self.upload_budget {
+                break;
+            }
+        }
+    }
+}
+
+impl Drop for AssetLoader {
+    /// Lets the workers finish their current job and joins them. Queued jobs
+    /// are dropped.
+    fn drop(&mut self) {
+        self.jobs = None;
+        // Dropping the receiver makes the workers' next send fail, which stops
+        // them instead of letting them work through the queue.
+        self.decoded = mpsc::channel().1;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::mesh::{Polygon, Vertex};
+    use std::{fs, path::PathBuf};
+
+    fn triangle() -> Rc<Mesh> {
+        let vertex = Vertex {
+            position: [0.0; 3],
+            color: [1.0; 3],
+            tex_coords: [0.0; 2],
+        };
+        Rc::new(Mesh::new(
+            vec![vertex; 3],
+            vec![Polygon { indices: [0, 1, 2] }],
+        ))
+    }
+
+    /// Uploads until every asset has loaded or failed.
+    fn finish(loader: &mut AssetLoader) {
+        while loader.pending() > 0 {
+            loader.upload();
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn meshes_load_in_the_background() {
+        gl::mock::install();
+        let dir: PathBuf =
+            std::env::temp_dir().join(format!("asset_loader_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("quad.obj"),
+            "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 4\n",
+        )
+        .unwrap();
+        fs::write(dir.join("broken.obj"), "f 1 2 3\n").unwrap();
+        let archive = Arc::new(EngineArchive::new(std::slice::from_ref(&dir)).unwrap());
+
+        let mut loader = AssetLoader::new(archive, LoaderConfig::default());
+        let placeholder = triangle();
+        let quad = loader.load_mesh("quad.obj", placeholder.clone());
+        let broken = loader.load_mesh("broken.obj", placeholder.clone());
+        let missing = loader.load_mesh("missing.obj", placeholder.clone());
+        assert_eq!(quad.state(), LoadState::Loading);
+        assert!(Rc::ptr_eq(&quad.get(), &placeholder));
+
+        gl::mock::take_calls();
+        finish(&mut loader);
+
+        assert_eq!(quad.state(), LoadState::Loaded);
+        assert!(!Rc::ptr_eq(&quad.get(), &placeholder));
+        // Only the quad got buffers for its vertices and indices.
+        assert_eq!(gl::mock::calls_named("NamedBufferStorage").len(), 2);
+
+        for failed in [broken, missing] {
+            assert_eq!(failed.state(), LoadState::Failed);
+            assert!(Rc::ptr_eq(&failed.get(), &placeholder));
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,141 @@
+use crate::window::VSync;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PresentationConfig {
+    pub vsync: VSync,
+    /// Frames per second; `None` runs as fast as vsync allows.
+    pub fps_cap: Option<f32>,
+}
+
+impl Default for PresentationConfig {
+    fn default() -> Self {
+        PresentationConfig {
+            vsync: VSync::On,
+            fps_cap: None,
+        }
+    }
+}
+
+/// Waits out the rest of each frame when an FPS cap is set.
+///
+/// `thread::sleep` can overshoot by a millisecond or more, so it only sleeps
+/// until [`Self::SPIN_MARGIN`] before the deadline and spins for the rest.
+pub struct FrameLimiter {
+    interval: Option<Duration>,
+    next_frame: Instant,
+}
+
+impl FrameLimiter {
+    const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+    pub fn new(fps_cap: Option<f32>) -> Self {
+        let mut limiter = FrameLimiter {
+            interval: None,
+            next_frame: Instant::now(),
+        };
+        limiter.set_fps_cap(fps_cap);
+        limiter
+    }
+
+    pub fn set_fps_cap(&mut self, fps_cap: Option<f32>) {
+        self.interval = fps_cap
+            .filter(|fps| *fps > 0.0)
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+        self.next_frame = Instant::now();
+    }
+
+    pub fn wait(&mut self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        let now = Instant::now();
+        if self.next_frame > now {
+            let remaining = self.next_frame - now;
+            if remaining > Self::SPIN_MARGIN {
+                std::thread::sleep(remaining - Self::SPIN_MARGIN);
+            }
+            while Instant::now() < self.next_frame {
+                std::hint::spin_loop();
+            }
+            self.next_frame += interval;
+        } else {
+            // Running behind: don't try to catch up with a burst of frames.
+            self.next_frame = now + interval;
+        }
+    }
+}
+
+/// Frame times collected over roughly one second.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramePacing {
+    pub fps: f32,
+    pub average: Duration,
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl std::fmt::Display for FramePacing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.0} fps, {:.2} ms (min {:.2}, max {:.2})",
+            self.fps,
+            self.average.as_secs_f64() * 1000.0,
+            self.min.as_secs_f64() * 1000.0,
+            self.max.as_secs_f64() * 1000.0,
+        )
+    }
+}
+
+pub struct FrameStats {
+    last_frame: Instant,
+    window_start: Instant,
+    frames: u32,
+    min: Duration,
+    max: Duration,
+}
+
+impl FrameStats {
+    const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn new() -> Self {
+        let now = Instant::now();
+        FrameStats {
+            last_frame: now,
+            window_start: now,
+            frames: 0,
+            min: Duration::MAX,
+            max: Duration::ZERO,
+        }
+    }
+
+    /// Records a presented frame. Returns the pacing since the last report
+    /// once every [`Self::REPORT_INTERVAL`].
+    pub fn record(&mut self) -> Option<FramePacing> {
+        let now = Instant::now();
+        let frame_time = now - self.last_frame;
+        self.last_frame = now;
+        self.frames += 1;
+        self.min = self.min.min(frame_time);
+        self.max = self.max.max(frame_time);
+
+        let elapsed = now - self.window_start;
+        if elapsed < Self::REPORT_INTERVAL {
+            return None;
+        }
+
+        let pacing = FramePacing {
+            fps: self.frames as f32 / elapsed.as_secs_f32(),
+            average: elapsed / self.frames,
+            min: self.min,
+            max: self.max,
+        };
+        self.window_start = now;
+        self.frames = 0;
+        self.min = Duration::MAX;
+        self.max = Duration::ZERO;
+        Some(pacing)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    archive::EngineArchive,
+    engine::{assets::AssetLoader, gamepad::Gamepads, input::Input},
+    shader_program::ShaderReloader,
+    window,
+};
+use anyhow::Result;
+use sdl2::event::Event;
+
+/// What a [`Game`] can reach from its hooks.
+pub struct Context<'a> {
+    pub archive: &'a EngineArchive,
+    pub assets: &'a mut AssetLoader,
+    pub input: &'a Input,
+    pub gamepads: &'a Gamepads,
+    pub window: &'a window::KWindow,
+    /// Only set in debug builds; see [`ShaderReloader`].
+    pub shader_reloader: Option<&'a mut ShaderReloader>,
+    exit: bool,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(
+        archive: &'a EngineArchive,
+        assets: &'a mut AssetLoader,
+        input: &'a Input,
+        gamepads: &'a Gamepads,
+        window: &'a window::KWindow,
+        shader_reloader: Option<&'a mut ShaderReloader>,
+    ) -> Self {
+        Context {
+            archive,
+            assets,
+            input,
+            gamepads,
+            window,
+            shader_reloader,
+            exit: false,
+        }
+    }
+
+    /// Stops [`crate::engine::KEngine::run`] after the current frame.
+    pub fn exit(&mut self) {
+        self.exit = true;
+    }
+
+    pub(crate) fn exit_requested(&self) -> bool {
+        self.exit
+    }
+}
+
+/// The application [`crate::engine::KEngine`] drives.
+///
+/// Each frame the engine forwards the frame's events to [`Self::on_event`],
+/// runs zero or more fixed-length [`Self::update`] ticks and then calls
+/// [`Self::render`].
+pub trait Game {
+    /// Called once the GL context is current.
+    fn init(context: &mut Context) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Advances the simulation by one tick of `delta_time` seconds.
+    fn update(&mut self, context: &mut Context, delta_time: f32);
+
+    /// Draws into the cleared default framebuffer. `alpha` in `[0, 1]` is how
+    /// far the frame is between the previous tick and the current one.
+    fn render(&self, alpha: f32);
+
+    fn on_event(&mut self, _context: &mut Context, _event: &Event) {}
+
+    /// Called after init and whenever the default framebuffer changes size.
+    /// The viewport has already been updated.
+    fn resize(&mut self, _width: u32, _height: u32) {}
+}
@@ -0,0 +1,58 @@
+use crate::engine::input::Event;
+use sdl2::{GameControllerSubsystem, controller::GameController};
+use std::{cell::RefCell, collections::BTreeMap, time::Duration};
+
+/// Opens game controllers as they are plugged in and closes them when they
+/// are removed. Their state is read through [`crate::engine::input::Input`].
+pub struct Gamepads {
+    subsystem: GameControllerSubsystem,
+    /// Keyed by joystick instance id. Rumble needs `&mut GameController`.
+    controllers: RefCell<BTreeMap<u32, GameController>>,
+}
+
+impl Gamepads {
+    pub(crate) fn new(subsystem: GameControllerSubsystem) -> Self {
+        Gamepads {
+            subsystem,
+            controllers: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    /// SDL sends a `ControllerDeviceAdded` for every controller connected at
+    /// startup too, so this is the only place controllers are opened.
+    pub(crate) fn handle_events<'e>(&mut self, events: impl Iterator<Item = &'e Event>) {
+        let controllers = self.controllers.get_mut();
+        for event in events {
+            match event {
+                Event::ControllerDeviceAdded { which, .. } => match self.subsystem.open(*which) {
+                    Ok(controller) => {
+                        controllers.insert(controller.instance_id(), controller);
+                    }
+                    Err(e) => eprintln!("Failed to open game controller {which}: {e}"),
+                },
+                Event::ControllerDeviceRemoved { which, .. } => {
+                    controllers.remove(which);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Instance ids of the connected gamepads, for
+    /// [`crate::engine::input::Input::gamepad_axis`] and friends.
+    pub fn ids(&self) -> Vec<u32> {
+        self.controllers.borrow().keys().copied().collect()
+    }
+
+    /// Strengths are in `[0, 1]`. Does nothing for controllers without rumble.
+    pub fn rumble(&self, id: u32, low_frequency: f32, high_frequency: f32, duration: Duration) {
+        let strength = |value: f32| (value.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        if let Some(controller) = self.controllers.borrow_mut().get_mut(&id) {
+            let _ = controller.set_rumble(
+                strength(low_frequency),
+                strength(high_frequency),
+                duration.as_millis().min(u32::MAX as u128) as u32,
+            );
+        }
+    }
+}
@@ -1,16 +1,46 @@
+pub use sdl2::controller::{Axis as GamepadAxis, Button as GamepadButton};
 pub use sdl2::event::Event;
 pub use sdl2::keyboard::Scancode;
 pub use sdl2::mouse::MouseButton;
-use std::rc::Rc;
+use sdl2::mouse::MouseWheelDirection;
+use std::{collections::BTreeMap, rc::Rc};
+
+#[derive(Debug, Clone, Default)]
+struct GamepadState {
+    buttons_down: u32,
+    buttons_pressed: u32,
+    axes: [i16; 6],
+}
+
+/// Text being composed by an input method, not yet committed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Composition {
+    pub text: String,
+    /// Cursor position in characters.
+    pub cursor: i32,
+    /// Length of the selection after the cursor, in characters.
+    pub selection_length: i32,
+}
 
 #[derive(Debug, Clone)]
 pub struct Input {
     keys_down: [bool; 512],
     keys_pressed: [bool; 512],
+    keys_released: [bool; 512],
+    keys_repeated: [bool; 512],
     mouse_down: u8,
     mouse_pressed: u8,
+    mouse_released: u8,
+    mouse_double_clicked: u8,
     mouse_pos: (i32, i32),
     mouse_rel: (i32, i32),
+    mouse_wheel: (f32, f32),
+    text: String,
+    composition: Option<Composition>,
+    /// Keyed by joystick instance id.
+    gamepads: BTreeMap<u32, GamepadState>,
+    stick_dead_zone: f32,
+    trigger_dead_zone: f32,
     events: Rc<[Event]>,
     pub(crate) exit: bool,
 }
@@ -20,20 +50,28 @@ impl Input {
         Input {
             keys_down: [false; 512],
             keys_pressed: [false; 512],
+            keys_released: [false; 512],
+            keys_repeated: [false; 512],
             mouse_down: 0,
             mouse_pressed: 0,
+            mouse_released: 0,
+            mouse_double_clicked: 0,
             mouse_pos: (0, 0),
             mouse_rel: (0, 0),
+            mouse_wheel: (0.0, 0.0),
+            text: String::new(),
+            composition: None,
+            gamepads: BTreeMap::new(),
+            stick_dead_zone: 0.2,
+            trigger_dead_zone: 0.05,
             events: Rc::new([]),
             exit: false,
         }
     }
 
+    /// Adds a frame's events. Presses and mouse motion accumulate until
+    /// [`Self::end_tick`], so frames that run no tick don't lose them.
     pub(crate) fn update(&mut self, events: impl Iterator<Item = Event>) {
-        self.mouse_rel = (0, 0);
-        self.keys_pressed = [false; 512];
-        self.mouse_pressed = 0;
-
         let events = events
             .inspect(|event| match event {
                 Event::Quit { .. } => self.exit = true,
@@ -43,6 +81,27 @@ impl Input {
                     self.mouse_pos = (*x, *y);
                     self.mouse_rel = (self.mouse_rel.0 + *xrel, self.mouse_rel.1 + *yrel);
                 }
+                Event::MouseWheel {
+                    direction,
+                    precise_x,
+                    precise_y,
+                    ..
+                } => {
+                    let sign = if *direction == MouseWheelDirection::Flipped {
+                        -1.0
+                    } else {
+                        1.0
+                    };
+                    self.mouse_wheel.0 += precise_x * sign;
+                    self.mouse_wheel.1 += precise_y * sign;
+                }
+                Event::KeyDown {
+                    scancode: Some(scancode),
+                    repeat: true,
+                    ..
+                } => {
+                    self.keys_repeated[*scancode as usize] = true;
+                }
                 Event::KeyDown {
                     scancode: Some(scancode),
                     ..
@@ -54,20 +113,77 @@ impl Input {
                     scancode: Some(scancode),
                     ..
                 } => {
+                    self.keys_released[*scancode as usize] = true;
                     self.keys_down[*scancode as usize] = false;
                 }
-                Event::MouseButtonDown { mouse_btn, .. } => {
+                Event::MouseButtonDown {
+                    mouse_btn, clicks, ..
+                } => {
                     self.mouse_pressed |= 1 << (*mouse_btn as u8);
                     self.mouse_down |= 1 << (*mouse_btn as u8);
+                    if *clicks == 2 {
+                        self.mouse_double_clicked |= 1 << (*mouse_btn as u8);
+                    }
                 }
                 Event::MouseButtonUp { mouse_btn, .. } => {
+                    self.mouse_released |= 1 << (*mouse_btn as u8);
                     self.mouse_down &= !(1 << (*mouse_btn as u8));
                 }
+                Event::TextInput { text, .. } => {
+                    self.text.push_str(text);
+                    self.composition = None;
+                }
+                Event::TextEditing {
+                    text,
+                    start,
+                    length,
+                    ..
+                } => {
+                    self.composition = (!text.is_empty()).then(|| Composition {
+                        text: text.clone(),
+                        cursor: *start,
+                        selection_length: *length,
+                    });
+                }
+                Event::ControllerDeviceRemoved { which, .. } => {
+                    self.gamepads.remove(which);
+                }
+                Event::ControllerButtonDown { which, button, .. } => {
+                    let gamepad = self.gamepads.entry(*which).or_default();
+                    gamepad.buttons_pressed |= 1 << (*button as u32);
+                    gamepad.buttons_down |= 1 << (*button as u32);
+                }
+                Event::ControllerButtonUp { which, button, .. } => {
+                    let gamepad = self.gamepads.entry(*which).or_default();
+                    gamepad.buttons_down &= !(1 << (*button as u32));
+                }
+                Event::ControllerAxisMotion {
+                    which, axis, value, ..
+                } => {
+                    self.gamepads.entry(*which).or_default().axes[*axis as usize] = *value;
+                }
                 _ => {}
             })
             .collect();
         self.events = events;
     }
+
+    /// Clears presses, releases, mouse motion and committed text once a
+    /// simulation tick has seen them.
+    pub(crate) fn end_tick(&mut self) {
+        self.mouse_rel = (0, 0);
+        self.mouse_wheel = (0.0, 0.0);
+        self.keys_pressed = [false; 512];
+        self.keys_released = [false; 512];
+        self.keys_repeated = [false; 512];
+        self.mouse_pressed = 0;
+        self.mouse_released = 0;
+        self.mouse_double_clicked = 0;
+        self.text.clear();
+        for gamepad in self.gamepads.values_mut() {
+            gamepad.buttons_pressed = 0;
+        }
+    }
 }
 
 impl Input {
@@ -75,10 +191,21 @@ impl Input {
         self.keys_down[scancode as usize]
     }
 
+    /// Ignores the OS's key repeats; see [`Self::was_key_repeated`].
     pub fn was_key_pressed(&self, scancode: Scancode) -> bool {
         self.keys_pressed[scancode as usize]
     }
 
+    pub fn was_key_released(&self, scancode: Scancode) -> bool {
+        self.keys_released[scancode as usize]
+    }
+
+    /// Whether the OS sent a key repeat for a held key, for things like
+    /// holding backspace in a text field.
+    pub fn was_key_repeated(&self, scancode: Scancode) -> bool {
+        self.keys_repeated[scancode as usize]
+    }
+
     pub fn is_mouse_down(&self, button: MouseButton) -> bool {
         (self.mouse_down & (1 << (button as u8))) != 0
     }
@@ -87,6 +214,32 @@ impl Input {
         (self.mouse_pressed & (1 << (button as u8))) != 0
     }
 
+    pub fn was_mouse_released(&self, button: MouseButton) -> bool {
+        (self.mouse_released & (1 << (button as u8))) != 0
+    }
+
+    /// Uses the OS's double-click time and distance. The first click of the
+    /// pair also counts as a press.
+    pub fn was_mouse_double_clicked(&self, button: MouseButton) -> bool {
+        (self.mouse_double_clicked & (1 << (button as u8))) != 0
+    }
+
+    /// Scroll since the last tick. Positive y is away from the user and
+    /// positive x is to the right, whatever the OS's natural scrolling setting.
+    pub fn mouse_wheel(&self) -> (f32, f32) {
+        self.mouse_wheel
+    }
+
+    /// UTF-8 text typed since the last tick. Only collected while text input
+    /// is enabled with [`crate::window::KWindow::set_text_input`].
+    pub fn text_input(&self) -> &str {
+        &self.text
+    }
+
+    pub fn composition(&self) -> Option<&Composition> {
+        self.composition.as_ref()
+    }
+
     pub fn mouse_pos(&self) -> (i32, i32) {
         self.mouse_pos
     }
@@ -95,7 +248,190 @@ impl Input {
         self.mouse_rel
     }
 
+    /// Instance ids of the gamepads that have sent input since they were
+    /// connected. [`crate::engine::Gamepads`] lists every connected one.
+    pub fn gamepad_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.gamepads.keys().copied()
+    }
+
+    pub fn is_gamepad_button_down(&self, id: u32, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|gamepad| (gamepad.buttons_down & (1 << (button as u32))) != 0)
+    }
+
+    pub fn was_gamepad_button_pressed(&self, id: u32, button: GamepadButton) -> bool {
+        self.gamepads
+            .get(&id)
+            .is_some_and(|gamepad| (gamepad.buttons_pressed & (1 << (button as u32))) != 0)
+    }
+
+    /// Sticks are in `[-1, 1]` with +y pointing down, triggers in `[0, 1]`.
+    /// Values inside the dead zone read as 0 and the rest is rescaled to the
+    /// full range. Stick dead zones are radial, so the two axes of a stick are
+    /// judged together.
+    pub fn gamepad_axis(&self, id: u32, axis: GamepadAxis) -> f32 {
+        let Some(gamepad) = self.gamepads.get(&id) else {
+            return 0.0;
+        };
+        let normalize =
+            |axis: GamepadAxis| (gamepad.axes[axis as usize] as f32 / 32767.0).max(-1.0);
+        let rescale = |value: f32, dead_zone: f32| {
+            if value <= dead_zone {
+                0.0
+            } else {
+                ((value - dead_zone) / (1.0 - dead_zone)).min(1.0)
+            }
+        };
+
+        let (x, y) = match axis {
+            GamepadAxis::TriggerLeft | GamepadAxis::TriggerRight => {
+                return rescale(normalize(axis), self.trigger_dead_zone);
+            }
+            GamepadAxis::LeftX | GamepadAxis::LeftY => (GamepadAxis::LeftX, GamepadAxis::LeftY),
+            GamepadAxis::RightX | GamepadAxis::RightY => (GamepadAxis::RightX, GamepadAxis::RightY),
+        };
+        let magnitude = normalize(x).hypot(normalize(y));
+        if magnitude == 0.0 {
+            return 0.0;
+        }
+        normalize(axis) * rescale(magnitude, self.stick_dead_zone) / magnitude
+    }
+
+    pub fn set_gamepad_dead_zones(&mut self, stick: f32, trigger: f32) {
+        assert!(
+            (0.0..1.0).contains(&stick) && (0.0..1.0).contains(&trigger),
+            "dead zones must be in [0, 1)"
+        );
+        self.stick_dead_zone = stick;
+        self.trigger_dead_zone = trigger;
+    }
+
     pub fn events(&self) -> impl Iterator<Item = &Event> {
         self.events.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdl2::{keyboard::Mod, mouse::MouseState};
+
+    fn key(scancode: Scancode, down: bool) -> Event {
+        if down {
+            Event::KeyDown {
+                timestamp: 0,
+                window_id: 0,
+                keycode: None,
+                scancode: Some(scancode),
+                keymod: Mod::NOMOD,
+                repeat: false,
+            }
+        } else {
+            Event::KeyUp {
+                timestamp: 0,
+                window_id: 0,
+                keycode: None,
+                scancode: Some(scancode),
+                keymod: Mod::NOMOD,
+                repeat: false,
+            }
+        }
+    }
+
+    fn axis(axis: GamepadAxis, value: i16) -> Event {
+        Event::ControllerAxisMotion {
+            timestamp: 0,
+            which: 1,
+            axis,
+            value,
+        }
+    }
+
+    #[test]
+    fn releases_and_double_clicks_last_until_the_tick_ends() {
+        let mut input = Input::new();
+        input.update(
+            [
+                key(Scancode::A, true),
+                key(Scancode::A, false),
+                Event::MouseButtonDown {
+                    timestamp: 0,
+                    window_id: 0,
+                    which: 0,
+                    mouse_btn: MouseButton::Left,
+                    clicks: 2,
+                    x: 10,
+                    y: 20,
+                },
+                Event::MouseButtonUp {
+                    timestamp: 0,
+                    window_id: 0,
+                    which: 0,
+                    mouse_btn: MouseButton::Left,
+                    clicks: 2,
+                    x: 10,
+                    y: 20,
+                },
+                Event::MouseMotion {
+                    timestamp: 0,
+                    window_id: 0,
+                    which: 0,
+                    mousestate: MouseState::from_sdl_state(0),
+                    x: 12,
+                    y: 18,
+                    xrel: 2,
+                    yrel: -2,
+                },
+            ]
+            .into_iter(),
+        );
+
+        assert!(input.was_key_pressed(Scancode::A));
+        assert!(input.was_key_released(Scancode::A));
+        assert!(!input.is_key_down(Scancode::A));
+        assert!(input.was_mouse_pressed(MouseButton::Left));
+        assert!(input.was_mouse_double_clicked(MouseButton::Left));
+        assert!(input.was_mouse_released(MouseButton::Left));
+        assert!(!input.was_mouse_released(MouseButton::Right));
+        assert!(!input.is_mouse_down(MouseButton::Left));
+        assert_eq!(input.mouse_pos(), (12, 18));
+
+        input.end_tick();
+        assert!(!input.was_key_released(Scancode::A));
+        assert!(!input.was_mouse_double_clicked(MouseButton::Left));
+        assert!(!input.was_mouse_released(MouseButton::Left));
+        // The position is where the cursor is, not a per-tick delta.
+        assert_eq!(input.mouse_pos(), (12, 18));
+        assert_eq!(input.mouse_rel(), (0, 0));
+    }
+
+    #[test]
+    fn dead_zones_rescale_the_rest_of_the_range() {
+        let mut input = Input::new();
+        input.update(
+            [
+                axis(GamepadAxis::LeftX, 16384),
+                axis(GamepadAxis::TriggerLeft, 16383),
+            ]
+            .into_iter(),
+        );
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(close(
+            input.gamepad_axis(1, GamepadAxis::TriggerLeft),
+            (0.5 - 0.05) / 0.95
+        ));
+
+        input.set_gamepad_dead_zones(0.25, 0.5);
+        assert!(close(input.gamepad_axis(1, GamepadAxis::LeftX), 1.0 / 3.0));
+        assert_eq!(input.gamepad_axis(1, GamepadAxis::LeftY), 0.0);
+        assert_eq!(input.gamepad_axis(1, GamepadAxis::TriggerLeft), 0.0);
+        assert_eq!(input.gamepad_axis(2, GamepadAxis::LeftX), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "dead zones must be in [0, 1)")]
+    fn dead_zones_below_one() {
+        Input::new().set_gamepad_dead_zones(1.0, 0.0);
+    }
+}
@@ -0,0 +1,654 @@
+//! Records the input events of each frame to a file and plays them back.
+//!
+//! A recording is `MAGIC`, then one entry per frame: the frame time in
+//! nanoseconds as a little-endian `u64`, the event count as a `u32` and the
+//! events, each a tag byte followed by its fields. Only events
+//! [`super::input::Input`] reads are stored; window and quit events still come
+//! from SDL during playback.
+//!
+//! The engine advances its fixed timestep by the recorded frame times, so a
+//! replay runs the same ticks with the same input as the recorded session.
+
+use crate::engine::input::{Event, GamepadAxis, GamepadButton, MouseButton, Scancode};
+use sdl2::{
+    keyboard::{Keycode, Mod},
+    mouse::{MouseState, MouseWheelDirection},
+};
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+use thiserror::Error;
+
+const MAGIC: &[u8; 5] = b"KINP2";
+
+const KEY_DOWN: u8 = 0;
+const KEY_UP: u8 = 1;
+const MOUSE_MOTION: u8 = 2;
+const MOUSE_BUTTON_DOWN: u8 = 3;
+const MOUSE_BUTTON_UP: u8 = 4;
+const GAMEPAD_BUTTON_DOWN: u8 = 5;
+const GAMEPAD_BUTTON_UP: u8 = 6;
+const GAMEPAD_AXIS: u8 = 7;
+const GAMEPAD_REMOVED: u8 = 8;
+const MOUSE_WHEEL: u8 = 9;
+const TEXT_INPUT: u8 = 10;
+const TEXT_EDITING: u8 = 11;
+
+/// In SDL's numbering, so `GAMEPAD_BUTTONS[button as usize] == button`.
+const GAMEPAD_BUTTONS: [GamepadButton; 21] = [
+    GamepadButton::A,
+    GamepadButton::B,
+    GamepadButton::X,
+    GamepadButton::Y,
+    GamepadButton::Back,
+    GamepadButton::Guide,
+    GamepadButton::Start,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+    GamepadButton::Misc1,
+    GamepadButton::Paddle1,
+    GamepadButton::Paddle2,
+    GamepadButton::Paddle3,
+    GamepadButton::Paddle4,
+    GamepadButton::Touchpad,
+];
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftX,
+    GamepadAxis::LeftY,
+    GamepadAxis::RightX,
+    GamepadAxis::RightY,
+    GamepadAxis::TriggerLeft,
+    GamepadAxis::TriggerRight,
+];
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("Failed to read input recording: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Not an input recording")]
+    InvalidMagic,
+
+    #[error("Input recording is truncated")]
+    Truncated,
+
+    #[error("Unknown event tag {tag} in frame {frame}")]
+    UnknownEvent { frame: usize, tag: u8 },
+
+    #[error("Invalid {what} {value} in frame {frame}")]
+    InvalidValue {
+        frame: usize,
+        what: &'static str,
+        value: i64,
+    },
+}
+
+pub struct InputRecorder {
+    writer: BufWriter<fs::File>,
+}
+
+impl InputRecorder {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        writer.write_all(MAGIC)?;
+        Ok(InputRecorder { writer })
+    }
+
+    pub fn record_frame(&mut self, frame_time: Duration, events: &[Event]) -> io::Result<()> {
+        let mut encoded = Vec::new();
+        let mut count: u32 = 0;
+        for event in events {
+            if encode_event(event, &mut encoded) {
+                count += 1;
+            }
+        }
+
+        let nanos = frame_time.as_nanos().min(u64::MAX as u128) as u64;
+        self.writer.write_all(&nanos.to_le_bytes())?;
+        self.writer.write_all(&count.to_le_bytes())?;
+        self.writer.write_all(&encoded)
+    }
+
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+pub struct InputFrame {
+    pub frame_time: Duration,
+    pub events: Vec<Event>,
+}
+
+/// A recording loaded into memory, handed out one frame at a time.
+pub struct InputReplay {
+    frames: std::vec::IntoIter<InputFrame>,
+}
+
+impl InputReplay {
+    pub fn load(path: &Path) -> Result<Self, ReplayError> {
+        Self::parse(&fs::read(path)?)
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader {
+            bytes: bytes.strip_prefix(MAGIC).ok_or(ReplayError::InvalidMagic)?,
+        };
+
+        let mut frames = Vec::new();
+        while !reader.bytes.is_empty() {
+            let frame_time = Duration::from_nanos(reader.u64()?);
+            let count = reader.u32()?;
+            let events = (0..count)
+                .map(|_| decode_event(&mut reader, frames.len()))
+                .collect::<Result<_, _>>()?;
+            frames.push(InputFrame { frame_time, events });
+        }
+        Ok(InputReplay {
+            frames: frames.into_iter(),
+        })
+    }
+
+    /// `None` once every frame has been played.
+    pub fn next_frame(&mut self) -> Option<InputFrame> {
+        self.frames.next()
+    }
+}
+
+/// Whether `event` is stored in recordings and replaced during playback.
+pub(crate) fn is_recorded(event: &Event) -> bool {
+    encode_event(event, &mut Vec::new())
+}
+
+/// Appends `event` and returns `true` if it is one [`super::input::Input`]
+/// uses.
+fn encode_event(event: &Event, out: &mut Vec<u8>) -> bool {
+    match event {
+        Event::KeyDown {
+            scancode: Some(scancode),
+            repeat,
+            ..
+        } => {
+            out.push(KEY_DOWN);
+            out.extend((*scancode as u16).to_le_bytes());
+            out.push(*repeat as u8);
+        }
+        Event::KeyUp {
+            scancode: Some(scancode),
+            ..
+        } => {
+            out.push(KEY_UP);
+            out.extend((*scancode as u16).to_le_bytes());
+        }
+        Event::MouseMotion {
+            mousestate,
+            x,
+            y,
+            xrel,
+            yrel,
+            ..
+        } => {
+            out.push(MOUSE_MOTION);
+            out.extend(mousestate.to_sdl_state().to_le_bytes());
+            for value in [x, y, xrel, yrel] {
+                out.extend(value.to_le_bytes());
+            }
+        }
+        Event::MouseButtonDown {
+            mouse_btn,
+            clicks,
+            x,
+            y,
+            ..
+        }
+        | Event::MouseButtonUp {
+            mouse_btn,
+            clicks,
+            x,
+            y,
+            ..
+        } => {
+            let down = matches!(event, Event::MouseButtonDown { .. });
+            out.push(if down {
+                MOUSE_BUTTON_DOWN
+            } else {
+                MOUSE_BUTTON_UP
+            });
+            out.push(*mouse_btn as u8);
+            out.push(*clicks);
+            out.extend(x.to_le_bytes());
+            out.extend(y.to_le_bytes());
+        }
+        Event::ControllerButtonDown { which, button, .. }
+        | Event::ControllerButtonUp { which, button, .. } => {
+            let down = matches!(event, Event::ControllerButtonDown { .. });
+            out.push(if down {
+                GAMEPAD_BUTTON_DOWN
+            } else {
+                GAMEPAD_BUTTON_UP
+            });
+            out.extend(which.to_le_bytes());
+            out.push(*button as u8);
+        }
+        Event::ControllerAxisMotion {
+            which, axis, value, ..
+        } => {
+            out.push(GAMEPAD_AXIS);
+            out.extend(which.to_le_bytes());
+            out.push(*axis as u8);
+            out.extend(value.to_le_bytes());
+        }
+        Event::ControllerDeviceRemoved { which, .. } => {
+            out.push(GAMEPAD_REMOVED);
+            out.extend(which.to_le_bytes());
+        }
+        Event::MouseWheel {
+            direction,
+            precise_x,
+            precise_y,
+            ..
+        } => {
+            out.push(MOUSE_WHEEL);
+            out.extend(direction.to_ll().to_le_bytes());
+            out.extend(precise_x.to_le_bytes());
+            out.extend(precise_y.to_le_bytes());
+        }
+        Event::TextInput { text, .. } => {
+            out.push(TEXT_INPUT);
+            encode_string(text, out);
+        }
+        Event::TextEditing {
+            text,
+            start,
+            length,
+            ..
+        } => {
+            out.push(TEXT_EDITING);
+            encode_string(text, out);
+            out.extend(start.to_le_bytes());
+            out.extend(length.to_le_bytes());
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn encode_string(text: &str, out: &mut Vec<u8>) {
+    out.extend((text.len() as u32).to_le_bytes());
+    out.extend(text.as_bytes());
+}
+
+fn decode_event(reader: &mut Reader, frame: usize) -> Result<Event, ReplayError> {
+    let invalid = |what, value: i64| ReplayError::InvalidValue { frame, what, value };
+    let scancode = |reader: &mut Reader| {
+        let value = reader.u16()?;
+        Scancode::from_i32(value as i32).ok_or(invalid("scancode", value as i64))
+    };
+    let gamepad_button = |reader: &mut Reader| {
+        let value = reader.u8()?;
+        GAMEPAD_BUTTONS
+            .get(value as usize)
+            .copied()
+            .ok_or(invalid("gamepad button", value as i64))
+    };
+
+    let tag = reader.u8()?;
+    Ok(match tag {
+        KEY_DOWN => {
+            let scancode = scancode(reader)?;
+            Event::KeyDown {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Keycode::from_scancode(scancode),
+                scancode: Some(scancode),
+                keymod: Mod::NOMOD,
+                repeat: reader.u8()? != 0,
+            }
+        }
+        KEY_UP => {
+            let scancode = scancode(reader)?;
+            Event::KeyUp {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Keycode::from_scancode(scancode),
+                scancode: Some(scancode),
+                keymod: Mod::NOMOD,
+                repeat: false,
+            }
+        }
+        MOUSE_MOTION => Event::MouseMotion {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mousestate: MouseState::from_sdl_state(reader.u32()?),
+            x: reader.i32()?,
+            y: reader.i32()?,
+            xrel: reader.i32()?,
+            yrel: reader.i32()?,
+        },
+        MOUSE_BUTTON_DOWN | MOUSE_BUTTON_UP => {
+            let mouse_btn = MouseButton::from_ll(reader.u8()?);
+            let clicks = reader.u8()?;
+            let (x, y) = (reader.i32()?, reader.i32()?);
+            if tag == MOUSE_BUTTON_DOWN {
+                Event::MouseButtonDown {
+                    timestamp: 0,
+                    window_id: 0,
+                    which: 0,
+                    mouse_btn,
+                    clicks,
+                    x,
+                    y,
+                }
+            } else {
+                Event::MouseButtonUp {
+                    timestamp: 0,
+                    window_id: 0,
+                    which: 0,
+                    mouse_btn,
+                    clicks,
+                    x,
+                    y,
+                }
+            }
+        }
+        GAMEPAD_BUTTON_DOWN => Event::ControllerButtonDown {
+            timestamp: 0,
+            which: reader.u32()?,
+            button: gamepad_button(reader)?,
+        },
+        GAMEPAD_BUTTON_UP => Event::ControllerButtonUp {
+            timestamp: 0,
+            which: reader.u32()?,
+            button: gamepad_button(reader)?,
+        },
+        GAMEPAD_AXIS => {
+            let which = reader.u32()?;
+            let axis = reader.u8()?;
+            Event::ControllerAxisMotion {
+                timestamp: 0,
+                which,
+                axis: GAMEPAD_AXES
+                    .get(axis as usize)
+                    .copied()
+                    .ok_or(invalid("gamepad axis", axis as i64))?,
+                value: reader.i16()?,
+            }
+        }
+        GAMEPAD_REMOVED => Event::ControllerDeviceRemoved {
+            timestamp: 0,
+            which: reader.u32()?,
+        },
+        MOUSE_WHEEL => {
+            let direction = MouseWheelDirection::from_ll(reader.u32()?);
+            let (precise_x, precise_y) = (reader.f32()?, reader.f32()?);
+            Event::MouseWheel {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                x: precise_x as i32,
+                y: precise_y as i32,
+                direction,
+                precise_x,
+                precise_y,
+                mouse_x: 0,
+                mouse_y: 0,
+            }
+        }
+        TEXT_INPUT => Event::TextInput {
+            timestamp: 0,
+            window_id: 0,
+            text: reader.string(frame)?,
+        },
+        TEXT_EDITING => Event::TextEditing {
+            timestamp: 0,
+            window_id: 0,
+            text: reader.string(frame)?,
+            start: reader.i32()?,
+            length: reader.i32()?,
+        },
+        tag => return Err(ReplayError::UnknownEvent { frame, tag }),
+    })
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let (head, rest) = self
+            .bytes
+            .split_first_chunk::<N>()
+            .ok_or(ReplayError::Truncated)?;
+        self.bytes = rest;
+        Ok(*head)
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, ReplayError> {
+        Ok(i16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, ReplayError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, ReplayError> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, ReplayError> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    fn string(&mut self, frame: usize) -> Result<String, ReplayError> {
+        let len = self.u32()? as usize;
+        if self.bytes.len() < len {
+            return Err(ReplayError::Truncated);
+        }
+        let (text, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        String::from_utf8(text.to_vec()).map_err(|_| ReplayError::InvalidValue {
+            frame,
+            what: "text length",
+            value: len as i64,
+        })
+    }
+
+    fn u64(&mut self) -> Result<u64, ReplayError> {
+        Ok(u64::from_le_bytes(self.take()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn one_of_each() -> Vec<Event> {
+        vec![
+            Event::KeyDown {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Keycode::from_scancode(Scancode::W),
+                scancode: Some(Scancode::W),
+                keymod: Mod::NOMOD,
+                repeat: true,
+            },
+            Event::KeyUp {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Keycode::from_scancode(Scancode::Space),
+                scancode: Some(Scancode::Space),
+                keymod: Mod::NOMOD,
+                repeat: false,
+            },
+            Event::MouseMotion {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                mousestate: MouseState::from_sdl_state(1),
+                x: 320,
+                y: 240,
+                xrel: -4,
+                yrel: 7,
+            },
+            Event::MouseButtonDown {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                mouse_btn: MouseButton::Left,
+                clicks: 2,
+                x: 10,
+                y: 20,
+            },
+            Event::MouseButtonUp {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                mouse_btn: MouseButton::Right,
+                clicks: 1,
+                x: -1,
+                y: 5,
+            },
+            Event::ControllerButtonDown {
+                timestamp: 0,
+                which: 3,
+                button: GamepadButton::Touchpad,
+            },
+            Event::ControllerButtonUp {
+                timestamp: 0,
+                which: 3,
+                button: GamepadButton::A,
+            },
+            Event::ControllerAxisMotion {
+                timestamp: 0,
+                which: 1,
+                axis: GamepadAxis::TriggerRight,
+                value: -32768,
+            },
+            Event::ControllerDeviceRemoved {
+                timestamp: 0,
+                which: 1,
+            },
+            Event::MouseWheel {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                x: 0,
+                y: -1,
+                direction: MouseWheelDirection::Flipped,
+                precise_x: 0.5,
+                precise_y: -1.5,
+                mouse_x: 0,
+                mouse_y: 0,
+            },
+            Event::TextInput {
+                timestamp: 0,
+                window_id: 0,
+                text: "héllo".to_string(),
+            },
+            Event::TextEditing {
+                timestamp: 0,
+                window_id: 0,
+                text: "かな".to_string(),
+                start: 1,
+                length: 2,
+            },
+        ]
+    }
+
+    fn record(name: &str, frames: &[(Duration, Vec<Event>)]) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!("input_replay_{name}_{}", std::process::id()));
+        let mut recorder = InputRecorder::create(&path).unwrap();
+        for (frame_time, events) in frames {
+            recorder.record_frame(*frame_time, events).unwrap();
+        }
+        recorder.finish().unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn every_event_reads_back() {
+        let frames = [
+            (Duration::from_micros(16_667), one_of_each()),
+            (Duration::ZERO, Vec::new()),
+            (Duration::from_millis(250), one_of_each()),
+        ];
+        let mut replay = InputReplay::parse(&record("every_event", &frames)).unwrap();
+
+        for (frame_time, events) in frames {
+            let frame = replay.next_frame().unwrap();
+            assert_eq!(frame.frame_time, frame_time);
+            assert_eq!(frame.events, events);
+        }
+        assert!(replay.next_frame().is_none());
+    }
+
+    #[test]
+    fn unrecorded_events_are_skipped() {
+        let quit = Event::Quit { timestamp: 0 };
+        assert!(!is_recorded(&quit));
+
+        let frames = [(
+            Duration::from_millis(5),
+            vec![quit, one_of_each().remove(0)],
+        )];
+        let mut replay = InputReplay::parse(&record("skipped", &frames)).unwrap();
+        assert_eq!(
+            replay.next_frame().unwrap().events,
+            [one_of_each().remove(0)]
+        );
+    }
+
+    #[test]
+    fn bad_files_are_errors() {
+        assert!(matches!(
+            InputReplay::parse(b""),
+            Err(ReplayError::InvalidMagic)
+        ));
+        assert!(matches!(
+            InputReplay::parse(b"KINP1\0\0\0\0\0\0\0\0\0\0\0\0"),
+            Err(ReplayError::InvalidMagic)
+        ));
+
+        let bytes = record("truncated", &[(Duration::from_millis(16), one_of_each())]);
+        for len in MAGIC.len() + 1..bytes.len() {
+            assert!(
+                matches!(
+                    InputReplay::parse(&bytes[..len]),
+                    Err(ReplayError::Truncated)
+                ),
+                "{len} of {} bytes",
+                bytes.len()
+            );
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(0u64.to_le_bytes());
+        bytes.extend(1u32.to_le_bytes());
+        bytes.push(200);
+        assert!(matches!(
+            InputReplay::parse(&bytes),
+            Err(ReplayError::UnknownEvent { frame: 0, tag: 200 })
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimestepConfig {
+    /// Simulation ticks per second.
+    pub tick_rate: f32,
+    /// Ticks run at most per frame. Time beyond that is dropped, so a long
+    /// stall slows the simulation down instead of freezing it while it catches
+    /// up.
+    pub max_catch_up_steps: u32,
+}
+
+impl Default for TimestepConfig {
+    fn default() -> Self {
+        TimestepConfig {
+            tick_rate: 60.0,
+            max_catch_up_steps: 5,
+        }
+    }
+}
+
+/// Turns elapsed real time into a whole number of fixed-length ticks.
+pub struct FixedTimestep {
+    tick: Duration,
+    max_catch_up_steps: u32,
+    accumulator: Duration,
+}
+
+impl FixedTimestep {
+    pub fn new(config: TimestepConfig) -> Self {
+        assert!(config.tick_rate > 0.0, "tick rate must be positive");
+        assert!(
+            config.max_catch_up_steps > 0,
+            "max catch-up steps must be at least 1"
+        );
+        FixedTimestep {
+            tick: Duration::from_secs_f64(1.0 / config.tick_rate as f64),
+            max_catch_up_steps: config.max_catch_up_steps,
+            accumulator: Duration::ZERO,
+        }
+    }
+
+    /// Length of a tick in seconds.
+    pub fn delta_time(&self) -> f32 {
+        self.tick.as_secs_f32()
+    }
+
+    /// Adds `frame_time` and returns how many ticks to run. The same frame
+    /// times always produce the same ticks, which input replays rely on.
+    pub fn advance(&mut self, frame_time: Duration) -> u32 {
+        self.accumulator += frame_time;
+
+        let mut steps = 0;
+        while self.accumulator >= self.tick && steps < self.max_catch_up_steps {
+            self.accumulator -= self.tick;
+            steps += 1;
+        }
+        if steps == self.max_catch_up_steps {
+            self.accumulator = self.accumulator.min(self.tick);
+        }
+        steps
+    }
+
+    /// How far the current frame is into the next tick, in `[0, 1]`.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f64() / self.tick.as_secs_f64()).min(1.0) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timestep(tick_rate: f32, max_catch_up_steps: u32) -> FixedTimestep {
+        FixedTimestep::new(TimestepConfig {
+            tick_rate,
+            max_catch_up_steps,
+        })
+    }
+
+    #[test]
+    fn whole_ticks_run_and_the_rest_carries_over() {
+        let mut timestep = timestep(10.0, 5);
+
+        assert_eq!(timestep.advance(Duration::from_millis(250)), 2);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-6);
+
+        assert_eq!(timestep.advance(Duration::from_millis(50)), 1);
+        assert_eq!(timestep.alpha(), 0.0);
+    }
+
+    #[test]
+    fn short_frames_only_move_alpha() {
+        let mut timestep = timestep(10.0, 5);
+
+        assert_eq!(timestep.advance(Duration::from_millis(25)), 0);
+        assert!((timestep.alpha() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn long_stalls_are_capped() {
+        let mut timestep = timestep(10.0, 3);
+
+        assert_eq!(timestep.advance(Duration::from_secs(10)), 3);
+        assert_eq!(timestep.alpha(), 1.0);
+        assert_eq!(timestep.advance(Duration::ZERO), 1);
+        assert_eq!(timestep.advance(Duration::ZERO), 0);
+    }
+
+    #[test]
+    fn same_frame_times_give_the_same_ticks() {
+        let frames = [16, 17, 33, 5, 100, 16].map(Duration::from_millis);
+        let run = || {
+            let mut timestep = timestep(60.0, 5);
+            frames.map(|frame| timestep.advance(frame))
+        };
+        assert_eq!(run(), run());
+    }
+}
@@ -2,16 +2,101 @@ extern crate anyhow;
 extern crate sdl2;
 
 mod archive;
+mod demo;
 mod engine;
 mod scene;
 mod shader_program;
+mod shaders;
 mod texture;
 mod window;
 
+use std::path::PathBuf;
+
 pub use texture::*;
 
 fn main() -> anyhow::Result<()> {
-    let mut engine = engine::KEngine::new(1920, 1080, "Rust OpenGL Window")?;
+    let mut search_path = vec![PathBuf::from("base")];
+    let mut record = None;
+    let mut replay = None;
+    let mut pure_manifest = None;
+    let mut print_origins = false;
+    let mut print_manifest = false;
+    let mut mode = window::WindowMode::Borderless;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fs-origins" => {
+                print_origins = true;
+                continue;
+            }
+            "--fs-manifest" => {
+                print_manifest = true;
+                continue;
+            }
+            _ => {}
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("{arg} needs a value"))?;
+        match arg.as_str() {
+            "--game" => search_path.push(value.into()),
+            "--pure" => pure_manifest = Some(value),
+            "--record" => record = Some(value),
+            "--replay" => replay = Some(value),
+            "--window" => {
+                mode = match value.as_str() {
+                    "windowed" => window::WindowMode::Windowed,
+                    "borderless" => window::WindowMode::Borderless,
+                    "fullscreen" => window::WindowMode::Fullscreen,
+                    _ => anyhow::bail!(
+                        "Unknown window mode {value}, expected windowed, borderless or fullscreen"
+                    ),
+                }
+            }
+            _ => anyhow::bail!("Unknown argument {arg}"),
+        }
+    }
+
+    let mut archive = archive::EngineArchive::new(&search_path)?;
+    if let Some(path) = pure_manifest {
+        let manifest = archive::Manifest::parse(&std::fs::read_to_string(&path)?)?;
+        archive.set_pure(manifest)?;
+    }
+    if print_manifest {
+        print!("{}", archive.manifest());
+        return Ok(());
+    }
+    if print_origins {
+        for (index, layer) in archive.layers().iter().enumerate() {
+            println!("layer {index}: {}", layer.display());
+        }
+        for (path, origin) in archive.origins() {
+            println!("{path}: {origin}");
+        }
+        return Ok(());
+    }
+
+    let mut engine = engine::KEngine::<demo::FlyCamera>::new(
+        archive,
+        window::KWindowCreateInfo {
+            title: "Rust OpenGL Window",
+            width: 1920,
+            height: 1080,
+            mode,
+            resizable: true,
+        },
+        engine::frame_pacing::PresentationConfig::default(),
+        engine::timestep::TimestepConfig::default(),
+        engine::assets::LoaderConfig::default(),
+    )?;
+    if let Some(path) = record {
+        engine.record_input(path.as_ref())?;
+    }
+    if let Some(path) = replay {
+        engine.replay_input(path.as_ref())?;
+    }
+
     engine.run();
 
     Ok(())
@@ -2,6 +2,7 @@ use camera::Camera;
 use model::Model;
 
 pub mod camera;
+pub mod mesh;
 pub mod model;
 
 pub struct Scene {
@@ -15,9 +16,28 @@ pub struct SceneCreateInfo {
 }
 
 impl Scene {
-    pub fn render(&self) {
+    /// Called when the default framebuffer changes size. The scene has no
+    /// off-screen render targets yet, so only the camera needs updating.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.camera.set_aspect(width as f32 / height as f32);
+    }
+
+    /// Called before each simulation tick.
+    pub fn begin_tick(&mut self) {
+        self.camera.begin_tick();
+        for model in &mut self.models {
+            model.begin_tick();
+        }
+    }
+
+    /// `alpha` in `[0, 1]` blends transforms between the last two ticks.
+    pub fn render(&self, alpha: f32) {
+        let camera_matrix = self.camera.interpolated_matrix(alpha);
         for model in &self.models {
-            model.render(&self.camera);
+            model.render(&camera_matrix, alpha);
         }
     }
 }
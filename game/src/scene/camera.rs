@@ -2,11 +2,17 @@ use nalgebra_glm::{self as glm, Mat4, Vec3};
 
 pub struct Camera {
     projection_matrix: Mat4,
+    /// Position, yaw and pitch at the start of the current tick.
+    previous: (Vec3, f32, f32),
+    camera_type: CameraType,
+    near: f32,
+    far: f32,
     position: Vec3,
     yaw: f32,
     pitch: f32,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum CameraType {
     Perspective {
         fov: f32,
@@ -91,10 +97,42 @@ impl Camera {
         // self.update_camera_matrix();
     }
 
-    pub fn camera_matrix(&self) -> Mat4 {
-        let translation = glm::translation(&-self.position);
-        let rotation_yaw = glm::rotation(-self.yaw.to_radians(), &glm::vec3(0.0, 1.0, 0.0));
-        let rotation_pitch = glm::rotation(-self.pitch.to_radians(), &glm::vec3(1.0, 0.0, 0.0));
+    /// Rebuilds the projection for a viewport with the given width / height
+    /// ratio. Orthographic cameras keep their vertical extent and centre.
+    pub fn set_aspect(&mut self, aspect: f32) {
+        match &mut self.camera_type {
+            CameraType::Perspective { aspect: old, .. } => *old = aspect,
+            CameraType::Orthographic {
+                left,
+                right,
+                bottom,
+                top,
+            } => {
+                let centre = (*left + *right) / 2.0;
+                let half_width = (*top - *bottom) * aspect / 2.0;
+                *left = centre - half_width;
+                *right = centre + half_width;
+            }
+        }
+        self.projection_matrix = projection_matrix(self.camera_type, self.near, self.far);
+    }
+
+    /// Remembers the current pose so rendering can blend from it.
+    pub fn begin_tick(&mut self) {
+        self.previous = (self.position, self.yaw, self.pitch);
+    }
+
+    /// The camera matrix `alpha` of the way from the previous tick's pose to
+    /// the current one.
+    pub fn interpolated_matrix(&self, alpha: f32) -> Mat4 {
+        let (position, yaw, pitch) = self.previous;
+        let position = glm::lerp(&position, &self.position, alpha);
+        let yaw = yaw + (self.yaw - yaw) * alpha;
+        let pitch = pitch + (self.pitch - pitch) * alpha;
+
+        let translation = glm::translation(&-position);
+        let rotation_yaw = glm::rotation(-yaw.to_radians(), &glm::vec3(0.0, 1.0, 0.0));
+        let rotation_pitch = glm::rotation(-pitch.to_radians(), &glm::vec3(1.0, 0.0, 0.0));
 
         self.projection_matrix * rotation_pitch * rotation_yaw * translation
     }
@@ -122,29 +160,35 @@ impl From<CameraCreateInfo> for Camera {
             far,
         } = create_info;
 
-        let projection_matrix = match camera_type {
-            CameraType::Perspective { fov, aspect } => {
-                let w = (fov.to_radians() / 2.0).tan();
-                let h = w / aspect;
-                let fovy = 2.0 * h.atan();
-
-                glm::perspective(aspect, fovy, near, far)
-            }
-            CameraType::Orthographic {
-                left,
-                right,
-                bottom,
-                top,
-            } => glm::ortho(left, right, bottom, top, near, far),
-        };
-
         let camera = Camera {
-            projection_matrix,
+            projection_matrix: projection_matrix(camera_type, near, far),
+            camera_type,
+            near,
+            far,
             position,
             yaw: 0.0,
             pitch: 0.0,
+            previous: (position, 0.0, 0.0),
         };
 
         camera
     }
 }
+
+fn projection_matrix(camera_type: CameraType, near: f32, far: f32) -> Mat4 {
+    match camera_type {
+        CameraType::Perspective { fov, aspect } => {
+            let w = (fov.to_radians() / 2.0).tan();
+            let h = w / aspect;
+            let fovy = 2.0 * h.atan();
+
+            glm::perspective(aspect, fovy, near, far)
+        }
+        CameraType::Orthographic {
+            left,
+            right,
+            bottom,
+            top,
+        } => glm::ortho(left, right, bottom, top, near, far),
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+use gl::{self, VertexAttributeDescription, VertexLayout};
+
+/// Vertex and index buffers, drawn as triangles.
+pub struct Mesh {
+    // Only read through `vertex_array`, but have to outlive it.
+    _vertex_buffer: gl::Buffer,
+    _index_buffer: gl::Buffer,
+    vertex_array: gl::VertexArray,
+    index_count: i32,
+    attributes: Vec<VertexAttributeDescription>,
+    layout_name: &'static str,
+}
+
+gl::vertex_layout! {
+    #[derive(Clone, Copy)]
+    pub struct Vertex {
+        #[location(0)]
+        pub position: [f32; 3],
+        #[location(1)]
+        pub color: [f32; 3],
+        #[location(2)]
+        pub tex_coords: [f32; 2],
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Polygon {
+    pub indices: [u32; 3],
+}
+
+impl Mesh {
+    pub fn new<V: VertexLayout>(vertices: Vec<V>, polygons: Vec<Polygon>) -> Self {
+        let index_count = (polygons.len() * 3) as i32;
+
+        let mut vertex_buffer = gl::Buffer::create1();
+        vertex_buffer.storage(vertices, gl::BufferStorageFlag::DynamicStorage);
+
+        let mut index_buffer = gl::Buffer::create1();
+        index_buffer.storage(polygons, gl::BufferStorageFlag::DynamicStorage);
+
+        let mut vertex_array = gl::VertexArray::create1();
+        vertex_array.vertex_buffer(0, &vertex_buffer, 0, V::stride());
+        vertex_array.element_buffer(&index_buffer);
+        vertex_array.apply_layout::<V>(0);
+
+        Mesh {
+            _vertex_buffer: vertex_buffer,
+            _index_buffer: index_buffer,
+            vertex_array,
+            index_count,
+            attributes: V::attributes(),
+            layout_name: std::any::type_name::<V>(),
+        }
+    }
+
+    /// The attributes of the vertex type the mesh was created with.
+    pub fn attributes(&self) -> &[VertexAttributeDescription] {
+        &self.attributes
+    }
+
+    /// The name of the vertex type the mesh was created with.
+    pub fn layout_name(&self) -> &'static str {
+        self.layout_name
+    }
+
+    pub fn draw(&self) {
+        self.vertex_array.bind();
+        gl::draw_elements(
+            gl::DrawMode::Triangles,
+            self.index_count,
+            gl::IndexType::UnsignedInt,
+            0,
+        );
+    }
+}
+
+/// A mesh decoded on the CPU, waiting for [`Mesh::new`].
+pub struct MeshData {
+    pub vertices: Vec<Vertex>,
+    pub polygons: Vec<Polygon>,
+}
+
+impl MeshData {
+    /// Reads the `v`, `vt` and `f` lines of a Wavefront OBJ file; everything
+    /// else is ignored. Faces with more than three corners are split into a
+    /// fan. Vertices are white unless they have the common `v x y z r g b`
+    /// extension, and texture coordinates are flipped to start at the top.
+    pub fn from_obj(text: &str) -> Result<Self> {
+        let mut positions = Vec::new();
+        let mut colors = Vec::new();
+        let mut tex_coords = Vec::new();
+        let mut data = MeshData {
+            vertices: Vec::new(),
+            polygons: Vec::new(),
+        };
+        // Corners that share a position and texture coordinate share a vertex.
+        let mut corners = HashMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let mut words = line.split_whitespace();
+            let Some(keyword) = words.next() else {
+                continue;
+            };
+            let words: Vec<&str> = words.collect();
+            let floats = || {
+                words
+                    .iter()
+                    .map(|word| word.parse::<f32>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .with_context(|| format!("line {line_number}: invalid number"))
+            };
+
+            match keyword {
+                "v" => match floats()?[..] {
+                    [x, y, z] | [x, y, z, _] => {
+                        positions.push([x, y, z]);
+                        colors.push([1.0; 3]);
+                    }
+                    [x, y, z, r, g, b] => {
+                        positions.push([x, y, z]);
+                        colors.push([r, g, b]);
+                    }
+                    _ => bail!("line {line_number}: expected `v x y z`"),
+                },
+                "vt" => match floats()?[..] {
+                    [u] => tex_coords.push([u, 1.0]),
+                    [u, v] | [u, v, _] => tex_coords.push([u, 1.0 - v]),
+                    _ => bail!("line {line_number}: expected `vt u v`"),
+                },
+                "f" => {
+                    if words.len() < 3 {
+                        bail!("line {line_number}: a face needs at least three corners");
+                    }
+                    let mut face = Vec::with_capacity(words.len());
+                    for corner in &words {
+                        let mut indices = corner.split('/');
+                        let position =
+                            obj_index(indices.next(), positions.len(), line_number, "position")?;
+                        let tex_coord = match indices.next() {
+                            Some("") | None => None,
+                            index => Some(obj_index(
+                                index,
+                                tex_coords.len(),
+                                line_number,
+                                "texture coordinate",
+                            )?),
+                        };
+
+                        let vertex = *corners.entry((position, tex_coord)).or_insert_with(|| {
+                            data.vertices.push(Vertex {
+                                position: positions[position],
+                                color: colors[position],
+                                tex_coords: tex_coord.map_or([0.0; 2], |index| tex_coords[index]),
+                            });
+                            (data.vertices.len() - 1) as u32
+                        });
+                        face.push(vertex);
+                    }
+                    for pair in face[1..].windows(2) {
+                        data.polygons.push(Polygon {
+                            indices: [face[0], pair[0], pair[1]],
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if data.polygons.is_empty() {
+            bail!("the file has no faces");
+        }
+        Ok(data)
+    }
+}
+
+/// Turns a 1-based OBJ index, or a negative one counting back from the last
+/// element, into an index into a list of `len`.
+fn obj_index(index: Option<&str>, len: usize, line_number: usize, what: &str) -> Result<usize> {
+    let index: i64 = index
+        .unwrap_or_default()
+        .parse()
+        .with_context(|| format!("line {line_number}: invalid {what} index"))?;
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index - 1
+    };
+    if index == 0 || resolved < 0 || resolved >= len as i64 {
+        bail!("line {line_number}: {what} index {index} is out of range");
+    }
+    Ok(resolved as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quads_are_split_and_corners_shared() {
+        let data = MeshData::from_obj(
+            "# a quad\n\
+             o quad\n\
+             v 0 0 0\n\
+             v 1 0 0 1 0 0\n\
+             v 1 1 0\n\
+             v 0 1 0\n\
+             vt 0 0\n\
+             vt 1 1\n\
+             vn 0 0 1\n\
+             f 1/1 2/1 3/2 4/2\n\
+             f -4/-2 -2/-1 -1/-1\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            data.polygons,
+            [
+                Polygon { indices: [0, 1, 2] },
+                Polygon { indices: [0, 2, 3] },
+                Polygon { indices: [0, 2, 3] },
+            ]
+        );
+        assert_eq!(data.vertices.len(), 4);
+        assert_eq!(data.vertices[1].position, [1.0, 0.0, 0.0]);
+        assert_eq!(data.vertices[1].color, [1.0, 0.0, 0.0]);
+        assert_eq!(data.vertices[0].color, [1.0; 3]);
+        assert_eq!(data.vertices[0].tex_coords, [0.0, 1.0]);
+        assert_eq!(data.vertices[2].tex_coords, [1.0, 0.0]);
+    }
+
+    #[test]
+    fn corners_without_texture_coordinates() {
+        let data = MeshData::from_obj("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1//1 2 3\n").unwrap();
+        assert_eq!(data.vertices.len(), 3);
+        assert_eq!(data.vertices[2].tex_coords, [0.0; 2]);
+    }
+
+    #[test]
+    fn bad_files_name_the_line() {
+        let error = |text| MeshData::from_obj(text).err().unwrap().to_string();
+        assert_eq!(
+            error("v 0 0 0\nv 1 0 0\nf 1 2 3\n"),
+            "line 3: position index 3 is out of range"
+        );
+        assert_eq!(error("v 0 0\n"), "line 1: expected `v x y z`");
+        assert_eq!(error("v 0 zero 0\n"), "line 1: invalid number");
+        assert_eq!(
+            error("v 0 0 0\nf 1 1\n"),
+            "line 2: a face needs at least three corners"
+        );
+        assert_eq!(error("v 0 0 0\n"), "the file has no faces");
+    }
+}
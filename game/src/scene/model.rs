@@ -1,157 +1,246 @@
-use std::rc::Rc;
-
-use gl;
-use nalgebra_glm::Mat4;
-
-use crate::{Texture, scene::camera::Camera, shader_program::ShaderProgram};
+use std::{cell::Cell, rc::Rc};
+
+use anyhow::{Result, bail};
+use gl::VertexAttribFormat;
+use nalgebra_glm::{self as glm, Mat4, Quat, Vec3};
+
+use super::mesh::Mesh;
+use crate::{
+    Texture,
+    engine::assets::{Asset, LoadState},
+    shader_program::{
+        ShaderProgram,
+        reflection::{InputKind, VertexInput},
+    },
+};
 
 pub struct Model {
-    vbo: gl::Buffer,
-    ebo: gl::Buffer,
-    vertex_array: gl::VertexArray,
-    vertex_count: i32,
+    mesh: Asset<Mesh>,
+    /// The mesh's state when its layout was last checked against the shader,
+    /// and whether it matched.
+    checked_mesh: Cell<(LoadState, bool)>,
     model_matrix: Mat4,
-    texture: Rc<Texture>,
+    /// `model_matrix` at the start of the current tick.
+    previous_model_matrix: Mat4,
+    texture: Asset<Texture>,
     shader_program: Rc<ShaderProgram>,
-    model_location: i32,
-    camera_location: i32,
-}
-
-#[repr(C)]
-#[derive(Clone, Copy)]
-pub struct Vertex {
-    pub position: [f32; 3],
-    pub color: [f32; 3],
-    pub tex_coords: [f32; 2],
-}
-
-#[repr(C)]
-#[derive(Clone, Copy)]
-pub struct Polygon {
-    pub indices: [u32; 3],
 }
 
 pub struct ModelCreateInfo {
-    pub vertices: Vec<Vertex>,
-    pub polygons: Vec<Polygon>,
+    pub mesh: Asset<Mesh>,
     pub model_matrix: Mat4,
-    pub texture: Rc<Texture>,
+    pub texture: Asset<Texture>,
     pub shader_program: Rc<ShaderProgram>,
 }
 
-pub struct AttributeDescription {
-    pub location: u32,
-    pub components: i32,
-    pub data_type: gl::VertexAttribPointerType,
-    pub stride: usize,
-    pub offset: usize,
-}
-
-pub trait IModel {
-    fn shader_program() -> Rc<ShaderProgram>;
-    fn attribute_descriptions() -> Vec<AttributeDescription>;
-}
-
 impl Model {
-    pub fn new(create_info: ModelCreateInfo) -> Self {
+    /// Fails if the mesh, usually the placeholder of one still loading,
+    /// doesn't fit the shader's vertex inputs.
+    pub fn new(create_info: ModelCreateInfo) -> Result<Self> {
         let ModelCreateInfo {
-            vertices,
-            polygons,
+            mesh,
             texture,
             model_matrix,
             shader_program,
         } = create_info;
 
-        let vertex_count = (polygons.len() * 3) as i32;
+        check_layout(&mesh.get(), &shader_program.vertex_inputs())?;
 
-        let mut vertex_buffer = gl::Buffer::create1();
-        vertex_buffer.storage(vertices, gl::BufferStorageFlag::DynamicStorage);
+        Ok(Self {
+            checked_mesh: Cell::new((mesh.state(), true)),
+            mesh,
+            texture,
+            model_matrix,
+            previous_model_matrix: model_matrix,
+            shader_program,
+        })
+    }
 
-        let mut index_buffer = gl::Buffer::create1();
-        index_buffer.storage(polygons, gl::BufferStorageFlag::DynamicStorage);
+    pub fn rotate(&mut self, rotation: &Mat4) {
+        self.model_matrix = rotation * self.model_matrix;
+    }
 
-        let mut vertex_array = gl::VertexArray::create1();
-        vertex_array.vertex_buffer(0, &vertex_buffer, 0, size_of::<Vertex>());
-        vertex_array.element_buffer(&index_buffer);
+    /// Remembers the current transform so rendering can blend from it.
+    pub fn begin_tick(&mut self) {
+        self.previous_model_matrix = self.model_matrix;
+    }
 
-        let attribute_descriptions = Self::default_attribute_descriptions();
-        for attr in attribute_descriptions.iter() {
-            vertex_array.enable_attrib(attr.location);
-            vertex_array.attrib_format(
-                attr.location,
-                attr.components,
-                attr.data_type,
-                false,
-                attr.offset as u32,
-            );
-            vertex_array.attrib_binding(attr.location, 0);
+    /// `alpha` is how far the frame is between the previous tick and the
+    /// current one.
+    pub fn render(&self, camera_matrix: &Mat4, alpha: f32) {
+        let mesh = self.mesh.get();
+        if !self.mesh_fits_shader(&mesh) {
+            return;
         }
 
-        let model_location = shader_program.uniform_location("model");
-        let camera_location = shader_program.uniform_location("camera");
+        self.shader_program.r#use();
+        self.texture.get().bind_to_unit(0);
 
-        Self {
-            vbo: vertex_buffer,
-            ebo: index_buffer,
-            vertex_array,
-            texture,
-            vertex_count,
-            model_matrix,
-            shader_program,
-            model_location,
-            camera_location,
+        // Looked up per frame: locations can change when the shaders are reloaded.
+        let model_location = self.shader_program.uniform_location("model");
+        let camera_location = self.shader_program.uniform_location("camera");
+        self.shader_program
+            .set_uniform_mat_4(model_location, self.interpolated_matrix(alpha));
+        self.shader_program
+            .set_uniform_mat_4(camera_location, *camera_matrix);
+
+        mesh.draw();
+    }
+
+    /// Checks the layout again once the mesh has loaded, since it can have a
+    /// different vertex type than its placeholder. Models whose mesh doesn't
+    /// fit aren't drawn.
+    fn mesh_fits_shader(&self, mesh: &Mesh) -> bool {
+        let state = self.mesh.state();
+        let (checked_state, fits) = self.checked_mesh.get();
+        if state == checked_state {
+            return fits;
         }
+
+        let fits = match check_layout(mesh, &self.shader_program.vertex_inputs()) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Not drawing a model: {e:#}");
+                false
+            }
+        };
+        self.checked_mesh.set((state, fits));
+        fits
     }
 
-    pub fn bind(&self) {
-        self.vertex_array.bind();
+    /// Blends translation and scale linearly and rotation with a slerp.
+    /// Assumes the matrices have no shear or perspective.
+    fn interpolated_matrix(&self, alpha: f32) -> Mat4 {
+        if alpha >= 1.0 || self.previous_model_matrix == self.model_matrix {
+            return self.model_matrix;
+        }
+
+        let (from_translation, from_rotation, from_scale) = decompose(&self.previous_model_matrix);
+        let (to_translation, to_rotation, to_scale) = decompose(&self.model_matrix);
+
+        let translation = glm::lerp(&from_translation, &to_translation, alpha);
+        let rotation = glm::quat_slerp(&from_rotation, &to_rotation, alpha);
+        let scale = glm::lerp(&from_scale, &to_scale, alpha);
+
+        glm::translation(&translation) * glm::quat_to_mat4(&rotation) * glm::scaling(&scale)
     }
+}
 
-    pub fn rotate(&mut self, rotation: &Mat4) {
-        self.model_matrix = rotation * self.model_matrix;
+fn decompose(matrix: &Mat4) -> (Vec3, Quat, Vec3) {
+    let translation = matrix.column(3).xyz();
+    let mut basis = glm::mat4_to_mat3(matrix);
+    let scale = glm::vec3(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    );
+    for (i, axis_scale) in scale.iter().enumerate() {
+        if *axis_scale > 0.0 {
+            basis.column_mut(i).unscale_mut(*axis_scale);
+        }
     }
+    (translation, glm::mat3_to_quat(&basis), scale)
+}
 
-    pub fn render(&self, camera: &Camera) {
-        self.shader_program.r#use();
-        self.texture.bind_to_unit(0);
-        self.bind();
+/// Fails if a shader input has no attribute in the mesh's vertex type or
+/// reads it with a different type. Attributes may have fewer components than
+/// the input, the rest is filled in by GL.
+fn check_layout(mesh: &Mesh, inputs: &[VertexInput]) -> Result<()> {
+    let attributes = mesh.attributes();
+    for input in inputs {
+        let Some(attribute) = attributes.iter().find(|a| a.location == input.location) else {
+            bail!(
+                "Vertex input \"{}\" at location {} has no matching attribute in {}",
+                input.name,
+                input.location,
+                mesh.layout_name()
+            );
+        };
+
+        let (kind, components) = match attribute.format {
+            VertexAttribFormat::Float { components, .. } => (InputKind::Float, components),
+            VertexAttribFormat::Integer { components, .. } => (InputKind::Integer, components),
+            VertexAttribFormat::Double { components } => (InputKind::Double, components),
+        };
+        if kind != input.kind || components as u32 > input.components {
+            bail!(
+                "Vertex input \"{}\" at location {} reads {} {:?} component(s), but {} provides {} {:?} component(s)",
+                input.name,
+                input.location,
+                input.components,
+                input.kind,
+                mesh.layout_name(),
+                components,
+                kind
+            );
+        }
+    }
+    Ok(())
+}
 
-        self.shader_program
-            .set_uniform_mat_4(self.model_location, self.model_matrix);
-        self.shader_program
-            .set_uniform_mat_4(self.camera_location, camera.camera_matrix());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        scene::mesh::{Polygon, Vertex},
+        shader_program::ShaderCode,
+    };
+
+    fn cube(texture: Asset<Texture>) -> Model {
+        let shader_program = ShaderProgram::new(
+            ShaderCode::GLSL("#version 460 core\nvoid main() {}"),
+            ShaderCode::GLSL("#version 460 core\nvoid main() {}"),
+        )
+        .unwrap();
+        let vertex = Vertex {
+            position: [0.0; 3],
+            color: [1.0; 3],
+            tex_coords: [0.0; 2],
+        };
+        let mesh = Mesh::new(vec![vertex; 8], vec![Polygon { indices: [0, 1, 2] }; 12]);
+        Model::new(ModelCreateInfo {
+            mesh: Asset::ready(Rc::new(mesh)),
+            model_matrix: glm::identity(),
+            texture,
+            shader_program: Rc::new(shader_program),
+        })
+        .unwrap()
+    }
 
-        gl::draw_elements(
-            gl::DrawMode::Triangles,
-            self.vertex_count,
-            gl::VertexAttribPointerType::UnsignedInt,
-            0,
+    #[test]
+    fn render_binds_the_texture_and_draws_every_index() {
+        gl::mock::install();
+        let model = cube(Asset::ready(Rc::new(Texture::placeholder())));
+        gl::mock::take_calls();
+
+        model.render(&glm::identity(), 1.0);
+
+        let binds = gl::mock::calls_named("BindTextureUnit");
+        assert_eq!(binds.len(), 1);
+        assert_eq!(binds[0].args[0], gl::mock::Arg::Int(0));
+
+        let draws = gl::mock::with_state(|state| state.draws.clone());
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws[0].mode, gl::DrawMode::Triangles as u32);
+        assert_eq!(draws[0].count, 36);
+        assert!(
+            draws[0]
+                .texture_units
+                .get(&0)
+                .is_some_and(|&texture| texture != 0)
         );
     }
 
-    fn default_attribute_descriptions() -> Vec<AttributeDescription> {
-        vec![
-            AttributeDescription {
-                location: 0,
-                components: 3,
-                data_type: gl::VertexAttribPointerType::Float,
-                stride: std::mem::size_of::<Vertex>(),
-                offset: std::mem::offset_of!(Vertex, position),
-            },
-            AttributeDescription {
-                location: 1,
-                components: 3,
-                data_type: gl::VertexAttribPointerType::Float,
-                stride: std::mem::size_of::<Vertex>(),
-                offset: std::mem::offset_of!(Vertex, color),
-            },
-            AttributeDescription {
-                location: 2,
-                components: 2,
-                data_type: gl::VertexAttribPointerType::Float,
-                stride: std::mem::size_of::<Vertex>(),
-                offset: std::mem::offset_of!(Vertex, tex_coords),
-            },
-        ]
+    #[test]
+    fn interpolates_between_ticks() {
+        gl::mock::install();
+        let mut model = cube(Asset::ready(Rc::new(Texture::placeholder())));
+        model.begin_tick();
+        model.model_matrix = glm::translation(&glm::vec3(2.0, 0.0, 4.0));
+
+        assert_eq!(model.interpolated_matrix(0.0), Mat4::identity());
+        assert_eq!(model.interpolated_matrix(1.0), model.model_matrix);
+        let halfway = model.interpolated_matrix(0.5);
+        assert_eq!(halfway.column(3).xyz(), glm::vec3(1.0, 0.0, 2.0));
     }
 }
@@ -1,37 +1,246 @@
+mod cache;
+mod hot_reload;
+mod permutations;
+mod preprocessor;
+pub mod reflection;
 mod shader;
 
+pub use crate::shader_program::cache::ProgramCache;
+pub use crate::shader_program::hot_reload::{ShaderReloader, ShaderSource};
+pub use crate::shader_program::permutations::{PermutationCache, PermutationCacheCreateInfo};
+pub use crate::shader_program::preprocessor::{PreprocessedSource, Preprocessor};
+use crate::shader_program::reflection::{SpirvReflection, VertexInput};
 use crate::shader_program::shader::Shader;
-pub use crate::shader_program::shader::ShaderCode;
+pub use crate::shader_program::shader::{ShaderCode, SpecializationValue};
 use gl;
 use nalgebra_glm::Mat4;
+use std::{
+    cell::{Ref, RefCell},
+    collections::HashMap,
+};
 
-pub struct ShaderProgram {
+struct ProgramState {
     program: gl::ShaderProgram,
+    vertex_inputs: Vec<VertexInput>,
+    uniform_locations: HashMap<String, i32>,
+}
+
+/// A linked program that can be swapped for a rebuilt one in place, so holders
+/// of an `Rc<ShaderProgram>` pick up reloaded shaders.
+pub struct ShaderProgram {
+    state: RefCell<ProgramState>,
+}
+
+/// Collects the stages of a [`ShaderProgram`]. Any combination GL can link is
+/// accepted: a compute stage on its own, or a vertex stage with optional
+/// tessellation, geometry and fragment stages.
+pub struct ShaderProgramBuilder<'a> {
+    stages: Vec<(gl::ShaderType, ShaderCode<'a>)>,
+    cache: Option<&'a ProgramCache>,
+}
+
+impl<'a> ShaderProgramBuilder<'a> {
+    pub fn stage(mut self, shader_type: gl::ShaderType, code: ShaderCode<'a>) -> Self {
+        self.stages.push((shader_type, code));
+        self
+    }
+
+    /// Reuses a binary from `cache` when the driver accepts it and stores a
+    /// fresh one otherwise.
+    pub fn cache(mut self, cache: &'a ProgramCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub fn build(self) -> Result<ShaderProgram, String> {
+        self.validate()?;
+        let vertex = self
+            .stages
+            .iter()
+            .find(|(shader_type, _)| *shader_type == gl::ShaderType::Vertex)
+            .map(|(_, code)| code);
+
+        let key = self.cache.map(|cache| cache.key(&self.stages));
+        if let (Some(cache), Some(key)) = (self.cache, key)
+            && let Some(binary) = cache.load(key)
+        {
+            let mut program = gl::ShaderProgram::create();
+            match program.program_binary(&binary) {
+                Ok(()) => return ShaderProgram::finish(program, vertex),
+                Err(e) => {
+                    eprintln!("Rebuilding cached shader program {key:016x}: {e}");
+                    let _ = cache.remove(key);
+                }
+            }
+        }
+
+        let shaders = self
+            .stages
+            .iter()
+            .map(|(shader_type, code)| Shader::new(*code, *shader_type))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+
+        let mut program = gl::ShaderProgram::create();
+        if self.cache.is_some() {
+            program.set_binary_retrievable(true);
+        }
+        for shader in &shaders {
+            program.attach_shader(shader.shader());
+        }
+        program.link().map_err(|e| e.to_string())?;
+
+        if let (Some(cache), Some(key)) = (self.cache, key)
+            && let Err(e) = cache.store(key, &program.get_program_binary())
+        {
+            eprintln!("Failed to cache shader program {key:016x}: {e}");
+        }
+
+        ShaderProgram::finish(program, vertex)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        use gl::ShaderType::*;
+        let has = |stage| {
+            self.stages
+                .iter()
+                .any(|(shader_type, _)| *shader_type == stage)
+        };
+
+        for (index, (shader_type, _)) in self.stages.iter().enumerate() {
+            if self.stages[..index]
+                .iter()
+                .any(|(other, _)| other == shader_type)
+            {
+                return Err(format!("{shader_type:?} stage was given more than once"));
+            }
+        }
+
+        if has(Compute) {
+            if self.stages.len() > 1 {
+                return Err("A compute stage can't be combined with other stages".to_string());
+            }
+            return Ok(());
+        }
+        if !has(Vertex) {
+            return Err("A program needs a vertex or a compute stage".to_string());
+        }
+        if has(TessControl) && !has(TessEvaluation) {
+            return Err("A tessellation control stage needs an evaluation stage".to_string());
+        }
+        Ok(())
+    }
 }
 
 impl ShaderProgram {
+    pub fn builder<'a>() -> ShaderProgramBuilder<'a> {
+        ShaderProgramBuilder {
+            stages: Vec::new(),
+            cache: None,
+        }
+    }
+
     pub fn new(vertex: ShaderCode, fragment: ShaderCode) -> Result<Self, String> {
-        let vertex_shader = Shader::new(vertex, gl::ShaderType::Vertex);
-        let fragment_shader = Shader::new(fragment, gl::ShaderType::Fragment);
+        Self::builder()
+            .stage(gl::ShaderType::Vertex, vertex)
+            .stage(gl::ShaderType::Fragment, fragment)
+            .build()
+    }
+
+    /// Compiles preprocessed GLSL, reporting errors against the original files
+    /// and lines.
+    pub fn from_preprocessed(
+        vertex: &PreprocessedSource,
+        fragment: &PreprocessedSource,
+    ) -> Result<Self, String> {
+        let compile = |source: &PreprocessedSource, shader_type| {
+            Shader::from_glsl(&source.source, shader_type)
+                .map_err(|e| source.map_log(&e.to_string()))
+        };
+        let vertex_shader = compile(vertex, gl::ShaderType::Vertex)?;
+        let fragment_shader = compile(fragment, gl::ShaderType::Fragment)?;
 
         let mut program = gl::ShaderProgram::create();
 
-        program.attach_shader(vertex_shader.into());
-        program.attach_shader(fragment_shader.into());
-        program.link();
+        program.attach_shader(vertex_shader.shader());
+        program.attach_shader(fragment_shader.shader());
+        program.link().map_err(|e| e.to_string())?;
+
+        Self::finish(program, Some(&ShaderCode::GLSL(&vertex.source)))
+    }
 
-        Ok(Self { program })
+    /// Like [`Self::new`], but goes through `cache`; see
+    /// [`ShaderProgramBuilder::cache`].
+    pub fn new_cached(
+        vertex: ShaderCode,
+        fragment: ShaderCode,
+        cache: &ProgramCache,
+    ) -> Result<Self, String> {
+        Self::builder()
+            .stage(gl::ShaderType::Vertex, vertex)
+            .stage(gl::ShaderType::Fragment, fragment)
+            .cache(cache)
+            .build()
+    }
+
+    /// `vertex` is `None` for compute programs, which have no vertex inputs.
+    fn finish(program: gl::ShaderProgram, vertex: Option<&ShaderCode>) -> Result<Self, String> {
+        let mut vertex_inputs: Vec<VertexInput> = match vertex {
+            None => Vec::new(),
+            Some(ShaderCode::SPIRV { binary, .. }) => SpirvReflection::parse(binary)
+                .map_err(|e| e.to_string())?
+                .inputs
+                .iter()
+                .filter_map(VertexInput::from_spirv)
+                .collect(),
+            Some(ShaderCode::GLSL(_)) => program
+                .resources(gl::ProgramInterface::ProgramInput)
+                .iter()
+                .filter_map(VertexInput::from_program_resource)
+                .collect(),
+        };
+        vertex_inputs.sort_by_key(|input| input.location);
+
+        Ok(Self {
+            state: RefCell::new(ProgramState {
+                program,
+                vertex_inputs,
+                uniform_locations: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Takes over `other`'s GL program. Uniform locations are looked up again
+    /// afterwards.
+    pub fn replace(&self, other: ShaderProgram) {
+        *self.state.borrow_mut() = other.state.into_inner();
+    }
+
+    /// User-defined inputs of the vertex stage, sorted by location.
+    pub fn vertex_inputs(&self) -> Ref<'_, [VertexInput]> {
+        Ref::map(self.state.borrow(), |state| state.vertex_inputs.as_slice())
     }
 
     pub fn r#use(&self) {
-        self.program.r#use();
+        self.state.borrow().program.r#use();
     }
 
+    /// Cached until the program is replaced, so it is cheap to call per frame.
     pub fn uniform_location(&self, name: &str) -> i32 {
-        self.program.get_uniform_location(name)
+        let mut state = self.state.borrow_mut();
+        if let Some(location) = state.uniform_locations.get(name) {
+            return *location;
+        }
+
+        let location = state.program.get_uniform_location(name);
+        state.uniform_locations.insert(name.to_string(), location);
+        location
     }
 
     pub fn set_uniform_mat_4(&self, location: i32, value: Mat4) {
-        self.program.set_uniform_mat_4(location, value);
+        self.state
+            .borrow()
+            .program
+            .set_uniform_mat_4(location, value);
     }
 }
@@ -0,0 +1,167 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::PathBuf,
+};
+
+use crate::shader_program::ShaderCode;
+
+const MAGIC: &[u8; 4] = b"KPGB";
+
+/// On-disk cache of linked program binaries.
+///
+/// Binaries are keyed by the shader inputs together with the driver's vendor,
+/// renderer and version, so a driver update never sees another driver's
+/// binary.
+pub struct ProgramCache {
+    dir: PathBuf,
+}
+
+impl ProgramCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Hashes `stages` and the current driver. Requires a loaded context.
+    pub fn key(&self, stages: &[(gl::ShaderType, ShaderCode)]) -> u64 {
+        let mut hasher = Fnv1a::new();
+        for name in [
+            gl::StringName::Vendor,
+            gl::StringName::Renderer,
+            gl::StringName::Version,
+        ] {
+            hasher.write_chunk(gl::get_string(name).as_bytes());
+        }
+        for (shader_type, code) in stages {
+            hasher.write(&(*shader_type as u32).to_le_bytes());
+            match code {
+                ShaderCode::GLSL(source) => {
+                    hasher.write(&[0]);
+                    hasher.write_chunk(source.as_bytes());
+                }
+                ShaderCode::SPIRV {
+                    binary,
+                    entry_point,
+                    constants,
+                } => {
+                    hasher.write(&[1]);
+                    hasher.write_chunk(binary);
+                    hasher.write_chunk(entry_point.as_bytes());
+                    hasher.write(&(constants.len() as u64).to_le_bytes());
+                    for (id, value) in constants.iter() {
+                        hasher.write(&id.to_le_bytes());
+                        hasher.write(&value.bits().to_le_bytes());
+                    }
+                }
+            }
+        }
+        hasher.finish()
+    }
+
+    /// Returns `None` when nothing is cached under `key` or the file is unreadable.
+    pub fn load(&self, key: u64) -> Option<gl::ProgramBinary> {
+        let bytes = fs::read(self.path(key)).ok()?;
+        let rest = bytes.strip_prefix(MAGIC)?;
+        let (format, data) = rest.split_first_chunk::<4>()?;
+
+        Some(gl::ProgramBinary {
+            format: u32::from_le_bytes(*format),
+            data: data.to_vec(),
+        })
+    }
+
+    /// Does nothing for an empty binary, which drivers return when they can't
+    /// retrieve one (`GL_PROGRAM_BINARY_LENGTH` 0).
+    pub fn store(&self, key: u64, binary: &gl::ProgramBinary) -> io::Result<()> {
+        if binary.data.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + binary.data.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&binary.format.to_le_bytes());
+        bytes.extend_from_slice(&binary.data);
+        fs::write(self.path(key), bytes)
+    }
+
+    /// Drops a stale entry, e.g. one the driver rejected.
+    pub fn remove(&self, key: u64) -> io::Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{key:016x}.bin"))
+    }
+}
+
+/// 64-bit FNV-1a. Stable across builds, unlike `DefaultHasher`.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Length-prefixed, so adjacent inputs can't run into each other.
+    fn write_chunk(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache(name: &str) -> ProgramCache {
+        let dir = std::env::temp_dir().join(format!("program_cache_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        ProgramCache::new(dir)
+    }
+
+    #[test]
+    fn stored_binaries_load_back() {
+        let cache = cache("round_trip");
+        let binary = gl::ProgramBinary {
+            format: 0x8741,
+            data: vec![1, 2, 3],
+        };
+        cache.store(7, &binary).unwrap();
+
+        let loaded = cache.load(7).unwrap();
+        assert_eq!(loaded.format, binary.format);
+        assert_eq!(loaded.data, binary.data);
+        let _ = fs::remove_dir_all(&cache.dir);
+    }
+
+    #[test]
+    fn empty_binaries_are_not_stored() {
+        let cache = cache("empty");
+        let binary = gl::ProgramBinary {
+            format: 0,
+            data: Vec::new(),
+        };
+        cache.store(7, &binary).unwrap();
+
+        assert!(cache.load(7).is_none());
+        assert!(!cache.dir.exists());
+    }
+}
@@ -0,0 +1,222 @@
+use std::{
+    fs,
+    path::PathBuf,
+    rc::{Rc, Weak},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Result, anyhow};
+
+use crate::{
+    archive::EngineArchive,
+    shader_program::{Preprocessor, ShaderProgram},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Where a GLSL stage is loaded from during development.
+#[derive(Debug, Clone)]
+pub enum ShaderSource {
+    File(PathBuf),
+    /// A path resolved through the [`EngineArchive`].
+    Archive(String),
+}
+
+impl ShaderSource {
+    fn read(&self, archive: &EngineArchive) -> Result<String> {
+        let bytes = match self {
+            ShaderSource::File(path) => fs::read(path)?,
+            ShaderSource::Archive(path) => archive.load(path)?,
+        };
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    fn modified(&self, archive: &EngineArchive) -> Option<SystemTime> {
+        match self {
+            ShaderSource::File(path) => fs::metadata(path).and_then(|m| m.modified()).ok(),
+            ShaderSource::Archive(path) => archive.modified(path),
+        }
+    }
+}
+
+impl std::fmt::Display for ShaderSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderSource::File(path) => write!(f, "{}", path.display()),
+            ShaderSource::Archive(path) => write!(f, "{path}"),
+        }
+    }
+}
+
+struct WatchedProgram {
+    program: Weak<ShaderProgram>,
+    vertex: ShaderSource,
+    fragment: ShaderSource,
+    /// Both stages and everything they include, with the modification time
+    /// seen at the last build.
+    dependencies: Vec<(ShaderSource, Option<SystemTime>)>,
+    /// The last reload error, so a failure that repeats on every poll is
+    /// reported once.
+    last_error: Option<String>,
+}
+
+impl WatchedProgram {
+    fn changed(&self, archive: &EngineArchive) -> bool {
+        self.dependencies
+            .iter()
+            .any(|(source, modified)| source.modified(archive) != *modified)
+    }
+}
+
+/// Builds GLSL programs from sources on disk or in the archive and rebuilds
+/// them when the sources or their includes change. Includes are resolved
+/// through the archive, so a stage read from a file may only include archive
+/// paths. Rebuilt programs replace the old ones in place; when a rebuild fails
+/// the old program stays active.
+pub struct ShaderReloader {
+    preprocessor: Preprocessor,
+    programs: Vec<WatchedProgram>,
+    last_poll: Instant,
+}
+
+impl ShaderReloader {
+    pub fn new(preprocessor: Preprocessor) -> Self {
+        Self {
+            preprocessor,
+            programs: Vec::new(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    pub fn load(
+        &mut self,
+        archive: &EngineArchive,
+        vertex: ShaderSource,
+        fragment: ShaderSource,
+    ) -> Result<Rc<ShaderProgram>> {
+        let (program, dependencies) = self.build(archive, &vertex, &fragment);
+        let program = Rc::new(program?);
+
+        self.programs.push(WatchedProgram {
+            program: Rc::downgrade(&program),
+            vertex,
+            fragment,
+            dependencies,
+            last_error: None,
+        });
+
+        Ok(program)
+    }
+
+    /// Rebuilds programs whose sources changed. Cheap to call every frame; the
+    /// sources are only checked a few times per second.
+    pub fn poll(&mut self, archive: &EngineArchive) {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return;
+        }
+        self.last_poll = Instant::now();
+
+        self.programs
+            .retain(|watched| watched.program.strong_count() > 0);
+
+        let mut refreshed = false;
+        for index in 0..self.programs.len() {
+            let watched = &self.programs[index];
+            if !watched.changed(archive) {
+                continue;
+            }
+
+            // Picks up changed packs, and loose files a changed source now
+            // includes.
+            if !refreshed {
+                if let Err(e) = archive.refresh() {
+                    eprintln!("Failed to reopen archives: {e}");
+                    continue;
+                }
+                refreshed = true;
+            }
+
+            let (rebuilt, dependencies) = self.build(archive, &watched.vertex, &watched.fragment);
+            let watched = &mut self.programs[index];
+            // Keep the old timestamps if a file couldn't be read, so it is
+            // retried on the next poll.
+            if !dependencies.is_empty() {
+                watched.dependencies = dependencies;
+            }
+
+            let Some(program) = watched.program.upgrade() else {
+                continue;
+            };
+            match rebuilt {
+                Ok(rebuilt) => {
+                    program.replace(rebuilt);
+                    watched.last_error = None;
+                    eprintln!("Reloaded {} + {}", watched.vertex, watched.fragment);
+                }
+                Err(e) => {
+                    let error = format!("{e:#}");
+                    if watched.last_error.as_ref() != Some(&error) {
+                        eprintln!(
+                            "Failed to reload {} + {}, keeping the previous program: {error}",
+                            watched.vertex, watched.fragment
+                        );
+                        watched.last_error = Some(error);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the program and the files it was built from. The file list is
+    /// empty when preprocessing failed.
+    fn build(
+        &self,
+        archive: &EngineArchive,
+        vertex: &ShaderSource,
+        fragment: &ShaderSource,
+    ) -> (
+        Result<ShaderProgram>,
+        Vec<(ShaderSource, Option<SystemTime>)>,
+    ) {
+        let preprocess = |root: &ShaderSource| {
+            let root_name = root.to_string();
+            let load = |path: &str| {
+                let source = if path == root_name {
+                    root.clone()
+                } else {
+                    ShaderSource::Archive(path.to_string())
+                };
+                source.read(archive).map_err(|e| e.to_string())
+            };
+            match root {
+                ShaderSource::File(_) => self.preprocessor.process_file(&root_name, load),
+                ShaderSource::Archive(_) => self.preprocessor.process(&root_name, load),
+            }
+        };
+
+        let (vertex_source, fragment_source) = match (preprocess(vertex), preprocess(fragment)) {
+            (Ok(vertex), Ok(fragment)) => (vertex, fragment),
+            (Err(e), _) | (_, Err(e)) => return (Err(e.into()), Vec::new()),
+        };
+
+        let mut dependencies = vec![vertex.clone(), fragment.clone()];
+        for source in [&vertex_source, &fragment_source] {
+            dependencies.extend(
+                source.files[1..]
+                    .iter()
+                    .map(|path| ShaderSource::Archive(path.clone())),
+            );
+        }
+        let dependencies = dependencies
+            .into_iter()
+            .map(|source| {
+                let modified = source.modified(archive);
+                (source, modified)
+            })
+            .collect();
+
+        let program = ShaderProgram::from_preprocessed(&vertex_source, &fragment_source)
+            .map_err(|e| anyhow!(e));
+        (program, dependencies)
+    }
+}
@@ -0,0 +1,240 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+    shader_program::{
+        ProgramCache, ShaderCode, ShaderProgram, SpecializationValue,
+        reflection::{SpecConstantValue, SpirvReflection},
+    },
+    shaders::{CompiledShader, Fragment, Vertex},
+};
+
+pub struct PermutationCacheCreateInfo<'a> {
+    pub vertex: &'a CompiledShader<Vertex>,
+    pub fragment: &'a CompiledShader<Fragment>,
+    /// Stores the linked permutations on disk as well.
+    pub program_cache: Option<ProgramCache>,
+}
+
+struct Stage {
+    binary: Vec<u8>,
+    entry_point: String,
+    /// `constant_id`s declared by the module, with their defaults.
+    constants: HashMap<u32, SpecConstantValue>,
+}
+
+impl Stage {
+    fn new<S>(shader: &CompiledShader<S>) -> Result<Self, String> {
+        let reflection = SpirvReflection::parse(shader.spirv).map_err(|e| e.to_string())?;
+        let constants = reflection
+            .specialization_constants
+            .iter()
+            .map(|constant| (constant.id, constant.default))
+            .collect();
+
+        Ok(Self {
+            binary: shader.spirv.to_vec(),
+            entry_point: shader.entry_point.to_string(),
+            constants,
+        })
+    }
+
+    /// GL rejects constants the module doesn't declare, so each stage only gets
+    /// its own.
+    fn constants(
+        &self,
+        constants: &[(u32, SpecializationValue)],
+    ) -> Vec<(u32, SpecializationValue)> {
+        constants
+            .iter()
+            .filter(|(id, _)| self.constants.contains_key(id))
+            .copied()
+            .collect()
+    }
+
+    fn code<'a>(&'a self, constants: &'a [(u32, SpecializationValue)]) -> ShaderCode<'a> {
+        ShaderCode::SPIRV {
+            binary: &self.binary,
+            entry_point: &self.entry_point,
+            constants,
+        }
+    }
+}
+
+/// Constant ids and their bits, sorted by id.
+type PermutationKey = Vec<(u32, u32)>;
+
+/// Builds one [`ShaderProgram`] per set of specialization constants from the
+/// same pair of SPIR-V modules, on first use.
+pub struct PermutationCache {
+    vertex: Stage,
+    fragment: Stage,
+    program_cache: Option<ProgramCache>,
+    programs: RefCell<HashMap<PermutationKey, Rc<ShaderProgram>>>,
+}
+
+impl PermutationCache {
+    pub fn new(create_info: PermutationCacheCreateInfo) -> Result<Self, String> {
+        Ok(Self {
+            vertex: Stage::new(create_info.vertex)?,
+            fragment: Stage::new(create_info.fragment)?,
+            program_cache: create_info.program_cache,
+            programs: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Returns the program specialized with `constants`, building it if this
+    /// set hasn't been requested yet. The order of `constants` doesn't matter.
+    pub fn get(
+        &self,
+        constants: &[(u32, SpecializationValue)],
+    ) -> Result<Rc<ShaderProgram>, String> {
+        let constants = sorted(constants)?;
+        self.check(&constants)?;
+
+        let key: PermutationKey = constants
+            .iter()
+            .map(|(id, value)| (*id, value.bits()))
+            .collect();
+        if let Some(program) = self.programs.borrow().get(&key) {
+            return Ok(program.clone());
+        }
+
+        let vertex_constants = self.vertex.constants(&constants);
+        let fragment_constants = self.fragment.constants(&constants);
+        let vertex = self.vertex.code(&vertex_constants);
+        let fragment = self.fragment.code(&fragment_constants);
+
+        let program = match &self.program_cache {
+            Some(cache) => ShaderProgram::new_cached(vertex, fragment, cache)?,
+            None => ShaderProgram::new(vertex, fragment)?,
+        };
+        let program = Rc::new(program);
+        self.programs.borrow_mut().insert(key, program.clone());
+
+        Ok(program)
+    }
+
+    /// Every constant has to be declared by a stage, with the same type there.
+    fn check(&self, constants: &[(u32, SpecializationValue)]) -> Result<(), String> {
+        for (id, value) in constants {
+            let defaults = [&self.vertex, &self.fragment]
+                .into_iter()
+                .filter_map(|stage| stage.constants.get(id))
+                .collect::<Vec<_>>();
+            if defaults.is_empty() {
+                return Err(format!(
+                    "No stage declares a specialization constant with constant_id {id}"
+                ));
+            }
+            if let Some(default) = defaults.iter().find(|default| !same_type(value, default)) {
+                return Err(format!(
+                    "Specialization constant {id} is {default:?} in the shader, but {value:?} was given"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sorts `constants` by id and drops repeats. Giving one id two different
+/// values is an error.
+fn sorted(
+    constants: &[(u32, SpecializationValue)],
+) -> Result<Vec<(u32, SpecializationValue)>, String> {
+    let mut constants = constants.to_vec();
+    constants.sort_by_key(|(id, _)| *id);
+    if let Some(pair) = constants
+        .windows(2)
+        .find(|pair| pair[0].0 == pair[1].0 && pair[0].1 != pair[1].1)
+    {
+        return Err(format!(
+            "Specialization constant {} is given twice, as {:?} and {:?}",
+            pair[0].0, pair[0].1, pair[1].1
+        ));
+    }
+    constants.dedup_by_key(|(id, _)| *id);
+    Ok(constants)
+}
+
+fn same_type(value: &SpecializationValue, default: &SpecConstantValue) -> bool {
+    matches!(
+        (value, default),
+        (SpecializationValue::Bool(_), SpecConstantValue::Bool(_))
+            | (SpecializationValue::Int(_), SpecConstantValue::Int(_))
+            | (SpecializationValue::UInt(_), SpecConstantValue::UInt(_))
+            | (SpecializationValue::Float(_), SpecConstantValue::Float(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shader_program::reflection::tests::{module, spec_constant_ops};
+
+    fn cache() -> PermutationCache {
+        let vertex = CompiledShader::new(module(&spec_constant_ops()).leak(), "main");
+        let fragment = CompiledShader::new(module(&[]).leak(), "main");
+        PermutationCache::new(PermutationCacheCreateInfo {
+            vertex: &vertex,
+            fragment: &fragment,
+            program_cache: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn constants_of_the_declared_type_are_accepted() {
+        let constants = [
+            (0, SpecializationValue::Bool(false)),
+            (1, SpecializationValue::Int(-3)),
+            (2, SpecializationValue::Float(0.5)),
+        ];
+        assert_eq!(cache().check(&constants), Ok(()));
+    }
+
+    #[test]
+    fn undeclared_constants_are_errors() {
+        let error = cache()
+            .check(&[(7, SpecializationValue::UInt(1))])
+            .unwrap_err();
+        assert!(error.contains("constant_id 7"), "{error}");
+    }
+
+    #[test]
+    fn mismatched_types_are_errors() {
+        let cache = cache();
+        assert!(cache.check(&[(0, SpecializationValue::UInt(1))]).is_err());
+        assert!(
+            cache
+                .check(&[(1, SpecializationValue::Float(1.0))])
+                .is_err()
+        );
+        assert!(cache.check(&[(2, SpecializationValue::Int(1))]).is_err());
+    }
+
+    #[test]
+    fn repeated_constants_must_agree() {
+        let constants = sorted(&[
+            (2, SpecializationValue::Float(0.5)),
+            (0, SpecializationValue::Bool(true)),
+            (2, SpecializationValue::Float(0.5)),
+        ]);
+        assert_eq!(
+            constants,
+            Ok(vec![
+                (0, SpecializationValue::Bool(true)),
+                (2, SpecializationValue::Float(0.5)),
+            ])
+        );
+
+        let error = sorted(&[
+            (1, SpecializationValue::Int(3)),
+            (1, SpecializationValue::Int(4)),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "Specialization constant 1 is given twice, as Int(3) and Int(4)"
+        );
+    }
+}
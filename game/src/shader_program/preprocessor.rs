@@ -0,0 +1,462 @@
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::archive;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PreprocessError {
+    #[error("Failed to load \"{path}\": {message}")]
+    Load { path: String, message: String },
+
+    #[error("{file}:{line}: {message}")]
+    Syntax {
+        file: String,
+        line: usize,
+        message: String,
+    },
+
+    #[error("\"{0}\" has no #version directive")]
+    MissingVersion(String),
+}
+
+/// GLSL with its includes expanded. Source string `n` in `#line` directives,
+/// and therefore in driver info logs, is `files[n]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreprocessedSource {
+    pub source: String,
+    pub files: Vec<String>,
+}
+
+impl PreprocessedSource {
+    /// Rewrites the `0(12)` / `0:12` locations drivers put in info logs to
+    /// `file:line`.
+    pub fn map_log(&self, log: &str) -> String {
+        log.lines()
+            .map(|line| self.map_log_line(line))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn map_log_line(&self, line: &str) -> String {
+        let bytes = line.as_bytes();
+        let mut start = 0;
+        while start < bytes.len() {
+            let at_word_start = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+            if at_word_start
+                && let Some((file, line_number, end)) = parse_location(bytes, start)
+                && let Some(name) = self.files.get(file)
+            {
+                return format!("{}{name}:{line_number}{}", &line[..start], &line[end..]);
+            }
+            start += 1;
+        }
+        line.to_string()
+    }
+}
+
+/// Parses `<file>(<line>)` or `<file>:<line>` at `start`, returning both
+/// numbers and the end of the match.
+fn parse_location(bytes: &[u8], start: usize) -> Option<(usize, usize, usize)> {
+    let digits = |from: usize| {
+        let len = bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        let value = std::str::from_utf8(&bytes[from..from + len])
+            .ok()?
+            .parse()
+            .ok()?;
+        Some((value, from + len))
+    };
+
+    let (file, after_file) = digits(start)?;
+    let (close, sep) = match bytes.get(after_file)? {
+        b'(' => (Some(b')'), after_file + 1),
+        b':' => (None, after_file + 1),
+        _ => return None,
+    };
+    let (line, mut end) = digits(sep)?;
+    if let Some(close) = close {
+        if bytes.get(end) != Some(&close) {
+            return None;
+        }
+        end += 1;
+    }
+    Some((file, line, end))
+}
+
+/// Expands `#include "path"` and injects `#define`s into GLSL sources.
+///
+/// Includes are resolved by the loader passed to [`Self::process`], usually
+/// [`crate::archive::EngineArchive::load`], so this runs without a GL context.
+/// Include paths are archive paths, or relative to the including file when
+/// they start with `./` or `../`. Every file is included at most once. A root
+/// read from disk with [`Self::process_file`] has no directory in the archive,
+/// so it may only use archive paths.
+#[derive(Debug, Clone, Default)]
+pub struct Preprocessor {
+    defines: Vec<(String, String)>,
+}
+
+struct Expansion<'a, F> {
+    load: &'a mut F,
+    root_on_disk: bool,
+    output: String,
+    files: Vec<String>,
+    included: HashSet<String>,
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `#define name value` right after the `#version` line.
+    pub fn define(mut self, name: &str, value: impl ToString) -> Self {
+        self.defines.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Preprocesses `path`, loading it and its includes through `load`.
+    pub fn process<F>(&self, path: &str, load: F) -> Result<PreprocessedSource, PreprocessError>
+    where
+        F: FnMut(&str) -> Result<String, String>,
+    {
+        self.expand(path, false, load)
+    }
+
+    /// Like [`Self::process`], but `path` is a file on disk rather than an
+    /// archive path. `load` is still called with `path` for the root.
+    pub fn process_file<F>(
+        &self,
+        path: &str,
+        load: F,
+    ) -> Result<PreprocessedSource, PreprocessError>
+    where
+        F: FnMut(&str) -> Result<String, String>,
+    {
+        self.expand(path, true, load)
+    }
+
+    fn expand<F>(
+        &self,
+        path: &str,
+        root_on_disk: bool,
+        mut load: F,
+    ) -> Result<PreprocessedSource, PreprocessError>
+    where
+        F: FnMut(&str) -> Result<String, String>,
+    {
+        let root = load_file(&mut load, path)?;
+        let mut lines = root.lines().enumerate();
+
+        // Only comments and blank lines may precede #version, so they're dropped.
+        let (version_index, version_line) = lines
+            .by_ref()
+            .find(|(_, line)| is_directive(line, "version"))
+            .ok_or_else(|| PreprocessError::MissingVersion(path.to_string()))?;
+
+        let mut expansion = Expansion {
+            load: &mut load,
+            root_on_disk,
+            output: String::new(),
+            files: vec![path.to_string()],
+            included: HashSet::from(
+                [archive::normalize(path).unwrap_or_else(|_| path.to_string())],
+            ),
+        };
+        expansion.output.push_str(version_line);
+        expansion.output.push('\n');
+        for (name, value) in &self.defines {
+            expansion
+                .output
+                .push_str(&format!("#define {name} {value}\n"));
+        }
+        expansion
+            .output
+            .push_str(&format!("#line {} 0\n", version_index + 2));
+
+        expansion.expand_lines(path, 0, lines)?;
+
+        Ok(PreprocessedSource {
+            source: expansion.output,
+            files: expansion.files,
+        })
+    }
+}
+
+impl<F> Expansion<'_, F>
+where
+    F: FnMut(&str) -> Result<String, String>,
+{
+    fn expand_lines<'l>(
+        &mut self,
+        file: &str,
+        file_index: usize,
+        lines: impl Iterator<Item = (usize, &'l str)>,
+    ) -> Result<(), PreprocessError> {
+        for (index, line) in lines {
+            let line_number = index + 1;
+            let syntax_error = |message: &str| PreprocessError::Syntax {
+                file: file.to_string(),
+                line: line_number,
+                message: message.to_string(),
+            };
+
+            if is_directive(line, "version") {
+                return Err(syntax_error("#version is only allowed in the root file"));
+            }
+
+            if !is_directive(line, "include") {
+                self.output.push_str(line);
+                self.output.push('\n');
+                continue;
+            }
+
+            let include =
+                parse_include(line).ok_or_else(|| syntax_error("expected #include \"path\""))?;
+            if file_index == 0 && self.root_on_disk && is_relative(include) {
+                return Err(syntax_error(&format!(
+                    "\"{include}\" is relative, but this file is not in the archive; \
+                     include it by its archive path"
+                )));
+            }
+            let path = resolve_include(file, include).map_err(|e| syntax_error(&e))?;
+            if self.included.insert(path.clone()) {
+                self.include(&path)?;
+            }
+            self.output
+                .push_str(&format!("#line {} {file_index}\n", line_number + 1));
+        }
+        Ok(())
+    }
+
+    fn include(&mut self, path: &str) -> Result<(), PreprocessError> {
+        let source = load_file(self.load, path)?;
+        let file_index = self.files.len();
+        self.files.push(path.to_string());
+
+        self.output.push_str(&format!("#line 1 {file_index}\n"));
+        self.expand_lines(path, file_index, source.lines().enumerate())
+    }
+}
+
+fn load_file<F>(load: &mut F, path: &str) -> Result<String, PreprocessError>
+where
+    F: FnMut(&str) -> Result<String, String>,
+{
+    load(path).map_err(|message| PreprocessError::Load {
+        path: path.to_string(),
+        message,
+    })
+}
+
+/// The normalized archive path of `include`, which is relative to the
+/// directory of `from` if it starts with `./` or `../`.
+fn resolve_include(from: &str, include: &str) -> Result<String, String> {
+    let include = include.replace('\\', "/");
+    if !is_relative(&include) {
+        return archive::normalize(&include).map_err(|e| e.to_string());
+    }
+
+    let from = from.replace('\\', "/");
+    let mut segments: Vec<&str> = from.split('/').collect();
+    segments.pop();
+    for segment in include.split('/') {
+        match segment {
+            "." => {}
+            ".." => {
+                segments
+                    .pop()
+                    .ok_or_else(|| format!("\"{include}\" leaves the archive root"))?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    archive::normalize(&segments.join("/")).map_err(|e| e.to_string())
+}
+
+fn is_relative(include: &str) -> bool {
+    ["./", "../", ".\\", "..\\"]
+        .iter()
+        .any(|prefix| include.starts_with(prefix))
+}
+
+fn is_directive(line: &str, name: &str) -> bool {
+    line.trim_start()
+        .strip_prefix('#')
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix(name))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+fn parse_include(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    let rest = rest.strip_prefix("include")?.trim();
+    rest.strip_prefix('"')?.strip_suffix('"')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn process(
+        preprocessor: &Preprocessor,
+        root: &str,
+        files: &[(&str, &str)],
+    ) -> Result<PreprocessedSource, PreprocessError> {
+        let files: HashMap<_, _> = files.iter().copied().collect();
+        preprocessor.process(root, |path| {
+            files
+                .get(path)
+                .map(|source| source.to_string())
+                .ok_or_else(|| "not found".to_string())
+        })
+    }
+
+    #[test]
+    fn version_comes_first_followed_by_defines() {
+        let preprocessor = Preprocessor::new().define("LIGHTS", 4);
+        let source = process(
+            &preprocessor,
+            "main.frag",
+            &[(
+                "main.frag",
+                "// header\n\n#version 460 core\nvoid main() {}\n",
+            )],
+        )
+        .unwrap();
+
+        assert_eq!(
+            source.source,
+            "#version 460 core\n#define LIGHTS 4\n#line 4 0\nvoid main() {}\n"
+        );
+        assert_eq!(source.files, ["main.frag"]);
+    }
+
+    #[test]
+    fn files_are_included_once() {
+        let source = process(
+            &Preprocessor::new(),
+            "shaders/main.frag",
+            &[
+                (
+                    "shaders/main.frag",
+                    "#version 460\n#include \"common/a.glsl\"\n#include \"Common\\A.glsl\"\n#include \"./lib.glsl\"\n",
+                ),
+                ("common/a.glsl", "float a;\n"),
+                ("shaders/lib.glsl", "#include \"../common/a.glsl\"\nfloat b;\n"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            source.files,
+            ["shaders/main.frag", "common/a.glsl", "shaders/lib.glsl"]
+        );
+        assert_eq!(source.source.matches("float a;").count(), 1);
+        assert!(source.source.contains("#line 1 2\n#line 2 2\nfloat b;"));
+    }
+
+    #[test]
+    fn includes_may_not_leave_the_archive() {
+        let error = process(
+            &Preprocessor::new(),
+            "main.frag",
+            &[("main.frag", "#version 460\n#include \"../secret.glsl\"\n")],
+        )
+        .unwrap_err();
+
+        assert!(
+            matches!(error, PreprocessError::Syntax { line: 2, .. }),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn roots_on_disk_only_include_archive_paths() {
+        let files: HashMap<_, _> = [
+            (
+                "/home/dev/shaders/main.frag",
+                "#version 460\n#include \"common/a.glsl\"\n",
+            ),
+            (
+                "/home/dev/shaders/relative.frag",
+                "#version 460\n#include \"./a.glsl\"\n",
+            ),
+            ("common/a.glsl", "#include \"./b.glsl\"\n"),
+            ("common/b.glsl", "float b;\n"),
+        ]
+        .into_iter()
+        .collect();
+        let process_file = |root| {
+            Preprocessor::new().process_file(root, |path| {
+                files
+                    .get(path)
+                    .map(|source| source.to_string())
+                    .ok_or_else(|| "not found".to_string())
+            })
+        };
+
+        // Archive files included from a disk root still resolve relative paths.
+        let source = process_file("/home/dev/shaders/main.frag").unwrap();
+        assert_eq!(
+            source.files,
+            [
+                "/home/dev/shaders/main.frag",
+                "common/a.glsl",
+                "common/b.glsl"
+            ]
+        );
+
+        let error = process_file("/home/dev/shaders/relative.frag").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "/home/dev/shaders/relative.frag:2: \"./a.glsl\" is relative, but this file is \
+             not in the archive; include it by its archive path"
+        );
+    }
+
+    #[test]
+    fn missing_version_and_nested_version_are_errors() {
+        let missing = process(
+            &Preprocessor::new(),
+            "a.frag",
+            &[("a.frag", "void main() {}")],
+        );
+        assert_eq!(
+            missing,
+            Err(PreprocessError::MissingVersion("a.frag".to_string()))
+        );
+
+        let nested = process(
+            &Preprocessor::new(),
+            "a.frag",
+            &[
+                ("a.frag", "#version 460\n#include \"b.glsl\"\n"),
+                ("b.glsl", "#version 460\n"),
+            ],
+        );
+        assert!(matches!(
+            nested,
+            Err(PreprocessError::Syntax { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn log_locations_map_to_files_and_lines() {
+        let source = PreprocessedSource {
+            source: String::new(),
+            files: vec!["main.frag".to_string(), "common/a.glsl".to_string()],
+        };
+        let log =
+            "0(12) : error C0000: syntax error\nERROR: 1:3: 'x' : undeclared\nERROR: 5:1: unknown";
+
+        assert_eq!(
+            source.map_log(log),
+            "main.frag:12 : error C0000: syntax error\nERROR: common/a.glsl:3: 'x' : undeclared\nERROR: 5:1: unknown"
+        );
+    }
+}
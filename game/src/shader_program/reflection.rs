@@ -0,0 +1,698 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+const MAGIC: u32 = 0x0723_0203;
+const HEADER_WORDS: usize = 5;
+/// Deeper type trees are treated as cyclic. Real shaders stay far below this.
+const MAX_TYPE_DEPTH: usize = 64;
+
+mod op {
+    pub const NAME: u16 = 5;
+    pub const ENTRY_POINT: u16 = 15;
+    pub const TYPE_BOOL: u16 = 20;
+    pub const TYPE_INT: u16 = 21;
+    pub const TYPE_FLOAT: u16 = 22;
+    pub const TYPE_VECTOR: u16 = 23;
+    pub const TYPE_MATRIX: u16 = 24;
+    pub const TYPE_IMAGE: u16 = 25;
+    pub const TYPE_SAMPLER: u16 = 26;
+    pub const TYPE_SAMPLED_IMAGE: u16 = 27;
+    pub const TYPE_ARRAY: u16 = 28;
+    pub const TYPE_RUNTIME_ARRAY: u16 = 29;
+    pub const TYPE_STRUCT: u16 = 30;
+    pub const TYPE_POINTER: u16 = 32;
+    pub const CONSTANT: u16 = 43;
+    pub const SPEC_CONSTANT_TRUE: u16 = 48;
+    pub const SPEC_CONSTANT_FALSE: u16 = 49;
+    pub const SPEC_CONSTANT: u16 = 50;
+    pub const VARIABLE: u16 = 59;
+    pub const DECORATE: u16 = 71;
+
+    /// Operands the parser reads from each instruction, not counting the
+    /// opcode word.
+    pub fn min_operands(opcode: u16) -> usize {
+        match opcode {
+            TYPE_BOOL | TYPE_IMAGE | TYPE_SAMPLER | TYPE_STRUCT => 1,
+            NAME | TYPE_FLOAT | TYPE_SAMPLED_IMAGE | TYPE_RUNTIME_ARRAY | DECORATE
+            | SPEC_CONSTANT_TRUE | SPEC_CONSTANT_FALSE => 2,
+            ENTRY_POINT | TYPE_INT | TYPE_VECTOR | TYPE_MATRIX | TYPE_ARRAY | TYPE_POINTER
+            | CONSTANT | SPEC_CONSTANT | VARIABLE => 3,
+            _ => 0,
+        }
+    }
+}
+
+mod decoration {
+    pub const SPEC_ID: u32 = 1;
+    pub const BLOCK: u32 = 2;
+    pub const BUFFER_BLOCK: u32 = 3;
+    pub const BUILT_IN: u32 = 11;
+    pub const LOCATION: u32 = 30;
+    pub const BINDING: u32 = 33;
+    pub const DESCRIPTOR_SET: u32 = 34;
+}
+
+mod storage_class {
+    pub const UNIFORM_CONSTANT: u32 = 0;
+    pub const INPUT: u32 = 1;
+    pub const UNIFORM: u32 = 2;
+    pub const OUTPUT: u32 = 3;
+    pub const STORAGE_BUFFER: u32 = 12;
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ReflectionError {
+    #[error("SPIR-V module length is not a multiple of 4 bytes")]
+    UnalignedLength,
+
+    #[error("SPIR-V module is missing its header")]
+    MissingHeader,
+
+    #[error("Invalid SPIR-V magic number: {0:#010x}")]
+    InvalidMagic(u32),
+
+    #[error("Truncated SPIR-V instruction at word {0}")]
+    TruncatedInstruction(usize),
+
+    #[error("SPIR-V type {0} refers to itself or nests too deeply")]
+    CyclicType(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpirvType {
+    Bool,
+    Int {
+        width: u32,
+        signed: bool,
+    },
+    Float {
+        width: u32,
+    },
+    Vector {
+        component: Box<SpirvType>,
+        count: u32,
+    },
+    Matrix {
+        column: Box<SpirvType>,
+        columns: u32,
+    },
+    /// `length` is `None` for runtime arrays or lengths set by spec constants.
+    Array {
+        element: Box<SpirvType>,
+        length: Option<u32>,
+    },
+    Struct {
+        members: Vec<SpirvType>,
+    },
+    Image,
+    Sampler,
+    SampledImage,
+    Unknown,
+}
+
+impl SpirvType {
+    /// The scalar type and component count of a scalar or vector.
+    pub fn scalar_components(&self) -> Option<(&SpirvType, u32)> {
+        match self {
+            SpirvType::Bool | SpirvType::Int { .. } | SpirvType::Float { .. } => Some((self, 1)),
+            SpirvType::Vector { component, count } => Some((component, *count)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint {
+    /// SPIR-V `ExecutionModel`: 0 vertex, 4 fragment, 5 compute, ...
+    pub execution_model: u32,
+    pub name: String,
+}
+
+/// A user-defined `in` or `out` variable. Built-ins are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceVariable {
+    pub name: Option<String>,
+    pub location: u32,
+    pub r#type: SpirvType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    UniformBuffer,
+    StorageBuffer,
+    SampledImage,
+    Image,
+    Sampler,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceBinding {
+    pub name: Option<String>,
+    pub set: u32,
+    pub binding: u32,
+    pub kind: BindingKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecConstantValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecializationConstant {
+    pub name: Option<String>,
+    pub id: u32,
+    pub default: SpecConstantValue,
+}
+
+/// What a SPIR-V module exposes to the rest of the pipeline, read without a
+/// GL context.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpirvReflection {
+    pub entry_points: Vec<EntryPoint>,
+    pub inputs: Vec<InterfaceVariable>,
+    pub outputs: Vec<InterfaceVariable>,
+    pub bindings: Vec<ResourceBinding>,
+    pub specialization_constants: Vec<SpecializationConstant>,
+}
+
+#[derive(Default)]
+struct Decorations {
+    location: Option<u32>,
+    binding: Option<u32>,
+    set: Option<u32>,
+    spec_id: Option<u32>,
+    built_in: bool,
+    block: bool,
+    buffer_block: bool,
+}
+
+struct Variable {
+    id: u32,
+    pointer_type: u32,
+    storage_class: u32,
+}
+
+/// Raw type instructions, resolved into [`SpirvType`]s once every id is known.
+enum TypeDef {
+    Bool,
+    Int(u32, bool),
+    Float(u32),
+    Vector(u32, u32),
+    Matrix(u32, u32),
+    Array(u32, Option<u32>),
+    Struct(Vec<u32>),
+    Pointer(u32),
+    Image,
+    Sampler,
+    SampledImage,
+}
+
+#[derive(Default)]
+struct Module {
+    names: HashMap<u32, String>,
+    decorations: HashMap<u32, Decorations>,
+    types: HashMap<u32, TypeDef>,
+    constants: HashMap<u32, u32>,
+    spec_constants: Vec<(u32, u32, Vec<u32>)>,
+    variables: Vec<Variable>,
+    entry_points: Vec<EntryPoint>,
+}
+
+impl SpirvReflection {
+    pub fn parse(bytes: &[u8]) -> Result<Self, ReflectionError> {
+        if !bytes.len().is_multiple_of(4) {
+            return Err(ReflectionError::UnalignedLength);
+        }
+        let mut words: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        if words.len() < HEADER_WORDS {
+            return Err(ReflectionError::MissingHeader);
+        }
+        if words[0] == MAGIC.swap_bytes() {
+            words.iter_mut().for_each(|word| *word = word.swap_bytes());
+        }
+        if words[0] != MAGIC {
+            return Err(ReflectionError::InvalidMagic(words[0]));
+        }
+
+        let module = Module::read(&words)?;
+        module.reflect()
+    }
+}
+
+impl Module {
+    fn read(words: &[u32]) -> Result<Self, ReflectionError> {
+        let mut module = Module::default();
+        let mut offset = HEADER_WORDS;
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            let opcode = (words[offset] & 0xffff) as u16;
+            if word_count == 0
+                || offset + word_count > words.len()
+                || word_count - 1 < op::min_operands(opcode)
+            {
+                return Err(ReflectionError::TruncatedInstruction(offset));
+            }
+            module.instruction(opcode, &words[offset + 1..offset + word_count]);
+            offset += word_count;
+        }
+        Ok(module)
+    }
+
+    /// `operands` holds at least [`op::min_operands`] words.
+    fn instruction(&mut self, opcode: u16, operands: &[u32]) {
+        let operand = |index: usize| operands.get(index).copied().unwrap_or(0);
+        let rest = |index: usize| operands.get(index..).unwrap_or(&[]);
+        match opcode {
+            op::NAME => {
+                self.names.insert(operand(0), read_string(rest(1)));
+            }
+            op::ENTRY_POINT => {
+                let name = read_string(rest(2));
+                self.entry_points.push(EntryPoint {
+                    execution_model: operand(0),
+                    name,
+                });
+            }
+            op::DECORATE => {
+                let entry = self.decorations.entry(operand(0)).or_default();
+                match operand(1) {
+                    decoration::LOCATION => entry.location = Some(operand(2)),
+                    decoration::BINDING => entry.binding = Some(operand(2)),
+                    decoration::DESCRIPTOR_SET => entry.set = Some(operand(2)),
+                    decoration::SPEC_ID => entry.spec_id = Some(operand(2)),
+                    decoration::BUILT_IN => entry.built_in = true,
+                    decoration::BLOCK => entry.block = true,
+                    decoration::BUFFER_BLOCK => entry.buffer_block = true,
+                    _ => {}
+                }
+            }
+            op::TYPE_BOOL => self.define(operand(0), TypeDef::Bool),
+            op::TYPE_INT => self.define(operand(0), TypeDef::Int(operand(1), operand(2) != 0)),
+            op::TYPE_FLOAT => self.define(operand(0), TypeDef::Float(operand(1))),
+            op::TYPE_VECTOR => self.define(operand(0), TypeDef::Vector(operand(1), operand(2))),
+            op::TYPE_MATRIX => self.define(operand(0), TypeDef::Matrix(operand(1), operand(2))),
+            op::TYPE_IMAGE => self.define(operand(0), TypeDef::Image),
+            op::TYPE_SAMPLER => self.define(operand(0), TypeDef::Sampler),
+            op::TYPE_SAMPLED_IMAGE => self.define(operand(0), TypeDef::SampledImage),
+            op::TYPE_ARRAY => {
+                let length = self.constants.get(&operand(2)).copied();
+                self.define(operand(0), TypeDef::Array(operand(1), length));
+            }
+            op::TYPE_RUNTIME_ARRAY => self.define(operand(0), TypeDef::Array(operand(1), None)),
+            op::TYPE_STRUCT => self.define(operand(0), TypeDef::Struct(rest(1).to_vec())),
+            op::TYPE_POINTER => self.define(operand(0), TypeDef::Pointer(operand(2))),
+            op::CONSTANT => {
+                self.constants.insert(operand(1), operand(2));
+            }
+            op::SPEC_CONSTANT_TRUE => self.spec_constants.push((operand(0), operand(1), vec![1])),
+            op::SPEC_CONSTANT_FALSE => self.spec_constants.push((operand(0), operand(1), vec![0])),
+            op::SPEC_CONSTANT => {
+                self.spec_constants
+                    .push((operand(0), operand(1), rest(2).to_vec()));
+            }
+            op::VARIABLE => self.variables.push(Variable {
+                pointer_type: operand(0),
+                id: operand(1),
+                storage_class: operand(2),
+            }),
+            _ => {}
+        }
+    }
+
+    fn define(&mut self, id: u32, def: TypeDef) {
+        self.types.insert(id, def);
+    }
+
+    fn resolve(&self, id: u32) -> Result<SpirvType, ReflectionError> {
+        self.resolve_nested(id, 0)
+    }
+
+    fn resolve_nested(&self, id: u32, depth: usize) -> Result<SpirvType, ReflectionError> {
+        if depth > MAX_TYPE_DEPTH {
+            return Err(ReflectionError::CyclicType(id));
+        }
+        let boxed = |id| self.resolve_nested(id, depth + 1).map(Box::new);
+        Ok(match self.types.get(&id) {
+            Some(TypeDef::Bool) => SpirvType::Bool,
+            Some(TypeDef::Int(width, signed)) => SpirvType::Int {
+                width: *width,
+                signed: *signed,
+            },
+            Some(TypeDef::Float(width)) => SpirvType::Float { width: *width },
+            Some(TypeDef::Vector(component, count)) => SpirvType::Vector {
+                component: boxed(*component)?,
+                count: *count,
+            },
+            Some(TypeDef::Matrix(column, columns)) => SpirvType::Matrix {
+                column: boxed(*column)?,
+                columns: *columns,
+            },
+            Some(TypeDef::Array(element, length)) => SpirvType::Array {
+                element: boxed(*element)?,
+                length: *length,
+            },
+            Some(TypeDef::Struct(members)) => SpirvType::Struct {
+                members: members
+                    .iter()
+                    .map(|member| self.resolve_nested(*member, depth + 1))
+                    .collect::<Result<_, _>>()?,
+            },
+            Some(TypeDef::Pointer(pointee)) => self.resolve_nested(*pointee, depth + 1)?,
+            Some(TypeDef::Image) => SpirvType::Image,
+            Some(TypeDef::Sampler) => SpirvType::Sampler,
+            Some(TypeDef::SampledImage) => SpirvType::SampledImage,
+            None => SpirvType::Unknown,
+        })
+    }
+
+    /// The type a pointer points to, looking through arrays of resources.
+    fn pointee(&self, pointer: u32) -> Option<u32> {
+        let mut id = match self.types.get(&pointer)? {
+            TypeDef::Pointer(pointee) => *pointee,
+            _ => return None,
+        };
+        // Bounded, so an array of itself can't loop forever.
+        for _ in 0..MAX_TYPE_DEPTH {
+            let Some(TypeDef::Array(element, _)) = self.types.get(&id) else {
+                break;
+            };
+            id = *element;
+        }
+        Some(id)
+    }
+
+    fn binding_kind(&self, variable: &Variable) -> BindingKind {
+        let Some(pointee) = self.pointee(variable.pointer_type) else {
+            return BindingKind::Other;
+        };
+        let decorations = self.decorations.get(&pointee);
+        let buffer_block = decorations.is_some_and(|d| d.buffer_block);
+        match (variable.storage_class, self.types.get(&pointee)) {
+            (storage_class::STORAGE_BUFFER, _) => BindingKind::StorageBuffer,
+            (storage_class::UNIFORM, _) if buffer_block => BindingKind::StorageBuffer,
+            (storage_class::UNIFORM, _) => BindingKind::UniformBuffer,
+            (_, Some(TypeDef::SampledImage)) => BindingKind::SampledImage,
+            (_, Some(TypeDef::Image)) => BindingKind::Image,
+            (_, Some(TypeDef::Sampler)) => BindingKind::Sampler,
+            _ => BindingKind::Other,
+        }
+    }
+
+    fn spec_constant_value(&self, type_id: u32, words: &[u32]) -> SpecConstantValue {
+        let low = words.first().copied().unwrap_or(0) as u64;
+        let high = words.get(1).copied().unwrap_or(0) as u64;
+        match self.types.get(&type_id) {
+            Some(TypeDef::Bool) => SpecConstantValue::Bool(low != 0),
+            Some(TypeDef::Int(64, true)) => SpecConstantValue::Int((high << 32 | low) as i64),
+            Some(TypeDef::Int(_, true)) => SpecConstantValue::Int(low as u32 as i32 as i64),
+            Some(TypeDef::Float(64)) => SpecConstantValue::Float(f64::from_bits(high << 32 | low)),
+            Some(TypeDef::Float(_)) => SpecConstantValue::Float(f32::from_bits(low as u32) as f64),
+            Some(TypeDef::Int(64, false)) => SpecConstantValue::UInt(high << 32 | low),
+            _ => SpecConstantValue::UInt(low),
+        }
+    }
+
+    fn reflect(self) -> Result<SpirvReflection, ReflectionError> {
+        let mut reflection = SpirvReflection {
+            entry_points: self.entry_points.clone(),
+            ..Default::default()
+        };
+        let no_decorations = Decorations::default();
+
+        for variable in &self.variables {
+            let decorations = self
+                .decorations
+                .get(&variable.id)
+                .unwrap_or(&no_decorations);
+            let name = self.names.get(&variable.id).cloned();
+
+            match variable.storage_class {
+                storage_class::INPUT | storage_class::OUTPUT => {
+                    let Some(location) = decorations.location else {
+                        continue;
+                    };
+                    if decorations.built_in {
+                        continue;
+                    }
+                    let interface_variable = InterfaceVariable {
+                        name,
+                        location,
+                        r#type: self.resolve(variable.pointer_type)?,
+                    };
+                    if variable.storage_class == storage_class::INPUT {
+                        reflection.inputs.push(interface_variable);
+                    } else {
+                        reflection.outputs.push(interface_variable);
+                    }
+                }
+                storage_class::UNIFORM_CONSTANT
+                | storage_class::UNIFORM
+                | storage_class::STORAGE_BUFFER => {
+                    let Some(binding) = decorations.binding else {
+                        continue;
+                    };
+                    reflection.bindings.push(ResourceBinding {
+                        name,
+                        set: decorations.set.unwrap_or(0),
+                        binding,
+                        kind: self.binding_kind(variable),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        for (type_id, id, words) in &self.spec_constants {
+            let Some(spec_id) = self.decorations.get(id).and_then(|d| d.spec_id) else {
+                continue;
+            };
+            reflection
+                .specialization_constants
+                .push(SpecializationConstant {
+                    name: self.names.get(id).cloned(),
+                    id: spec_id,
+                    default: self.spec_constant_value(*type_id, words),
+                });
+        }
+
+        reflection.inputs.sort_by_key(|input| input.location);
+        reflection.outputs.sort_by_key(|output| output.location);
+        Ok(reflection)
+    }
+}
+
+/// Reads a nul-terminated literal string.
+fn read_string(words: &[u32]) -> String {
+    let bytes: Vec<u8> = words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .take_while(|byte| *byte != 0)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// How a vertex shader reads an attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Float,
+    Integer,
+    Double,
+}
+
+/// A vertex shader input, from SPIR-V reflection or the linked program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VertexInput {
+    pub name: String,
+    pub location: u32,
+    pub kind: InputKind,
+    pub components: u32,
+}
+
+impl VertexInput {
+    /// `None` for inputs that aren't scalars or vectors, e.g. matrices.
+    pub fn from_spirv(variable: &InterfaceVariable) -> Option<Self> {
+        let (scalar, components) = variable.r#type.scalar_components()?;
+        let kind = match scalar {
+            SpirvType::Float { width: 64 } => InputKind::Double,
+            SpirvType::Float { .. } => InputKind::Float,
+            SpirvType::Int { .. } | SpirvType::Bool => InputKind::Integer,
+            _ => return None,
+        };
+
+        Some(Self {
+            name: variable.name.clone().unwrap_or_default(),
+            location: variable.location,
+            kind,
+            components,
+        })
+    }
+
+    /// `None` for built-ins and inputs that aren't scalars or vectors.
+    pub fn from_program_resource(resource: &gl::ProgramResource) -> Option<Self> {
+        let location = u32::try_from(resource.location?).ok()?;
+        let (scalar, components) = resource.vector_type()?;
+        let kind = match scalar {
+            gl::ScalarType::Float => InputKind::Float,
+            gl::ScalarType::Double => InputKind::Double,
+            gl::ScalarType::Bool | gl::ScalarType::Int | gl::ScalarType::UnsignedInt => {
+                InputKind::Integer
+            }
+        };
+
+        Some(Self {
+            name: resource.name.clone(),
+            location,
+            kind,
+            components,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A module made of a header and `instructions`, each an opcode and its
+    /// operands.
+    pub(crate) fn module(instructions: &[(u16, &[u32])]) -> Vec<u8> {
+        let mut words = vec![MAGIC, 0x0001_0000, 0, 100, 0];
+        for (opcode, operands) in instructions {
+            words.push(((operands.len() as u32 + 1) << 16) | *opcode as u32);
+            words.extend_from_slice(operands);
+        }
+        words.iter().flat_map(|word| word.to_le_bytes()).collect()
+    }
+
+    /// A bool, an int and a float constant with `constant_id`s 0, 1 and 2.
+    pub(crate) fn spec_constant_ops() -> Vec<(u16, &'static [u32])> {
+        vec![
+            (op::TYPE_BOOL, &[1]),
+            (op::TYPE_INT, &[2, 32, 1]),
+            (op::TYPE_FLOAT, &[3, 32]),
+            (op::SPEC_CONSTANT_TRUE, &[1, 4]),
+            (op::SPEC_CONSTANT, &[2, 5, 7]),
+            (op::SPEC_CONSTANT, &[3, 6, 0x3f80_0000]),
+            (op::DECORATE, &[4, decoration::SPEC_ID, 0]),
+            (op::DECORATE, &[5, decoration::SPEC_ID, 1]),
+            (op::DECORATE, &[6, decoration::SPEC_ID, 2]),
+        ]
+    }
+
+    #[test]
+    fn reflects_specialization_constants() {
+        let reflection = SpirvReflection::parse(&module(&spec_constant_ops())).unwrap();
+        let constants: Vec<_> = reflection
+            .specialization_constants
+            .iter()
+            .map(|constant| (constant.id, constant.default))
+            .collect();
+        assert_eq!(
+            constants,
+            [
+                (0, SpecConstantValue::Bool(true)),
+                (1, SpecConstantValue::Int(7)),
+                (2, SpecConstantValue::Float(1.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn slang_entry_points_match_the_generated_constants() {
+        let check = |spirv, entry_point, execution_model| {
+            let reflection = SpirvReflection::parse(spirv).unwrap();
+            assert_eq!(reflection.entry_points.len(), 1);
+            assert_eq!(reflection.entry_points[0].name, entry_point);
+            assert_eq!(reflection.entry_points[0].execution_model, execution_model);
+        };
+        let vertex = &crate::shaders::SLANG_VERTEX2;
+        let fragment = &crate::shaders::SLANG_FRAGMENT2;
+        check(vertex.spirv, vertex.entry_point, 0);
+        check(fragment.spirv, fragment.entry_point, 4);
+    }
+
+    #[test]
+    fn reflects_the_vertex_shader() {
+        let reflection = SpirvReflection::parse(crate::shaders::GLSL_VERTEX.spirv).unwrap();
+
+        assert_eq!(reflection.entry_points.len(), 1);
+        assert_eq!(reflection.entry_points[0].execution_model, 0);
+        assert_eq!(reflection.entry_points[0].name, "main");
+
+        let float = Box::new(SpirvType::Float { width: 32 });
+        let vec = |count| SpirvType::Vector {
+            component: float.clone(),
+            count,
+        };
+        let inputs: Vec<_> = reflection
+            .inputs
+            .iter()
+            .map(|input| (input.name.as_deref(), input.location, input.r#type.clone()))
+            .collect();
+        assert_eq!(
+            inputs,
+            [
+                (Some("in_position"), 0, vec(3)),
+                (Some("in_color"), 1, vec(3)),
+                (Some("in_uv"), 2, vec(2)),
+            ]
+        );
+        let outputs: Vec<_> = reflection.outputs.iter().map(|o| o.location).collect();
+        assert_eq!(outputs, [0, 1]);
+    }
+
+    #[test]
+    fn rejects_malformed_modules() {
+        assert_eq!(
+            SpirvReflection::parse(&[0; 6]),
+            Err(ReflectionError::UnalignedLength)
+        );
+        assert_eq!(
+            SpirvReflection::parse(&[0; 8]),
+            Err(ReflectionError::MissingHeader)
+        );
+        assert_eq!(
+            SpirvReflection::parse(&[0; 20]),
+            Err(ReflectionError::InvalidMagic(0))
+        );
+    }
+
+    #[test]
+    fn short_instructions_are_errors() {
+        for instruction in [
+            (op::NAME, &[1][..]),
+            (op::ENTRY_POINT, &[0, 1][..]),
+            (op::TYPE_POINTER, &[1, 1][..]),
+            (op::SPEC_CONSTANT, &[1, 2][..]),
+        ] {
+            assert_eq!(
+                SpirvReflection::parse(&module(&[instruction])),
+                Err(ReflectionError::TruncatedInstruction(HEADER_WORDS)),
+                "opcode {}",
+                instruction.0
+            );
+        }
+    }
+
+    #[test]
+    fn self_referential_types_are_errors() {
+        let bytes = module(&[
+            (op::TYPE_POINTER, &[1, storage_class::INPUT, 2]),
+            (op::TYPE_STRUCT, &[2, 2]),
+            (op::VARIABLE, &[1, 3, storage_class::INPUT]),
+            (op::DECORATE, &[3, decoration::LOCATION, 0]),
+        ]);
+        assert_eq!(
+            SpirvReflection::parse(&bytes),
+            Err(ReflectionError::CyclicType(2))
+        );
+    }
+}
@@ -11,33 +11,76 @@ pub enum ShaderSourceType {
     SPIRV,
 }
 
+/// Value of a SPIR-V specialization constant. Must match the constant's type
+/// in the shader.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecializationValue {
+    Bool(bool),
+    Int(i32),
+    UInt(u32),
+    Float(f32),
+}
+
+impl SpecializationValue {
+    /// The 32 bits `glSpecializeShader` expects.
+    pub fn bits(self) -> u32 {
+        match self {
+            SpecializationValue::Bool(value) => value as u32,
+            SpecializationValue::Int(value) => value as u32,
+            SpecializationValue::UInt(value) => value,
+            SpecializationValue::Float(value) => value.to_bits(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
 pub enum ShaderCode<'a> {
     GLSL(&'a str),
-    SPIRV(&'a [u8]),
+    /// `constants` pairs `constant_id`s with their values; constants not listed
+    /// keep the default from the module.
+    SPIRV {
+        binary: &'a [u8],
+        entry_point: &'a str,
+        constants: &'a [(u32, SpecializationValue)],
+    },
 }
 
 impl Shader {
-    pub fn new(code: ShaderCode, shader_type: gl::ShaderType) -> Self {
+    pub fn new(code: ShaderCode, shader_type: gl::ShaderType) -> Result<Self, gl::ShaderError> {
         match code {
             ShaderCode::GLSL(source) => Self::from_glsl(source, shader_type),
-            ShaderCode::SPIRV(binary) => Self::from_spirv(binary, shader_type),
+            ShaderCode::SPIRV {
+                binary,
+                entry_point,
+                constants,
+            } => Self::from_spirv(binary, entry_point, constants, shader_type),
         }
     }
 
-    pub fn from_glsl(source: &str, shader_type: gl::ShaderType) -> Self {
+    pub fn from_glsl(source: &str, shader_type: gl::ShaderType) -> Result<Self, gl::ShaderError> {
         let mut shader = gl::Shader::create(shader_type);
         shader.source(&[source]);
-        shader.compile();
+        shader.compile()?;
 
-        Self { shader }
+        Ok(Self { shader })
     }
 
-    pub fn from_spirv(binary: &[u8], shader_type: gl::ShaderType) -> Self {
+    pub fn from_spirv(
+        binary: &[u8],
+        entry_point: &str,
+        constants: &[(u32, SpecializationValue)],
+        shader_type: gl::ShaderType,
+    ) -> Result<Self, gl::ShaderError> {
+        let constants: Vec<(u32, u32)> = constants
+            .iter()
+            .map(|(id, value)| (*id, value.bits()))
+            .collect();
+
         let mut shader = gl::Shader::create(shader_type);
         shader.binary(binary);
-        shader.specialize("main", &[]);
+        shader.specialize(entry_point, &constants)?;
 
-        Self { shader }
+        Ok(Self { shader })
     }
 
     pub fn shader(&self) -> &gl::Shader {
@@ -0,0 +1,34 @@
+//! SPIR-V for every shader in `src/shaders`, compiled by `build.rs`.
+
+use std::marker::PhantomData;
+
+pub enum Vertex {}
+pub enum Fragment {}
+
+/// A compiled stage. The type parameter keeps vertex and fragment stages from
+/// being swapped.
+pub struct CompiledShader<Stage> {
+    pub spirv: &'static [u8],
+    pub entry_point: &'static str,
+    stage: PhantomData<Stage>,
+}
+
+impl<Stage> CompiledShader<Stage> {
+    pub(crate) const fn new(spirv: &'static [u8], entry_point: &'static str) -> Self {
+        Self {
+            spirv,
+            entry_point,
+            stage: PhantomData,
+        }
+    }
+}
+
+// Every file in `src/shaders` gets a constant, whether or not the game uses it.
+#[allow(dead_code)]
+mod generated {
+    use super::{CompiledShader, Fragment, Vertex};
+
+    include!(concat!(env!("OUT_DIR"), "/shaders.rs"));
+}
+
+pub use generated::*;
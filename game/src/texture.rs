@@ -57,6 +57,25 @@ impl From<TextureCreateInfo> for Texture {
 }
 
 impl Texture {
+    /// A 2x2 magenta and black checkerboard, shown while the real texture
+    /// loads.
+    pub fn placeholder() -> Self {
+        const MAGENTA: [u8; 4] = [255, 0, 255, 255];
+        const BLACK: [u8; 4] = [0, 0, 0, 255];
+        Texture::from(TextureCreateInfo {
+            rgba_image: RgbaImage::from_fn(2, 2, |x, y| {
+                image::Rgba(if (x + y) % 2 == 0 { MAGENTA } else { BLACK })
+            }),
+            internal_format: gl::BaseInternalFormat::RGBA,
+            mip_level: 0,
+            wrap_s: gl::TextureWrapMode::Repeat,
+            wrap_t: gl::TextureWrapMode::Repeat,
+            min_filter: gl::InterpolationMode::Nearest,
+            mag_filter: gl::InterpolationMode::Nearest,
+            mipmap_interpolation: None,
+        })
+    }
+
     pub fn bind_to_unit(&self, unit: u32) {
         self.texture.bind_unit(unit);
     }
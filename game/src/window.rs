@@ -1,16 +1,39 @@
+use sdl2::video::SwapInterval;
 use std::ffi::c_void;
 
 pub struct KWindow {
+    title: String,
     sdl_context: sdl2::Sdl,
     _sdl_video: sdl2::VideoSubsystem,
     sdl_window: sdl2::video::Window,
     _gl_context: sdl2::video::GLContext,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowMode {
+    Windowed,
+    /// Fullscreen at the desktop resolution, without a mode switch.
+    Borderless,
+    /// Switches the display to `width` x `height`.
+    Fullscreen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VSync {
+    Off,
+    On,
+    /// Syncs to the display unless a frame is late, in which case it is shown
+    /// immediately and may tear. Not every driver supports it.
+    Adaptive,
+}
+
 pub struct KWindowCreateInfo<'s> {
     pub title: &'s str,
     pub width: u32,
     pub height: u32,
+    pub mode: WindowMode,
+    /// Only affects [`WindowMode::Windowed`].
+    pub resizable: bool,
 }
 
 impl KWindow {
@@ -24,23 +47,34 @@ impl KWindow {
         gl_attr.set_context_profile(sdl2::video::GLProfile::Core);
         gl_attr.set_context_version(4, 6);
 
-        let sdl_window = _sdl_video
-            .window(create_info.title, create_info.width, create_info.height)
-            .position(-1800, 100)
-            .fullscreen_desktop()
-            .opengl()
-            .build()
-            .expect("Failed to create SDL2 window");
+        let mut builder =
+            _sdl_video.window(create_info.title, create_info.width, create_info.height);
+        builder.position_centered().opengl();
+        match create_info.mode {
+            WindowMode::Windowed => {
+                if create_info.resizable {
+                    builder.resizable();
+                }
+            }
+            WindowMode::Borderless => {
+                builder.fullscreen_desktop();
+            }
+            WindowMode::Fullscreen => {
+                builder.fullscreen();
+            }
+        }
+        let sdl_window = builder.build().expect("Failed to create SDL2 window");
 
         let _gl_context = sdl_window
             .gl_create_context()
             .expect("Failed to create OpenGL context");
 
-        _sdl_video
-            .gl_set_swap_interval(0)
-            .expect("Failed to disable vsync");
+        // SDL starts with text input enabled on desktop platforms, which can
+        // pop up an IME while the keyboard is used for movement.
+        _sdl_video.text_input().stop();
 
         KWindow {
+            title: create_info.title.to_string(),
             sdl_context,
             _sdl_video,
             sdl_window,
@@ -58,6 +92,70 @@ impl KWindow {
             .expect("Failed to get SDL2 event pump")
     }
 
+    /// Size of the default framebuffer in pixels, which can differ from the
+    /// window size on high-DPI displays.
+    pub fn drawable_size(&self) -> (u32, u32) {
+        self.sdl_window.drawable_size()
+    }
+
+    /// Shows `status` after the title, or only the title when it is empty.
+    pub fn set_status(&mut self, status: &str) {
+        let title = if status.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{} | {status}", self.title)
+        };
+        // The title came from a `&str` and status is formatted by the engine,
+        // so neither contains a NUL.
+        let _ = self.sdl_window.set_title(&title);
+    }
+
+    /// Sets the swap interval, falling back from adaptive to regular vsync and
+    /// from vsync to none when the driver refuses. Returns the mode in effect.
+    pub fn set_vsync(&self, vsync: VSync) -> VSync {
+        let interval = match vsync {
+            VSync::Off => SwapInterval::Immediate,
+            VSync::On => SwapInterval::VSync,
+            VSync::Adaptive => SwapInterval::LateSwapTearing,
+        };
+        match self._sdl_video.gl_set_swap_interval(interval) {
+            Ok(()) => vsync,
+            Err(e) => {
+                let fallback = match vsync {
+                    VSync::Adaptive => VSync::On,
+                    VSync::On | VSync::Off => VSync::Off,
+                };
+                eprintln!("Failed to set vsync to {vsync:?}: {e}");
+                if fallback == vsync {
+                    // Nothing left to fall back to; keep whatever the driver has.
+                    return match self._sdl_video.gl_get_swap_interval() {
+                        SwapInterval::Immediate => VSync::Off,
+                        SwapInterval::VSync => VSync::On,
+                        SwapInterval::LateSwapTearing => VSync::Adaptive,
+                    };
+                }
+                self.set_vsync(fallback)
+            }
+        }
+    }
+
+    /// Turns on `TextInput` and `TextEditing` events, and the IME on platforms
+    /// that have one.
+    pub fn set_text_input(&self, enabled: bool) {
+        let text_input = self._sdl_video.text_input();
+        if enabled {
+            text_input.start();
+        } else {
+            text_input.stop();
+        }
+    }
+
+    pub fn game_controller(&self) -> sdl2::GameControllerSubsystem {
+        self.sdl_context
+            .game_controller()
+            .expect("Failed to get SDL2 game controller subsystem")
+    }
+
     pub fn swap_window(&self) {
         self.sdl_window.gl_swap_window();
     }
@@ -66,3 +164,10 @@ impl KWindow {
         self.sdl_context.mouse().set_relative_mouse_mode(enabled);
     }
 }
+
+impl Drop for KWindow {
+    fn drop(&mut self) {
+        // Runs before the fields drop, while the context is still current.
+        gl::context_destroyed();
+    }
+}
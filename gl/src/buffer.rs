@@ -1,4 +1,4 @@
-use super::gl;
+use super::{gl, tracking, ObjectKind};
 pub struct Buffer(u32);
 
 #[repr(u32)]
@@ -35,13 +35,18 @@ impl From<u32> for BufferTarget {
 }
 
 impl Buffer {
+    fn tracked(id: u32) -> Self {
+        tracking::created(ObjectKind::Buffer, id);
+        Buffer(id)
+    }
+
     #[inline]
     pub fn r#gen(n: isize) -> Vec<Self> {
         let mut buffers = vec![0; n as usize];
         unsafe {
             gl().GenBuffers(n as _, buffers.as_mut_ptr());
         }
-        buffers.into_iter().map(Buffer).collect()
+        buffers.into_iter().map(Buffer::tracked).collect()
     }
 
     #[inline]
@@ -50,7 +55,7 @@ impl Buffer {
         unsafe {
             gl().GenBuffers(1, &mut buffer);
         }
-        Buffer(buffer)
+        Buffer::tracked(buffer)
     }
 
     #[inline]
@@ -59,7 +64,7 @@ impl Buffer {
         unsafe {
             gl().CreateBuffers(n as _, buffers.as_mut_ptr());
         }
-        buffers.into_iter().map(Buffer).collect()
+        buffers.into_iter().map(Buffer::tracked).collect()
     }
 
     #[inline]
@@ -68,7 +73,7 @@ impl Buffer {
         unsafe {
             gl().CreateBuffers(1, &mut buffer);
         }
-        Buffer(buffer)
+        Buffer::tracked(buffer)
     }
 
     #[inline]
@@ -76,6 +81,10 @@ impl Buffer {
         self.0
     }
 
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::Buffer, self.id(), label);
+    }
+
     #[inline]
     pub fn bind(&self, target: BufferTarget) {
         unsafe {
@@ -103,9 +112,7 @@ impl Buffer {
 
 impl Drop for Buffer {
     fn drop(&mut self) {
-        unsafe {
-            gl().DeleteBuffers(1, &self.0);
-        }
+        tracking::dropped(ObjectKind::Buffer, self.0);
     }
 }
 
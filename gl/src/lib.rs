@@ -6,21 +6,39 @@ use std::{
 
 mod buffer;
 mod debug;
+mod limits;
+#[cfg(feature = "mock")]
+pub mod mock;
+mod program_interface;
+mod program_pipeline;
 mod shader;
 mod shader_program;
 mod texture;
+mod tracking;
 mod vertex_array;
+mod vertex_layout;
 
 pub use buffer::*;
 pub use debug::*;
+pub use limits::*;
+pub use program_interface::*;
+pub use program_pipeline::*;
 pub use shader::*;
 pub use shader_program::*;
 pub use texture::*;
+pub use tracking::{
+    context_destroyed, flush_deferred_deletes, live_object_counts, live_objects, LiveObject,
+    ObjectKind,
+};
 pub use vertex_array::*;
+pub use vertex_layout::*;
 
 static mut GL: Option<gl46::GlFns> = None;
 
 /// Loads the OpenGL function pointers into a global static variable.
+///
+/// The calling thread is treated as the one the context is current on: objects
+/// dropped on other threads are queued for [`flush_deferred_deletes`].
 pub fn load_fns(loader: impl Fn(&str) -> *const c_void) -> Result<(), &'static str> {
     unsafe {
         let load_fns = |ptr: *const u8| -> *const c_void {
@@ -30,6 +48,8 @@ pub fn load_fns(loader: impl Fn(&str) -> *const c_void) -> Result<(), &'static s
         };
         let gl = gl46::GlFns::load_from(&load_fns)?;
         GL = Some(gl);
+        limits::forget();
+        tracking::context_created(Some(std::thread::current().id()));
         Ok(())
     }
 }
@@ -47,6 +67,33 @@ pub fn gl() -> &'static gl46::GlFns {
     }
 }
 
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringName {
+    Vendor = gl46::GL_VENDOR.0,
+    Renderer = gl46::GL_RENDERER.0,
+    Version = gl46::GL_VERSION.0,
+    ShadingLanguageVersion = gl46::GL_SHADING_LANGUAGE_VERSION.0,
+}
+
+pub fn get_string(name: StringName) -> String {
+    unsafe {
+        let ptr = gl().GetString(gl46::GLenum(name as u32));
+        if ptr.is_null() {
+            return String::new();
+        }
+        CStr::from_ptr(ptr.cast()).to_string_lossy().into_owned()
+    }
+}
+
+pub fn get_integer(pname: u32) -> i32 {
+    let mut value = 0;
+    unsafe {
+        gl().GetIntegerv(gl46::GLenum(pname), &mut value);
+    }
+    value
+}
+
 pub unsafe fn viewport(x: i32, y: i32, width: i32, height: i32) {
     unsafe { gl().Viewport(x, y, width, height) }
 }
@@ -121,12 +168,64 @@ pub enum DrawMode {
     Points = gl46::GL_POINTS.0,
     Lines = gl46::GL_LINES.0,
     LineStrip = gl46::GL_LINE_STRIP.0,
+    LineLoop = gl46::GL_LINE_LOOP.0,
     Triangles = gl46::GL_TRIANGLES.0,
     TriangleStrip = gl46::GL_TRIANGLE_STRIP.0,
     TriangleFan = gl46::GL_TRIANGLE_FAN.0,
+    /// Only usable with a geometry shader.
+    LinesAdjacency = gl46::GL_LINES_ADJACENCY.0,
+    LineStripAdjacency = gl46::GL_LINE_STRIP_ADJACENCY.0,
+    TrianglesAdjacency = gl46::GL_TRIANGLES_ADJACENCY.0,
+    TriangleStripAdjacency = gl46::GL_TRIANGLE_STRIP_ADJACENCY.0,
+    /// Required when the program has tessellation stages. The patch size is
+    /// set with [`patch_vertices`].
+    Patches = gl46::GL_PATCHES.0,
+}
+
+/// Sets how many vertices make up a patch for [`DrawMode::Patches`].
+pub fn patch_vertices(count: i32) {
+    let max = max_patch_vertices();
+    if count < 1 || count > max {
+        panic!("patch vertex count {count} must be between 1 and GL_MAX_PATCH_VERTICES ({max})");
+    }
+    unsafe {
+        gl().PatchParameteri(gl46::GL_PATCH_VERTICES, count);
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexType {
+    UnsignedByte = gl46::GL_UNSIGNED_BYTE.0,
+    UnsignedShort = gl46::GL_UNSIGNED_SHORT.0,
+    UnsignedInt = gl46::GL_UNSIGNED_INT.0,
+}
+
+impl IndexType {
+    pub fn size(self) -> usize {
+        match self {
+            IndexType::UnsignedByte => 1,
+            IndexType::UnsignedShort => 2,
+            IndexType::UnsignedInt => 4,
+        }
+    }
+}
+
+impl From<IndexType> for u32 {
+    fn from(value: IndexType) -> Self {
+        value as u32
+    }
+}
+
+/// Draws `count` vertices starting at `first`, without an element buffer.
+pub fn draw_arrays(mode: DrawMode, first: i32, count: i32) {
+    unsafe {
+        gl().DrawArrays(gl46::GLenum(mode as u32), first, count);
+    }
 }
 
-pub fn draw_elements(mode: DrawMode, count: i32, r#type: VertexAttribPointerType, offset: usize) {
+/// `offset` is in bytes into the bound element buffer.
+pub fn draw_elements(mode: DrawMode, count: i32, r#type: IndexType, offset: usize) {
     unsafe {
         gl().DrawElements(
             gl46::GLenum(mode as u32),
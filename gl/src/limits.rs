@@ -0,0 +1,68 @@
+//! Implementation limits of the current context. Each is queried the first
+//! time it is needed and kept until the functions are loaded again.
+
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    max_vertex_attribs: Option<i32>,
+    max_patch_vertices: Option<i32>,
+}
+
+const UNKNOWN: Limits = Limits {
+    max_vertex_attribs: None,
+    max_patch_vertices: None,
+};
+
+#[cfg(not(feature = "mock"))]
+static LIMITS: std::sync::Mutex<Limits> = std::sync::Mutex::new(UNKNOWN);
+
+#[cfg(not(feature = "mock"))]
+fn with_limits<R>(f: impl FnOnce(&mut Limits) -> R) -> R {
+    f(&mut LIMITS.lock().unwrap())
+}
+
+// The mock keeps a context per thread, so its limits are per thread too.
+#[cfg(feature = "mock")]
+thread_local! {
+    static LIMITS: std::cell::Cell<Limits> = const { std::cell::Cell::new(UNKNOWN) };
+}
+
+#[cfg(feature = "mock")]
+fn with_limits<R>(f: impl FnOnce(&mut Limits) -> R) -> R {
+    LIMITS.with(|limits| {
+        let mut current = limits.get();
+        let result = f(&mut current);
+        limits.set(current);
+        result
+    })
+}
+
+/// Called when a new context's functions are loaded.
+pub(crate) fn forget() {
+    with_limits(|limits| *limits = UNKNOWN);
+}
+
+/// Returns the limit in `field`, querying `pname` the first time.
+fn cached(field: fn(&mut Limits) -> &mut Option<i32>, pname: gl46::GLenum) -> i32 {
+    if let Some(value) = with_limits(|limits| *field(limits)) {
+        return value;
+    }
+    let value = crate::get_integer(pname.0);
+    with_limits(|limits| *field(limits) = Some(value));
+    value
+}
+
+/// `GL_MAX_VERTEX_ATTRIBS` of the current context.
+pub fn max_vertex_attribs() -> u32 {
+    cached(
+        |limits| &mut limits.max_vertex_attribs,
+        gl46::GL_MAX_VERTEX_ATTRIBS,
+    ) as u32
+}
+
+/// `GL_MAX_PATCH_VERTICES` of the current context.
+pub fn max_patch_vertices() -> i32 {
+    cached(
+        |limits| &mut limits.max_patch_vertices,
+        gl46::GL_MAX_PATCH_VERTICES,
+    )
+}
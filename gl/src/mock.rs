@@ -0,0 +1,1815 @@
+//! Recording OpenGL backend for tests that run without a driver.
+//!
+//! [`install`] loads a function table whose entries record every call and
+//! simulate the bits of GL state the wrappers in this crate rely on: object
+//! name allocation, the bound vertex array, program and texture units, buffer
+//! contents and shader/program status. State is kept per thread, so parallel
+//! tests don't observe each other's calls.
+//!
+//! Entry points the mock doesn't simulate resolve to stubs with the matching
+//! signature that record the call and return zero.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    ffi::{c_void, CStr},
+    sync::Once,
+};
+
+use gl46::{GLbitfield, GLenum, GLDEBUGPROC};
+
+mod unsupported;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Int(i64),
+    Float(f32),
+    Str(String),
+}
+
+macro_rules! impl_int_arg {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for Arg {
+            fn from(value: $ty) -> Self {
+                Arg::Int(value as i64)
+            }
+        })*
+    };
+}
+
+impl_int_arg!(u8, i32, u32, isize, usize);
+
+impl From<GLenum> for Arg {
+    fn from(value: GLenum) -> Self {
+        Arg::Int(value.0 as i64)
+    }
+}
+
+impl From<GLbitfield> for Arg {
+    fn from(value: GLbitfield) -> Self {
+        Arg::Int(value.0 as i64)
+    }
+}
+
+impl From<f32> for Arg {
+    fn from(value: f32) -> Self {
+        Arg::Float(value)
+    }
+}
+
+impl From<String> for Arg {
+    fn from(value: String) -> Self {
+        Arg::Str(value)
+    }
+}
+
+/// A single recorded GL call. `name` is the entry point without the `gl` prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub name: &'static str,
+    pub args: Vec<Arg>,
+}
+
+impl Call {
+    pub fn is(&self, name: &str) -> bool {
+        self.name == name
+    }
+
+    /// Returns the integer argument at `index`, panicking if it isn't one.
+    pub fn int(&self, index: usize) -> i64 {
+        match self.args.get(index) {
+            Some(Arg::Int(value)) => *value,
+            other => panic!(
+                "{}: argument {index} is not an integer: {other:?}",
+                self.name
+            ),
+        }
+    }
+}
+
+/// A `DrawElements` call together with the state it was issued with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Draw {
+    pub mode: u32,
+    pub count: i32,
+    pub index_type: u32,
+    pub offset: usize,
+    pub vertex_array: u32,
+    pub program: u32,
+    pub pipeline: u32,
+    pub texture_units: HashMap<u32, u32>,
+}
+
+/// Which `*AttribFormat`/`*AttribPointer` variant last described an attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttribKind {
+    #[default]
+    Float,
+    Integer,
+    Double,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Attrib {
+    pub enabled: bool,
+    pub kind: AttribKind,
+    pub size: i32,
+    pub r#type: u32,
+    pub normalized: bool,
+    pub relative_offset: u32,
+    pub binding: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexBufferBinding {
+    pub buffer: u32,
+    pub offset: isize,
+    pub stride: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VertexArray {
+    pub element_buffer: u32,
+    pub bindings: HashMap<u32, VertexBufferBinding>,
+    pub attribs: HashMap<u32, Attrib>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Texture {
+    pub target: u32,
+    pub parameters: HashMap<u32, i32>,
+    pub levels: i32,
+    pub internal_format: u32,
+    pub width: i32,
+    pub height: i32,
+    pub mipmaps_generated: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Shader {
+    pub shader_type: u32,
+    pub source: String,
+    pub binary: Vec<u8>,
+    pub entry_point: Option<String>,
+    pub specialization_constants: Vec<(u32, u32)>,
+    pub compiled: bool,
+}
+
+/// An active resource reported through the program interface queries.
+#[derive(Debug, Clone, Default)]
+pub struct Resource {
+    pub name: String,
+    /// Values keyed by `GL_TYPE`, `GL_LOCATION`, ...; missing ones read as 0.
+    pub properties: HashMap<u32, i32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Program {
+    pub attached_shaders: Vec<u32>,
+    pub separable: bool,
+    pub binary_retrievable: bool,
+    /// Returned by `glGetProgramBinary` once linked; `glProgramBinary` stores
+    /// what it was given here.
+    pub binary: Option<(u32, Vec<u8>)>,
+    pub linked: bool,
+    pub uniform_locations: HashMap<String, i32>,
+    /// Active resources per `GL_PROGRAM_INPUT`-style interface, set up by tests.
+    pub resources: HashMap<u32, Vec<Resource>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Pipeline {
+    /// Program used by each stage, keyed by its `GL_*_SHADER_BIT`.
+    pub stages: HashMap<u32, u32>,
+    pub active_program: u32,
+    pub validated: bool,
+}
+
+/// Simulated context state. Tests can inspect it, or tweak it before calling
+/// into the wrappers, through [`with_state`].
+#[derive(Debug)]
+pub struct MockState {
+    next_name: u32,
+    calls: Vec<Call>,
+    pub draws: Vec<Draw>,
+    pub capabilities: HashSet<u32>,
+    pub integers: HashMap<u32, i32>,
+    pub viewport: (i32, i32, i32, i32),
+    pub clear_color: [f32; 4],
+    pub bound_buffers: HashMap<u32, u32>,
+    pub bound_vertex_array: u32,
+    pub current_program: u32,
+    pub active_texture: u32,
+    pub texture_units: HashMap<u32, u32>,
+    pub buffers: HashMap<u32, Vec<u8>>,
+    pub vertex_arrays: HashMap<u32, VertexArray>,
+    pub textures: HashMap<u32, Texture>,
+    pub shaders: HashMap<u32, Shader>,
+    pub programs: HashMap<u32, Program>,
+    pub pipelines: HashMap<u32, Pipeline>,
+    pub bound_pipeline: u32,
+    pub uniforms: HashMap<(u32, i32), Vec<f32>>,
+    pub labels: HashMap<(u32, u32), String>,
+    /// Result reported through `GL_COMPILE_STATUS` and `GL_SPIR_V_BINARY`.
+    pub compile_succeeds: bool,
+    /// Result reported through `GL_LINK_STATUS`.
+    pub link_succeeds: bool,
+    /// Result reported through a pipeline's `GL_VALIDATE_STATUS`.
+    pub validate_succeeds: bool,
+    /// Whether `glProgramBinary` accepts the binaries it is given.
+    pub program_binary_succeeds: bool,
+    pub info_log: String,
+}
+
+impl Default for MockState {
+    fn default() -> Self {
+        Self {
+            next_name: 1,
+            calls: Vec::new(),
+            draws: Vec::new(),
+            capabilities: HashSet::new(),
+            integers: HashMap::from([
+                (gl46::GL_MAX_VERTEX_ATTRIBS.0, 16),
+                (gl46::GL_MAX_PATCH_VERTICES.0, 32),
+                (gl46::GL_PATCH_VERTICES.0, 3),
+            ]),
+            viewport: (0, 0, 0, 0),
+            clear_color: [0.0; 4],
+            bound_buffers: HashMap::new(),
+            bound_vertex_array: 0,
+            current_program: 0,
+            active_texture: 0,
+            texture_units: HashMap::new(),
+            buffers: HashMap::new(),
+            vertex_arrays: HashMap::new(),
+            textures: HashMap::new(),
+            shaders: HashMap::new(),
+            programs: HashMap::new(),
+            pipelines: HashMap::new(),
+            bound_pipeline: 0,
+            uniforms: HashMap::new(),
+            labels: HashMap::new(),
+            compile_succeeds: true,
+            link_succeeds: true,
+            validate_succeeds: true,
+            program_binary_succeeds: true,
+            info_log: String::new(),
+        }
+    }
+}
+
+impl MockState {
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    fn record(&mut self, name: &'static str, args: Vec<Arg>) {
+        self.calls.push(Call { name, args });
+    }
+
+    fn alloc(&mut self) -> u32 {
+        let name = self.next_name;
+        self.next_name += 1;
+        name
+    }
+
+    fn bound_texture(&self) -> u32 {
+        self.texture_units
+            .get(&self.active_texture)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<MockState> = RefCell::new(MockState::default());
+}
+
+/// Loads the mock function table and resets this thread's state.
+///
+/// The mock context counts as current on every thread, so objects are deleted
+/// wherever they are dropped.
+pub fn install() {
+    static LOAD: Once = Once::new();
+    LOAD.call_once(|| {
+        super::load_fns(lookup).expect("Failed to load mock OpenGL functions.");
+    });
+    super::limits::forget();
+    super::tracking::context_created(None);
+    reset();
+}
+
+/// Drops every recorded call and simulated object on this thread.
+pub fn reset() {
+    STATE.with(|state| *state.borrow_mut() = MockState::default());
+    super::tracking::reset_registry();
+}
+
+pub fn with_state<R>(f: impl FnOnce(&mut MockState) -> R) -> R {
+    STATE.with(|state| f(&mut state.borrow_mut()))
+}
+
+pub fn calls() -> Vec<Call> {
+    with_state(|state| state.calls.clone())
+}
+
+/// Returns the calls recorded since the last `take_calls` or `reset`.
+pub fn take_calls() -> Vec<Call> {
+    with_state(|state| std::mem::take(&mut state.calls))
+}
+
+pub fn calls_named(name: &str) -> Vec<Call> {
+    with_state(|state| state.calls.iter().filter(|c| c.is(name)).cloned().collect())
+}
+
+macro_rules! args {
+    ($($arg:expr),* $(,)?) => {
+        vec![$(Arg::from($arg)),*]
+    };
+}
+
+macro_rules! mock_fns {
+    ($($name:literal => $f:ident),* $(,)?) => {
+        fn lookup(name: &str) -> *const c_void {
+            match name {
+                $($name => $f as *const c_void,)*
+                _ => unsupported::lookup(name)
+                    .unwrap_or_else(|| panic!("{name} has no mock stub")),
+            }
+        }
+    };
+}
+
+mock_fns! {
+    "glViewport" => viewport,
+    "glEnable" => enable,
+    "glClear" => clear,
+    "glClearColor" => clear_color,
+    "glBlendFunc" => blend_func,
+    "glDrawElements" => draw_elements,
+    "glPatchParameteri" => patch_parameter_i,
+    "glGetError" => get_error,
+    "glGetIntegerv" => get_integer_v,
+    "glGetString" => get_string,
+    "glDebugMessageCallback" => debug_message_callback,
+    "glObjectLabel" => object_label,
+    "glGenBuffers" => gen_buffers,
+    "glCreateBuffers" => create_buffers,
+    "glBindBuffer" => bind_buffer,
+    "glBufferData" => buffer_data,
+    "glNamedBufferStorage" => named_buffer_storage,
+    "glDeleteBuffers" => delete_buffers,
+    "glGenVertexArrays" => gen_vertex_arrays,
+    "glCreateVertexArrays" => create_vertex_arrays,
+    "glBindVertexArray" => bind_vertex_array,
+    "glVertexArrayVertexBuffer" => vertex_array_vertex_buffer,
+    "glVertexArrayElementBuffer" => vertex_array_element_buffer,
+    "glVertexArrayAttribBinding" => vertex_array_attrib_binding,
+    "glVertexArrayAttribFormat" => vertex_array_attrib_format,
+    "glVertexArrayAttribIFormat" => vertex_array_attrib_i_format,
+    "glVertexArrayAttribLFormat" => vertex_array_attrib_l_format,
+    "glEnableVertexArrayAttrib" => enable_vertex_array_attrib,
+    "glDisableVertexArrayAttrib" => disable_vertex_array_attrib,
+    "glEnableVertexAttribArray" => enable_vertex_attrib_array,
+    "glDisableVertexAttribArray" => disable_vertex_attrib_array,
+    "glVertexAttribPointer" => vertex_attrib_pointer,
+    "glVertexAttribIPointer" => vertex_attrib_i_pointer,
+    "glVertexAttribLPointer" => vertex_attrib_l_pointer,
+    "glDeleteVertexArrays" => delete_vertex_arrays,
+    "glGenTextures" => gen_textures,
+    "glCreateTextures" => create_textures,
+    "glBindTexture" => bind_texture,
+    "glBindTextureUnit" => bind_texture_unit,
+    "glActiveTexture" => active_texture,
+    "glTexParameteri" => tex_parameter_i,
+    "glTextureParameteri" => texture_parameter_i,
+    "glTexImage2D" => tex_image_2d,
+    "glTextureStorage2D" => texture_storage_2d,
+    "glTextureSubImage2D" => texture_sub_image_2d,
+    "glGenerateMipmap" => generate_mipmap,
+    "glGenerateTextureMipmap" => generate_texture_mipmap,
+    "glDeleteTextures" => delete_textures,
+    "glCreateShader" => create_shader,
+    "glShaderSource" => shader_source,
+    "glShaderBinary" => shader_binary,
+    "glCompileShader" => compile_shader,
+    "glSpecializeShader" => specialize_shader,
+    "glGetShaderiv" => get_shader_iv,
+    "glGetShaderInfoLog" => get_info_log,
+    "glDeleteShader" => delete_shader,
+    "glCreateProgram" => create_program,
+    "glAttachShader" => attach_shader,
+    "glDetachShader" => detach_shader,
+    "glProgramParameteri" => program_parameter_i,
+    "glGetProgramBinary" => get_program_binary,
+    "glProgramBinary" => program_binary,
+    "glLinkProgram" => link_program,
+    "glUseProgram" => use_program,
+    "glGetProgramiv" => get_program_iv,
+    "glGetProgramInfoLog" => get_info_log,
+    "glGetUniformLocation" => get_uniform_location,
+    "glGetProgramInterfaceiv" => get_program_interface_iv,
+    "glGetProgramResourceiv" => get_program_resource_iv,
+    "glGetProgramResourceName" => get_program_resource_name,
+    "glUniformMatrix4fv" => uniform_matrix_4fv,
+    "glProgramUniformMatrix4fv" => program_uniform_matrix_4fv,
+    "glDeleteProgram" => delete_program,
+    "glCreateProgramPipelines" => create_program_pipelines,
+    "glUseProgramStages" => use_program_stages,
+    "glActiveShaderProgram" => active_shader_program,
+    "glBindProgramPipeline" => bind_program_pipeline,
+    "glValidateProgramPipeline" => validate_program_pipeline,
+    "glGetProgramPipelineiv" => get_program_pipeline_iv,
+    "glGetProgramPipelineInfoLog" => get_info_log,
+    "glDeleteProgramPipelines" => delete_program_pipelines,
+}
+
+unsafe fn write_names(state: &mut MockState, n: i32, names: *mut u32) -> Vec<u32> {
+    let allocated: Vec<u32> = (0..n).map(|_| state.alloc()).collect();
+    unsafe { std::ptr::copy_nonoverlapping(allocated.as_ptr(), names, allocated.len()) };
+    allocated
+}
+
+unsafe fn read_names(n: i32, names: *const u32) -> Vec<u32> {
+    unsafe { std::slice::from_raw_parts(names, n as usize).to_vec() }
+}
+
+unsafe fn read_c_str(ptr: *const u8) -> String {
+    unsafe { CStr::from_ptr(ptr.cast()).to_string_lossy().into_owned() }
+}
+
+extern "system" fn viewport(x: i32, y: i32, width: i32, height: i32) {
+    with_state(|s| {
+        s.viewport = (x, y, width, height);
+        s.record("Viewport", args![x, y, width, height]);
+    });
+}
+
+extern "system" fn enable(cap: GLenum) {
+    with_state(|s| {
+        s.capabilities.insert(cap.0);
+        s.record("Enable", args![cap]);
+    });
+}
+
+extern "system" fn clear(mask: GLbitfield) {
+    with_state(|s| s.record("Clear", args![mask]));
+}
+
+extern "system" fn clear_color(r: f32, g: f32, b: f32, a: f32) {
+    with_state(|s| {
+        s.clear_color = [r, g, b, a];
+        s.record("ClearColor", args![r, g, b, a]);
+    });
+}
+
+extern "system" fn blend_func(sfactor: GLenum, dfactor: GLenum) {
+    with_state(|s| s.record("BlendFunc", args![sfactor, dfactor]));
+}
+
+extern "system" fn patch_parameter_i(pname: GLenum, value: i32) {
+    with_state(|s| {
+        s.integers.insert(pname.0, value);
+        s.record("PatchParameteri", args![pname, value]);
+    });
+}
+
+extern "system" fn draw_elements(mode: GLenum, count: i32, r#type: GLenum, indices: *const c_void) {
+    with_state(|s| {
+        let draw = Draw {
+            mode: mode.0,
+            count,
+            index_type: r#type.0,
+            offset: indices as usize,
+            vertex_array: s.bound_vertex_array,
+            program: s.current_program,
+            pipeline: s.bound_pipeline,
+            texture_units: s.texture_units.clone(),
+        };
+        s.draws.push(draw);
+        s.record("DrawElements", args![mode, count, r#type, indices as usize]);
+    });
+}
+
+extern "system" fn get_error() -> GLenum {
+    gl46::GL_NO_ERROR
+}
+
+unsafe extern "system" fn get_integer_v(pname: GLenum, data: *mut i32) {
+    with_state(|s| {
+        let value = s.integers.get(&pname.0).copied().unwrap_or(0);
+        unsafe { *data = value };
+        s.record("GetIntegerv", args![pname]);
+    });
+}
+
+extern "system" fn get_string(name: GLenum) -> *const u8 {
+    with_state(|s| s.record("GetString", args![name]));
+    let value: &'static [u8] = match name {
+        gl46::GL_VENDOR => b"gl::mock\0",
+        gl46::GL_RENDERER => b"gl::mock renderer\0",
+        gl46::GL_VERSION => b"4.6.0 gl::mock\0",
+        gl46::GL_SHADING_LANGUAGE_VERSION => b"4.60 gl::mock\0",
+        _ => b"\0",
+    };
+    value.as_ptr()
+}
+
+extern "system" fn debug_message_callback(callback: GLDEBUGPROC, _user_param: *const c_void) {
+    with_state(|s| s.record("DebugMessageCallback", args![callback.is_some() as u8]));
+}
+
+unsafe extern "system" fn object_label(
+    identifier: GLenum,
+    name: u32,
+    length: i32,
+    label: *const u8,
+) {
+    with_state(|s| {
+        let label = if length < 0 {
+            unsafe { read_c_str(label) }
+        } else {
+            let bytes = unsafe { std::slice::from_raw_parts(label, length as usize) };
+            String::from_utf8_lossy(bytes).into_owned()
+        };
+        s.labels.insert((identifier.0, name), label.clone());
+        s.record("ObjectLabel", args![identifier, name, label]);
+    });
+}
+
+/// The `glGen*` entry points only reserve names; objects appear on first bind.
+macro_rules! gen_fn {
+    ($f:ident, $name:literal) => {
+        unsafe extern "system" fn $f(n: i32, names: *mut u32) {
+            with_state(|s| {
+                unsafe { write_names(s, n, names) };
+                s.record($name, args![n]);
+            });
+        }
+    };
+}
+
+gen_fn!(gen_buffers, "GenBuffers");
+gen_fn!(gen_vertex_arrays, "GenVertexArrays");
+gen_fn!(gen_textures, "GenTextures");
+
+unsafe extern "system" fn create_buffers(n: i32, buffers: *mut u32) {
+    with_state(|s| {
+        for name in unsafe { write_names(s, n, buffers) } {
+            s.buffers.insert(name, Vec::new());
+        }
+        s.record("CreateBuffers", args![n]);
+    });
+}
+
+extern "system" fn bind_buffer(target: GLenum, buffer: u32) {
+    with_state(|s| {
+        s.bound_buffers.insert(target.0, buffer);
+        s.buffers.entry(buffer).or_default();
+        s.record("BindBuffer", args![target, buffer]);
+    });
+}
+
+unsafe extern "system" fn buffer_data(
+    target: GLenum,
+    size: isize,
+    data: *const c_void,
+    usage: GLenum,
+) {
+    with_state(|s| {
+        let buffer = s.bound_buffers.get(&target.0).copied().unwrap_or(0);
+        let contents = if data.is_null() {
+            vec![0; size as usize]
+        } else {
+            unsafe { std::slice::from_raw_parts(data.cast::<u8>(), size as usize).to_vec() }
+        };
+        s.buffers.insert(buffer, contents);
+        s.record("BufferData", args![target, size, usage]);
+    });
+}
+
+unsafe extern "system" fn named_buffer_storage(
+    buffer: u32,
+    size: isize,
+    data: *const c_void,
+    flags: GLbitfield,
+) {
+    with_state(|s| {
+        let contents = if data.is_null() {
+            vec![0; size as usize]
+        } else {
+            unsafe { std::slice::from_raw_parts(data.cast::<u8>(), size as usize).to_vec() }
+        };
+        s.buffers.insert(buffer, contents);
+        s.record("NamedBufferStorage", args![buffer, size, flags]);
+    });
+}
+
+unsafe extern "system" fn delete_buffers(n: i32, buffers: *const u32) {
+    with_state(|s| {
+        for name in unsafe { read_names(n, buffers) } {
+            s.buffers.remove(&name);
+            s.record("DeleteBuffers", args![name]);
+        }
+    });
+}
+
+unsafe extern "system" fn create_vertex_arrays(n: i32, arrays: *mut u32) {
+    with_state(|s| {
+        for name in unsafe { write_names(s, n, arrays) } {
+            s.vertex_arrays.insert(name, VertexArray::default());
+        }
+        s.record("CreateVertexArrays", args![n]);
+    });
+}
+
+extern "system" fn bind_vertex_array(array: u32) {
+    with_state(|s| {
+        s.bound_vertex_array = array;
+        s.vertex_arrays.entry(array).or_default();
+        s.record("BindVertexArray", args![array]);
+    });
+}
+
+extern "system" fn vertex_array_vertex_buffer(
+    vaobj: u32,
+    binding: u32,
+    buffer: u32,
+    offset: isize,
+    stride: i32,
+) {
+    with_state(|s| {
+        let binding_state = VertexBufferBinding {
+            buffer,
+            offset,
+            stride,
+        };
+        s.vertex_arrays
+            .entry(vaobj)
+            .or_default()
+            .bindings
+            .insert(binding, binding_state);
+        s.record(
+            "VertexArrayVertexBuffer",
+            args![vaobj, binding, buffer, offset, stride],
+        );
+    });
+}
+
+extern "system" fn vertex_array_element_buffer(vaobj: u32, buffer: u32) {
+    with_state(|s| {
+        s.vertex_arrays.entry(vaobj).or_default().element_buffer = buffer;
+        s.record("VertexArrayElementBuffer", args![vaobj, buffer]);
+    });
+}
+
+extern "system" fn vertex_array_attrib_binding(vaobj: u32, attrib: u32, binding: u32) {
+    with_state(|s| {
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        vao.attribs.entry(attrib).or_default().binding = binding;
+        s.record("VertexArrayAttribBinding", args![vaobj, attrib, binding]);
+    });
+}
+
+extern "system" fn vertex_array_attrib_format(
+    vaobj: u32,
+    attrib: u32,
+    size: i32,
+    r#type: GLenum,
+    normalized: u8,
+    relative_offset: u32,
+) {
+    with_state(|s| {
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        let entry = vao.attribs.entry(attrib).or_default();
+        entry.kind = AttribKind::Float;
+        entry.size = size;
+        entry.r#type = r#type.0;
+        entry.normalized = normalized != 0;
+        entry.relative_offset = relative_offset;
+        s.record(
+            "VertexArrayAttribFormat",
+            args![vaobj, attrib, size, r#type, normalized, relative_offset],
+        );
+    });
+}
+
+fn set_attrib_i_l_format(
+    name: &'static str,
+    kind: AttribKind,
+    vaobj: u32,
+    attrib: u32,
+    size: i32,
+    r#type: GLenum,
+    relative_offset: u32,
+) {
+    with_state(|s| {
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        let entry = vao.attribs.entry(attrib).or_default();
+        entry.kind = kind;
+        entry.size = size;
+        entry.r#type = r#type.0;
+        entry.normalized = false;
+        entry.relative_offset = relative_offset;
+        s.record(name, args![vaobj, attrib, size, r#type, relative_offset]);
+    });
+}
+
+extern "system" fn vertex_array_attrib_i_format(
+    vaobj: u32,
+    attrib: u32,
+    size: i32,
+    r#type: GLenum,
+    relative_offset: u32,
+) {
+    set_attrib_i_l_format(
+        "VertexArrayAttribIFormat",
+        AttribKind::Integer,
+        vaobj,
+        attrib,
+        size,
+        r#type,
+        relative_offset,
+    );
+}
+
+extern "system" fn vertex_array_attrib_l_format(
+    vaobj: u32,
+    attrib: u32,
+    size: i32,
+    r#type: GLenum,
+    relative_offset: u32,
+) {
+    set_attrib_i_l_format(
+        "VertexArrayAttribLFormat",
+        AttribKind::Double,
+        vaobj,
+        attrib,
+        size,
+        r#type,
+        relative_offset,
+    );
+}
+
+extern "system" fn enable_vertex_array_attrib(vaobj: u32, index: u32) {
+    with_state(|s| {
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        vao.attribs.entry(index).or_default().enabled = true;
+        s.record("EnableVertexArrayAttrib", args![vaobj, index]);
+    });
+}
+
+extern "system" fn disable_vertex_array_attrib(vaobj: u32, index: u32) {
+    with_state(|s| {
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        vao.attribs.entry(index).or_default().enabled = false;
+        s.record("DisableVertexArrayAttrib", args![vaobj, index]);
+    });
+}
+
+extern "system" fn enable_vertex_attrib_array(index: u32) {
+    with_state(|s| {
+        let vaobj = s.bound_vertex_array;
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        vao.attribs.entry(index).or_default().enabled = true;
+        s.record("EnableVertexAttribArray", args![index]);
+    });
+}
+
+extern "system" fn disable_vertex_attrib_array(index: u32) {
+    with_state(|s| {
+        let vaobj = s.bound_vertex_array;
+        let vao = s.vertex_arrays.entry(vaobj).or_default();
+        vao.attribs.entry(index).or_default().enabled = false;
+        s.record("DisableVertexAttribArray", args![index]);
+    });
+}
+
+/// The legacy pointer calls describe the attribute and capture the buffer
+/// bound to `GL_ARRAY_BUFFER` in a binding of the same index.
+fn set_attrib_pointer(s: &mut MockState, index: u32, attrib: Attrib, stride: i32, pointer: usize) {
+    let vaobj = s.bound_vertex_array;
+    let buffer = s
+        .bound_buffers
+        .get(&gl46::GL_ARRAY_BUFFER.0)
+        .copied()
+        .unwrap_or(0);
+    let vao = s.vertex_arrays.entry(vaobj).or_default();
+    let enabled = vao.attribs.get(&index).is_some_and(|attrib| attrib.enabled);
+    vao.attribs.insert(index, Attrib { enabled, ..attrib });
+    vao.bindings.insert(
+        index,
+        VertexBufferBinding {
+            buffer,
+            offset: pointer as isize,
+            stride,
+        },
+    );
+}
+
+extern "system" fn vertex_attrib_pointer(
+    index: u32,
+    size: i32,
+    r#type: GLenum,
+    normalized: u8,
+    stride: i32,
+    pointer: *const c_void,
+) {
+    with_state(|s| {
+        let attrib = Attrib {
+            kind: AttribKind::Float,
+            size,
+            r#type: r#type.0,
+            normalized: normalized != 0,
+            binding: index,
+            ..Default::default()
+        };
+        set_attrib_pointer(s, index, attrib, stride, pointer as usize);
+        s.record(
+            "VertexAttribPointer",
+            args![index, size, r#type, normalized, stride, pointer as usize],
+        );
+    });
+}
+
+extern "system" fn vertex_attrib_i_pointer(
+    index: u32,
+    size: i32,
+    r#type: GLenum,
+    stride: i32,
+    pointer: *const c_void,
+) {
+    with_state(|s| {
+        let attrib = Attrib {
+            kind: AttribKind::Integer,
+            size,
+            r#type: r#type.0,
+            binding: index,
+            ..Default::default()
+        };
+        set_attrib_pointer(s, index, attrib, stride, pointer as usize);
+        s.record(
+            "VertexAttribIPointer",
+            args![index, size, r#type, stride, pointer as usize],
+        );
+    });
+}
+
+extern "system" fn vertex_attrib_l_pointer(
+    index: u32,
+    size: i32,
+    r#type: GLenum,
+    stride: i32,
+    pointer: *const c_void,
+) {
+    with_state(|s| {
+        let attrib = Attrib {
+            kind: AttribKind::Double,
+            size,
+            r#type: r#type.0,
+            binding: index,
+            ..Default::default()
+        };
+        set_attrib_pointer(s, index, attrib, stride, pointer as usize);
+        s.record(
+            "VertexAttribLPointer",
+            args![index, size, r#type, stride, pointer as usize],
+        );
+    });
+}
+
+unsafe extern "system" fn delete_vertex_arrays(n: i32, arrays: *const u32) {
+    with_state(|s| {
+        for name in unsafe { read_names(n, arrays) } {
+            s.vertex_arrays.remove(&name);
+            if s.bound_vertex_array == name {
+                s.bound_vertex_array = 0;
+            }
+            s.record("DeleteVertexArrays", args![name]);
+        }
+    });
+}
+
+unsafe extern "system" fn create_textures(target: GLenum, n: i32, textures: *mut u32) {
+    with_state(|s| {
+        for name in unsafe { write_names(s, n, textures) } {
+            let texture = Texture {
+                target: target.0,
+                ..Default::default()
+            };
+            s.textures.insert(name, texture);
+        }
+        s.record("CreateTextures", args![target, n]);
+    });
+}
+
+extern "system" fn bind_texture(target: GLenum, texture: u32) {
+    with_state(|s| {
+        s.texture_units.insert(s.active_texture, texture);
+        s.textures.entry(texture).or_default().target = target.0;
+        s.record("BindTexture", args![target, texture]);
+    });
+}
+
+extern "system" fn bind_texture_unit(unit: u32, texture: u32) {
+    with_state(|s| {
+        s.texture_units.insert(unit, texture);
+        s.record("BindTextureUnit", args![unit, texture]);
+    });
+}
+
+extern "system" fn active_texture(texture: GLenum) {
+    with_state(|s| {
+        s.active_texture = texture.0 - gl46::GL_TEXTURE0.0;
+        s.record("ActiveTexture", args![texture]);
+    });
+}
+
+extern "system" fn tex_parameter_i(target: GLenum, pname: GLenum, param: i32) {
+    with_state(|s| {
+        let texture = s.bound_texture();
+        let entry = s.textures.entry(texture).or_default();
+        entry.parameters.insert(pname.0, param);
+        s.record("TexParameteri", args![target, pname, param]);
+    });
+}
+
+extern "system" fn texture_parameter_i(texture: u32, pname: GLenum, param: i32) {
+    with_state(|s| {
+        let entry = s.textures.entry(texture).or_default();
+        entry.parameters.insert(pname.0, param);
+        s.record("TextureParameteri", args![texture, pname, param]);
+    });
+}
+
+extern "system" fn tex_image_2d(
+    target: GLenum,
+    level: i32,
+    internal_format: i32,
+    width: i32,
+    height: i32,
+    _border: i32,
+    format: GLenum,
+    r#type: GLenum,
+    _pixels: *const c_void,
+) {
+    with_state(|s| {
+        let texture = s.bound_texture();
+        let entry = s.textures.entry(texture).or_default();
+        entry.levels = entry.levels.max(level + 1);
+        entry.internal_format = internal_format as u32;
+        entry.width = width;
+        entry.height = height;
+        s.record(
+            "TexImage2D",
+            args![
+                target,
+                level,
+                internal_format,
+                width,
+                height,
+                format,
+                r#type
+            ],
+        );
+    });
+}
+
+extern "system" fn texture_storage_2d(
+    texture: u32,
+    levels: i32,
+    internal_format: GLenum,
+    width: i32,
+    height: i32,
+) {
+    with_state(|s| {
+        let entry = s.textures.entry(texture).or_default();
+        entry.levels = levels;
+        entry.internal_format = internal_format.0;
+        entry.width = width;
+        entry.height = height;
+        s.record(
+            "TextureStorage2D",
+            args![texture, levels, internal_format, width, height],
+        );
+    });
+}
+
+extern "system" fn texture_sub_image_2d(
+    texture: u32,
+    level: i32,
+    xoffset: i32,
+    yoffset: i32,
+    width: i32,
+    height: i32,
+    format: GLenum,
+    r#type: GLenum,
+    _pixels: *const c_void,
+) {
+    with_state(|s| {
+        s.record(
+            "TextureSubImage2D",
+            args![texture, level, xoffset, yoffset, width, height, format, r#type],
+        );
+    });
+}
+
+extern "system" fn generate_mipmap(target: GLenum) {
+    with_state(|s| {
+        let texture = s.bound_texture();
+        s.textures.entry(texture).or_default().mipmaps_generated = true;
+        s.record("GenerateMipmap", args![target]);
+    });
+}
+
+extern "system" fn generate_texture_mipmap(texture: u32) {
+    with_state(|s| {
+        s.textures.entry(texture).or_default().mipmaps_generated = true;
+        s.record("GenerateTextureMipmap", args![texture]);
+    });
+}
+
+unsafe extern "system" fn delete_textures(n: i32, textures: *const u32) {
+    with_state(|s| {
+        for name in unsafe { read_names(n, textures) } {
+            s.textures.remove(&name);
+            s.texture_units.retain(|_, texture| *texture != name);
+            s.record("DeleteTextures", args![name]);
+        }
+    });
+}
+
+extern "system" fn create_shader(shader_type: GLenum) -> u32 {
+    with_state(|s| {
+        let name = s.alloc();
+        let shader = Shader {
+            shader_type: shader_type.0,
+            ..Default::default()
+        };
+        s.shaders.insert(name, shader);
+        s.record("CreateShader", args![shader_type, name]);
+        name
+    })
+}
+
+unsafe extern "system" fn shader_source(
+    shader: u32,
+    count: i32,
+    strings: *const *const u8,
+    lengths: *const i32,
+) {
+    with_state(|s| {
+        let mut source = String::new();
+        for i in 0..count as usize {
+            unsafe {
+                let string = *strings.add(i);
+                if lengths.is_null() || *lengths.add(i) < 0 {
+                    source.push_str(&read_c_str(string));
+                } else {
+                    let bytes = std::slice::from_raw_parts(string, *lengths.add(i) as usize);
+                    source.push_str(&String::from_utf8_lossy(bytes));
+                }
+            }
+        }
+        s.shaders.entry(shader).or_default().source = source.clone();
+        s.record("ShaderSource", args![shader, count, source]);
+    });
+}
+
+unsafe extern "system" fn shader_binary(
+    count: i32,
+    shaders: *const u32,
+    binary_format: GLenum,
+    binary: *const c_void,
+    length: i32,
+) {
+    with_state(|s| {
+        let bytes = unsafe { std::slice::from_raw_parts(binary.cast::<u8>(), length as usize) };
+        for name in unsafe { read_names(count, shaders) } {
+            s.shaders.entry(name).or_default().binary = bytes.to_vec();
+            s.record("ShaderBinary", args![name, binary_format, length]);
+        }
+    });
+}
+
+extern "system" fn compile_shader(shader: u32) {
+    with_state(|s| {
+        let compiled = s.compile_succeeds;
+        s.shaders.entry(shader).or_default().compiled = compiled;
+        s.record("CompileShader", args![shader]);
+    });
+}
+
+unsafe extern "system" fn specialize_shader(
+    shader: u32,
+    entry_point: *const u8,
+    count: u32,
+    indices: *const u32,
+    values: *const u32,
+) {
+    with_state(|s| {
+        let entry_point = unsafe { read_c_str(entry_point) };
+        let constants = unsafe {
+            let indices = std::slice::from_raw_parts(indices, count as usize);
+            let values = std::slice::from_raw_parts(values, count as usize);
+            indices
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect()
+        };
+        let compiled = s.compile_succeeds;
+        let entry = s.shaders.entry(shader).or_default();
+        entry.entry_point = Some(entry_point.clone());
+        entry.specialization_constants = constants;
+        entry.compiled = compiled;
+        s.record("SpecializeShader", args![shader, entry_point, count]);
+    });
+}
+
+unsafe extern "system" fn get_shader_iv(shader: u32, pname: GLenum, params: *mut i32) {
+    with_state(|s| {
+        let entry = s.shaders.get(&shader);
+        let value = match pname {
+            gl46::GL_COMPILE_STATUS | gl46::GL_SPIR_V_BINARY => {
+                entry.is_some_and(|shader| shader.compiled) as i32
+            }
+            gl46::GL_SHADER_TYPE => entry.map_or(0, |shader| shader.shader_type as i32),
+            gl46::GL_INFO_LOG_LENGTH => s.info_log.len() as i32,
+            _ => 0,
+        };
+        unsafe { *params = value };
+        s.record("GetShaderiv", args![shader, pname]);
+    });
+}
+
+/// Shared by `glGetShaderInfoLog` and `glGetProgramInfoLog`.
+unsafe extern "system" fn get_info_log(
+    _object: u32,
+    buf_size: i32,
+    length: *mut i32,
+    log: *mut u8,
+) {
+    with_state(|s| {
+        let bytes = s.info_log.as_bytes();
+        let len = bytes.len().min(buf_size.max(0) as usize);
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), log, len);
+            if !length.is_null() {
+                *length = len as i32;
+            }
+        }
+    });
+}
+
+extern "system" fn delete_shader(shader: u32) {
+    with_state(|s| {
+        s.shaders.remove(&shader);
+        s.record("DeleteShader", args![shader]);
+    });
+}
+
+extern "system" fn create_program() -> u32 {
+    with_state(|s| {
+        let name = s.alloc();
+        s.programs.insert(name, Program::default());
+        s.record("CreateProgram", args![name]);
+        name
+    })
+}
+
+extern "system" fn attach_shader(program: u32, shader: u32) {
+    with_state(|s| {
+        let entry = s.programs.entry(program).or_default();
+        entry.attached_shaders.push(shader);
+        s.record("AttachShader", args![program, shader]);
+    });
+}
+
+extern "system" fn detach_shader(program: u32, shader: u32) {
+    with_state(|s| {
+        let entry = s.programs.entry(program).or_default();
+        entry
+            .attached_shaders
+            .retain(|attached| *attached != shader);
+        s.record("DetachShader", args![program, shader]);
+    });
+}
+
+extern "system" fn program_parameter_i(program: u32, pname: GLenum, value: i32) {
+    with_state(|s| {
+        let entry = s.programs.entry(program).or_default();
+        match pname {
+            gl46::GL_PROGRAM_SEPARABLE => entry.separable = value != 0,
+            gl46::GL_PROGRAM_BINARY_RETRIEVABLE_HINT => entry.binary_retrievable = value != 0,
+            _ => {}
+        }
+        s.record("ProgramParameteri", args![program, pname, value]);
+    });
+}
+
+/// Binaries handed out by the mock use this format and contain the program
+/// name, so tests can tell them apart.
+pub const BINARY_FORMAT: u32 = 0x4D4F_434B;
+
+unsafe extern "system" fn get_program_binary(
+    program: u32,
+    buf_size: i32,
+    length: *mut i32,
+    binary_format: *mut GLenum,
+    binary: *mut c_void,
+) {
+    with_state(|s| {
+        let (format, data) = s
+            .programs
+            .get(&program)
+            .and_then(|program| program.binary.clone())
+            .unwrap_or_default();
+        let len = data.len().min(buf_size.max(0) as usize);
+        unsafe {
+            std::ptr::copy_nonoverlapping(data.as_ptr(), binary.cast(), len);
+            *binary_format = GLenum(format);
+            if !length.is_null() {
+                *length = len as i32;
+            }
+        }
+        s.record("GetProgramBinary", args![program, buf_size]);
+    });
+}
+
+unsafe extern "system" fn program_binary(
+    program: u32,
+    binary_format: GLenum,
+    binary: *const c_void,
+    length: i32,
+) {
+    with_state(|s| {
+        let data = unsafe { std::slice::from_raw_parts(binary.cast::<u8>(), length as usize) };
+        let linked = s.program_binary_succeeds;
+        let entry = s.programs.entry(program).or_default();
+        entry.linked = linked;
+        entry.binary = linked.then(|| (binary_format.0, data.to_vec()));
+        s.record("ProgramBinary", args![program, binary_format, length]);
+    });
+}
+
+extern "system" fn link_program(program: u32) {
+    with_state(|s| {
+        let linked = s.link_succeeds;
+        let entry = s.programs.entry(program).or_default();
+        entry.linked = linked;
+        entry.binary = linked.then(|| (BINARY_FORMAT, program.to_le_bytes().to_vec()));
+        s.record("LinkProgram", args![program]);
+    });
+}
+
+extern "system" fn use_program(program: u32) {
+    with_state(|s| {
+        s.current_program = program;
+        s.record("UseProgram", args![program]);
+    });
+}
+
+unsafe extern "system" fn get_program_iv(program: u32, pname: GLenum, params: *mut i32) {
+    with_state(|s| {
+        let entry = s.programs.get(&program);
+        let value = match pname {
+            gl46::GL_LINK_STATUS => entry.is_some_and(|program| program.linked) as i32,
+            gl46::GL_PROGRAM_BINARY_LENGTH => entry
+                .and_then(|program| program.binary.as_ref())
+                .map_or(0, |(_, data)| data.len() as i32),
+            gl46::GL_ATTACHED_SHADERS => {
+                entry.map_or(0, |program| program.attached_shaders.len() as i32)
+            }
+            gl46::GL_INFO_LOG_LENGTH => s.info_log.len() as i32,
+            _ => 0,
+        };
+        unsafe { *params = value };
+        s.record("GetProgramiv", args![program, pname]);
+    });
+}
+
+fn program_resources(s: &MockState, program: u32, interface: GLenum) -> &[Resource] {
+    s.programs
+        .get(&program)
+        .and_then(|program| program.resources.get(&interface.0))
+        .map_or(&[], Vec::as_slice)
+}
+
+unsafe extern "system" fn get_program_interface_iv(
+    program: u32,
+    interface: GLenum,
+    pname: GLenum,
+    params: *mut i32,
+) {
+    with_state(|s| {
+        let resources = program_resources(s, program, interface);
+        let value = match pname {
+            gl46::GL_ACTIVE_RESOURCES => resources.len() as i32,
+            gl46::GL_MAX_NAME_LENGTH => resources
+                .iter()
+                .map(|resource| resource.name.len() as i32 + 1)
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        };
+        unsafe { *params = value };
+        s.record("GetProgramInterfaceiv", args![program, interface, pname]);
+    });
+}
+
+unsafe extern "system" fn get_program_resource_iv(
+    program: u32,
+    interface: GLenum,
+    index: u32,
+    prop_count: i32,
+    props: *const GLenum,
+    count: i32,
+    length: *mut i32,
+    params: *mut i32,
+) {
+    with_state(|s| {
+        let resource = program_resources(s, program, interface).get(index as usize);
+        let props = unsafe { std::slice::from_raw_parts(props, prop_count as usize) };
+        let written = props.len().min(count.max(0) as usize);
+        for (i, prop) in props.iter().take(written).enumerate() {
+            let value = resource
+                .and_then(|resource| resource.properties.get(&prop.0).copied())
+                .unwrap_or(0);
+            unsafe { *params.add(i) = value };
+        }
+        if !length.is_null() {
+            unsafe { *length = written as i32 };
+        }
+        s.record("GetProgramResourceiv", args![program, interface, index]);
+    });
+}
+
+unsafe extern "system" fn get_program_resource_name(
+    program: u32,
+    interface: GLenum,
+    index: u32,
+    buf_size: i32,
+    length: *mut i32,
+    name: *mut u8,
+) {
+    with_state(|s| {
+        let resource_name = program_resources(s, program, interface)
+            .get(index as usize)
+            .map(|resource| resource.name.clone())
+            .unwrap_or_default();
+        let len = resource_name.len().min((buf_size.max(1) - 1) as usize);
+        unsafe {
+            std::ptr::copy_nonoverlapping(resource_name.as_ptr(), name, len);
+            *name.add(len) = 0;
+            if !length.is_null() {
+                *length = len as i32;
+            }
+        }
+        s.record("GetProgramResourceName", args![program, interface, index]);
+    });
+}
+
+/// Hands out locations in request order unless a test assigned them up front.
+unsafe extern "system" fn get_uniform_location(program: u32, name: *const u8) -> i32 {
+    with_state(|s| {
+        let name = unsafe { read_c_str(name) };
+        let entry = s.programs.entry(program).or_default();
+        let next = entry.uniform_locations.len() as i32;
+        let location = *entry.uniform_locations.entry(name.clone()).or_insert(next);
+        s.record("GetUniformLocation", args![program, name, location]);
+        location
+    })
+}
+
+unsafe extern "system" fn uniform_matrix_4fv(
+    location: i32,
+    count: i32,
+    transpose: u8,
+    value: *const f32,
+) {
+    with_state(|s| {
+        let values = unsafe { std::slice::from_raw_parts(value, 16 * count as usize).to_vec() };
+        s.uniforms.insert((s.current_program, location), values);
+        s.record("UniformMatrix4fv", args![location, count, transpose]);
+    });
+}
+
+unsafe extern "system" fn program_uniform_matrix_4fv(
+    program: u32,
+    location: i32,
+    count: i32,
+    transpose: u8,
+    value: *const f32,
+) {
+    with_state(|s| {
+        let values = unsafe { std::slice::from_raw_parts(value, 16 * count as usize).to_vec() };
+        s.uniforms.insert((program, location), values);
+        s.record(
+            "ProgramUniformMatrix4fv",
+            args![program, location, count, transpose],
+        );
+    });
+}
+
+unsafe extern "system" fn create_program_pipelines(n: i32, pipelines: *mut u32) {
+    with_state(|s| {
+        for name in unsafe { write_names(s, n, pipelines) } {
+            s.pipelines.insert(name, Pipeline::default());
+        }
+        s.record("CreateProgramPipelines", args![n]);
+    });
+}
+
+extern "system" fn use_program_stages(pipeline: u32, stages: GLbitfield, program: u32) {
+    with_state(|s| {
+        let entry = s.pipelines.entry(pipeline).or_default();
+        for bit in (0..32).map(|shift| 1u32 << shift) {
+            if stages.0 & bit == 0 {
+                continue;
+            }
+            if program == 0 {
+                entry.stages.remove(&bit);
+            } else {
+                entry.stages.insert(bit, program);
+            }
+        }
+        s.record("UseProgramStages", args![pipeline, stages, program]);
+    });
+}
+
+extern "system" fn active_shader_program(pipeline: u32, program: u32) {
+    with_state(|s| {
+        s.pipelines.entry(pipeline).or_default().active_program = program;
+        s.record("ActiveShaderProgram", args![pipeline, program]);
+    });
+}
+
+extern "system" fn bind_program_pipeline(pipeline: u32) {
+    with_state(|s| {
+        s.bound_pipeline = pipeline;
+        s.record("BindProgramPipeline", args![pipeline]);
+    });
+}
+
+extern "system" fn validate_program_pipeline(pipeline: u32) {
+    with_state(|s| {
+        let validated = s.validate_succeeds;
+        s.pipelines.entry(pipeline).or_default().validated = validated;
+        s.record("ValidateProgramPipeline", args![pipeline]);
+    });
+}
+
+unsafe extern "system" fn get_program_pipeline_iv(pipeline: u32, pname: GLenum, params: *mut i32) {
+    with_state(|s| {
+        let value = match pname {
+            gl46::GL_VALIDATE_STATUS => {
+                s.pipelines.get(&pipeline).is_some_and(|p| p.validated) as i32
+            }
+            gl46::GL_INFO_LOG_LENGTH => s.info_log.len() as i32,
+            _ => 0,
+        };
+        unsafe { *params = value };
+        s.record("GetProgramPipelineiv", args![pipeline, pname]);
+    });
+}
+
+unsafe extern "system" fn delete_program_pipelines(n: i32, pipelines: *const u32) {
+    with_state(|s| {
+        for name in unsafe { read_names(n, pipelines) } {
+            s.pipelines.remove(&name);
+            if s.bound_pipeline == name {
+                s.bound_pipeline = 0;
+            }
+            s.record("DeleteProgramPipelines", args![name]);
+        }
+    });
+}
+
+extern "system" fn delete_program(program: u32) {
+    with_state(|s| {
+        s.programs.remove(&program);
+        if s.current_program == program {
+            s.current_program = 0;
+        }
+        s.record("DeleteProgram", args![program]);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gl, live_object_counts, Buffer, DrawMode, IndexType, Int2101010Rev, Integer, Normalized,
+        ObjectKind, ProgramError, ProgramPipeline, ProgramStages, ShaderProgram,
+        UnsignedInt10F11F11FRev, VertexArray, VertexAttribIType, VertexAttribPointerType,
+        VertexLayout,
+    };
+
+    crate::vertex_layout! {
+        #[derive(Clone, Copy)]
+        struct EveryFormat {
+            #[location(0)]
+            position: [f32; 3],
+            #[location(1)]
+            color: Normalized<[u8; 4]>,
+            #[location(2)]
+            joints: Integer<[u16; 4]>,
+            #[location(3)]
+            normal: Normalized<Int2101010Rev>,
+            #[location(4)]
+            radiance: UnsignedInt10F11F11FRev,
+            #[location(5)]
+            time: f64,
+            #[location(6)]
+            offset: [i16; 2],
+        }
+    }
+
+    #[test]
+    fn unsupported_entry_points_record_and_return_zero() {
+        install();
+        let is_buffer = unsafe { gl().IsBuffer(7) };
+        unsafe { gl().Finish() };
+
+        assert_eq!(is_buffer, 0);
+        let names: Vec<_> = calls().iter().map(|call| call.name).collect();
+        assert_eq!(names, ["IsBuffer", "Finish"]);
+    }
+
+    #[test]
+    fn buffers_get_fresh_names_and_are_deleted_on_drop() {
+        install();
+        let buffers = Buffer::create(2);
+        assert_eq!(buffers[0].id(), 1);
+        assert_eq!(buffers[1].id(), 2);
+
+        drop(buffers);
+        assert_eq!(calls_named("DeleteBuffers").len(), 2);
+    }
+
+    #[test]
+    fn objects_are_tracked_per_thread() {
+        install();
+        let _buffer = Buffer::create1();
+
+        let other = std::thread::spawn(|| {
+            install();
+            let _buffers = Buffer::create(3);
+            live_object_counts().get(&ObjectKind::Buffer).copied()
+        });
+
+        let other = other.join().unwrap();
+        if cfg!(debug_assertions) {
+            assert_eq!(other, Some(3));
+            assert_eq!(live_object_counts().get(&ObjectKind::Buffer), Some(&1));
+        }
+    }
+
+    #[test]
+    fn attribute_limit_is_queried_once() {
+        install();
+        let mut vertex_array = VertexArray::create1();
+        for index in 0..4 {
+            vertex_array.enable_attrib(index);
+        }
+
+        assert_eq!(calls_named("GetIntegerv").len(), 1);
+        assert_eq!(calls_named("EnableVertexArrayAttrib").len(), 4);
+
+        // A new context may have different limits.
+        install();
+        vertex_array.enable_attrib(0);
+        assert_eq!(calls_named("GetIntegerv").len(), 1);
+    }
+
+    #[test]
+    fn patch_vertex_limit_is_queried_once() {
+        install();
+        crate::patch_vertices(4);
+        crate::patch_vertices(32);
+
+        assert_eq!(calls_named("GetIntegerv").len(), 1);
+        assert_eq!(
+            with_state(|state| state.integers[&gl46::GL_PATCH_VERTICES.0]),
+            32
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "between 1 and GL_MAX_PATCH_VERTICES (32)")]
+    fn patches_past_the_limit_panic() {
+        install();
+        crate::patch_vertices(33);
+    }
+
+    #[test]
+    fn legacy_pointers_record_normalization_and_types() {
+        install();
+        let vertex_array = VertexArray::create1();
+        vertex_array.bind();
+        unsafe {
+            crate::vertex_attrib_pointer(0, 4, VertexAttribPointerType::UnsignedByte, true, 32, 0);
+            crate::vertex_attrib_pointer(1, 3, VertexAttribPointerType::Float, false, 32, 4);
+            crate::vertex_attrib_i_pointer(2, 2, VertexAttribIType::Short, 32, 16);
+            crate::vertex_attrib_l_pointer(3, 1, 32, 24);
+        }
+
+        let pointers = calls_named("VertexAttribPointer");
+        assert_eq!(pointers[0].int(3), 1);
+        assert_eq!(pointers[1].int(3), 0);
+
+        with_state(|state| {
+            let attribs = &state.vertex_arrays[&vertex_array.id()].attribs;
+            let described = |location: u32| {
+                let attrib = &attribs[&location];
+                (attrib.kind, attrib.size, attrib.r#type, attrib.normalized)
+            };
+            assert_eq!(
+                described(0),
+                (AttribKind::Float, 4, gl46::GL_UNSIGNED_BYTE.0, true)
+            );
+            assert_eq!(
+                described(1),
+                (AttribKind::Float, 3, gl46::GL_FLOAT.0, false)
+            );
+            assert_eq!(
+                described(2),
+                (AttribKind::Integer, 2, gl46::GL_SHORT.0, false)
+            );
+            assert_eq!(
+                described(3),
+                (AttribKind::Double, 1, gl46::GL_DOUBLE.0, false)
+            );
+        });
+    }
+
+    #[test]
+    fn draws_record_the_index_type() {
+        install();
+        for (index_type, offset) in [
+            (IndexType::UnsignedByte, 3),
+            (IndexType::UnsignedShort, 6),
+            (IndexType::UnsignedInt, 12),
+        ] {
+            crate::draw_elements(DrawMode::Triangles, 3, index_type, offset);
+        }
+
+        let draws = with_state(|state| state.draws.clone());
+        let recorded: Vec<_> = draws
+            .iter()
+            .map(|draw| (draw.index_type, draw.offset))
+            .collect();
+        assert_eq!(
+            recorded,
+            [
+                (gl46::GL_UNSIGNED_BYTE.0, 3),
+                (gl46::GL_UNSIGNED_SHORT.0, 6),
+                (gl46::GL_UNSIGNED_INT.0, 12),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "attribute location 16 exceeds GL_MAX_VERTEX_ATTRIBS (16)")]
+    fn locations_past_the_limit_panic() {
+        install();
+        VertexArray::create1().enable_attrib(16);
+    }
+
+    #[test]
+    #[should_panic(expected = "packed 2_10_10_10 attributes must have 4 components")]
+    fn packed_attributes_need_every_component() {
+        install();
+        VertexArray::create1().attrib_format(0, 3, VertexAttribPointerType::Int2101010Rev, true, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "components must be between 1 and 4")]
+    fn attributes_have_at_most_four_components() {
+        install();
+        VertexArray::create1().attrib_i_format(0, 5, VertexAttribIType::Int, 0);
+    }
+
+    #[test]
+    fn layouts_apply_every_attribute_format() {
+        install();
+        let mut vertex_array = VertexArray::create1();
+        vertex_array.apply_layout::<EveryFormat>(0);
+
+        let float = |size, r#type: GLenum, normalized, offset| Attrib {
+            enabled: true,
+            kind: AttribKind::Float,
+            size,
+            r#type: r#type.0,
+            normalized,
+            relative_offset: offset,
+            binding: 0,
+        };
+        let expected = [
+            float(3, gl46::GL_FLOAT, false, 0),
+            float(4, gl46::GL_UNSIGNED_BYTE, true, 12),
+            Attrib {
+                kind: AttribKind::Integer,
+                ..float(4, gl46::GL_UNSIGNED_SHORT, false, 16)
+            },
+            float(4, gl46::GL_INT_2_10_10_10_REV, true, 24),
+            float(3, gl46::GL_UNSIGNED_INT_10F_11F_11F_REV, false, 28),
+            Attrib {
+                kind: AttribKind::Double,
+                ..float(1, gl46::GL_DOUBLE, false, 32)
+            },
+            float(2, gl46::GL_SHORT, false, 40),
+        ];
+        with_state(|state| {
+            let attribs = &state.vertex_arrays[&vertex_array.id()].attribs;
+            for (location, attrib) in expected.iter().enumerate() {
+                assert_eq!(&attribs[&(location as u32)], attrib, "location {location}");
+            }
+        });
+
+        let formats: Vec<_> = calls()
+            .into_iter()
+            .filter(|call| {
+                call.name.starts_with("VertexArrayAttrib") && call.name.ends_with("Format")
+            })
+            .map(|call| (call.name, call.int(1)))
+            .collect();
+        assert_eq!(
+            formats,
+            [
+                ("VertexArrayAttribFormat", 0),
+                ("VertexArrayAttribFormat", 1),
+                ("VertexArrayAttribIFormat", 2),
+                ("VertexArrayAttribFormat", 3),
+                ("VertexArrayAttribFormat", 4),
+                ("VertexArrayAttribLFormat", 5),
+                ("VertexArrayAttribFormat", 6),
+            ]
+        );
+        assert_eq!(EveryFormat::stride(), 48);
+    }
+
+    fn separable_program() -> ShaderProgram {
+        let mut program = ShaderProgram::create();
+        program.set_separable(true);
+        program.link().unwrap();
+        program
+    }
+
+    #[test]
+    fn pipelines_combine_separable_programs() {
+        install();
+        let vertex = separable_program();
+        let fragment = separable_program();
+        with_state(|state| {
+            assert!(state.programs[&vertex.id()].separable);
+            assert!(state.programs[&fragment.id()].separable);
+        });
+
+        let mut pipeline = ProgramPipeline::create1();
+        pipeline.use_program_stages(
+            ProgramStages::VERTEX | ProgramStages::TESS_EVALUATION,
+            &vertex,
+        );
+        pipeline.use_program_stages(ProgramStages::FRAGMENT, &fragment);
+        pipeline.clear_program_stages(ProgramStages::TESS_EVALUATION);
+        pipeline.active_shader_program(&fragment);
+        pipeline.bind();
+
+        with_state(|state| {
+            let simulated = &state.pipelines[&pipeline.id()];
+            assert_eq!(
+                simulated.stages,
+                HashMap::from([
+                    (gl46::GL_VERTEX_SHADER_BIT.0, vertex.id()),
+                    (gl46::GL_FRAGMENT_SHADER_BIT.0, fragment.id()),
+                ])
+            );
+            assert_eq!(simulated.active_program, fragment.id());
+            assert_eq!(state.bound_pipeline, pipeline.id());
+        });
+        assert_eq!(pipeline.validate(), Ok(()));
+
+        let id = pipeline.id();
+        drop(pipeline);
+        assert_eq!(calls_named("DeleteProgramPipelines")[0].int(0), id as i64);
+    }
+
+    #[test]
+    fn failed_validation_returns_the_log() {
+        install();
+        let program = separable_program();
+        let mut pipeline = ProgramPipeline::create1();
+        pipeline.use_program_stages(ProgramStages::VERTEX, &program);
+        with_state(|state| {
+            state.validate_succeeds = false;
+            state.info_log = "no fragment stage".to_string();
+        });
+
+        assert_eq!(
+            pipeline.validate(),
+            Err(ProgramError::Validation("no fragment stage".to_string()))
+        );
+    }
+}
@@ -0,0 +1,727 @@
+//! Stubs for the entry points the mock doesn't simulate.
+//!
+//! Every stub has the exact signature of its GL command, so calling one is
+//! well defined. It records the call without arguments and returns zero. The
+//! signatures mirror `gl46`'s `gl_command_types.rs`.
+
+use std::ffi::c_void;
+
+use gl46::*;
+
+use super::with_state;
+
+// `gl46` keeps its scalar aliases crate-private.
+type GLboolean = u8;
+type GLbyte = i8;
+type GLchar = u8;
+type GLdouble = f64;
+type GLfloat = f32;
+type GLint = i32;
+type GLint64 = i64;
+type GLintptr = isize;
+type GLshort = i16;
+type GLsizei = i32;
+type GLsizeiptr = isize;
+type GLubyte = u8;
+type GLuint = u32;
+type GLuint64 = u64;
+type GLuint64EXT = u64;
+type GLushort = u16;
+
+macro_rules! stubs {
+    ($($f:ident($($ty:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            #[allow(non_snake_case)]
+            extern "system" fn $f($(_: $ty),*) $(-> $ret)? {
+                with_state(|s| s.record(&stringify!($f)[2..], Vec::new()));
+                // Zero is a valid value for every GL return type: null
+                // pointers, `GL_FALSE`, `GL_NO_ERROR` and object name 0.
+                unsafe { std::mem::zeroed() }
+            }
+        )*
+
+        pub(super) fn lookup(name: &str) -> Option<*const c_void> {
+            match name {
+                $(stringify!($f) => Some($f as *const c_void),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+stubs! {
+    glActiveShaderProgram(GLuint, GLuint);
+    glActiveTexture(TextureUnit);
+    glAttachShader(GLuint, GLuint);
+    glBeginConditionalRender(GLuint, ConditionalRenderMode);
+    glBeginQuery(QueryTarget, GLuint);
+    glBeginQueryIndexed(QueryTarget, GLuint, GLuint);
+    glBeginTransformFeedback(PrimitiveType);
+    glBindAttribLocation(GLuint, GLuint, *const GLchar);
+    glBindBuffer(BufferTargetARB, GLuint);
+    glBindBufferBase(BufferTargetARB, GLuint, GLuint);
+    glBindBufferRange(BufferTargetARB, GLuint, GLuint, GLintptr, GLsizeiptr);
+    glBindBuffersBase(BufferTargetARB, GLuint, GLsizei, *const GLuint);
+    glBindBuffersRange(BufferTargetARB, GLuint, GLsizei, *const GLuint, *const GLintptr, *const GLsizeiptr);
+    glBindFragDataLocation(GLuint, GLuint, *const GLchar);
+    glBindFragDataLocationIndexed(GLuint, GLuint, GLuint, *const GLchar);
+    glBindFramebuffer(FramebufferTarget, GLuint);
+    glBindImageTexture(GLuint, GLuint, GLint, GLboolean, GLint, BufferAccessARB, InternalFormat);
+    glBindImageTextures(GLuint, GLsizei, *const GLuint);
+    glBindProgramPipeline(GLuint);
+    glBindRenderbuffer(RenderbufferTarget, GLuint);
+    glBindSampler(GLuint, GLuint);
+    glBindSamplers(GLuint, GLsizei, *const GLuint);
+    glBindTexture(TextureTarget, GLuint);
+    glBindTextureUnit(GLuint, GLuint);
+    glBindTextures(GLuint, GLsizei, *const GLuint);
+    glBindTransformFeedback(BindTransformFeedbackTarget, GLuint);
+    glBindVertexArray(GLuint);
+    glBindVertexBuffer(GLuint, GLuint, GLintptr, GLsizei);
+    glBindVertexBuffers(GLuint, GLsizei, *const GLuint, *const GLintptr, *const GLsizei);
+    glBlendColor(GLfloat, GLfloat, GLfloat, GLfloat);
+    glBlendEquation(BlendEquationModeEXT);
+    glBlendEquationSeparate(BlendEquationModeEXT, BlendEquationModeEXT);
+    glBlendEquationSeparatei(GLuint, BlendEquationModeEXT, BlendEquationModeEXT);
+    glBlendEquationi(GLuint, BlendEquationModeEXT);
+    glBlendFunc(BlendingFactor, BlendingFactor);
+    glBlendFuncSeparate(BlendingFactor, BlendingFactor, BlendingFactor, BlendingFactor);
+    glBlendFuncSeparatei(GLuint, BlendingFactor, BlendingFactor, BlendingFactor, BlendingFactor);
+    glBlendFunci(GLuint, BlendingFactor, BlendingFactor);
+    glBlitFramebuffer(GLint, GLint, GLint, GLint, GLint, GLint, GLint, GLint, GLbitfield, BlitFramebufferFilter);
+    glBlitNamedFramebuffer(GLuint, GLuint, GLint, GLint, GLint, GLint, GLint, GLint, GLint, GLint, GLbitfield, BlitFramebufferFilter);
+    glBufferData(BufferTargetARB, GLsizeiptr, *const c_void, BufferUsageARB);
+    glBufferStorage(BufferStorageTarget, GLsizeiptr, *const c_void, GLbitfield);
+    glBufferSubData(BufferTargetARB, GLintptr, GLsizeiptr, *const c_void);
+    glCheckFramebufferStatus(FramebufferTarget) -> FramebufferStatus;
+    glCheckNamedFramebufferStatus(GLuint, FramebufferTarget) -> FramebufferStatus;
+    glClampColor(ClampColorTargetARB, ClampColorModeARB);
+    glClear(GLbitfield);
+    glClearBufferData(BufferStorageTarget, InternalFormat, PixelFormat, PixelType, *const c_void);
+    glClearBufferSubData(BufferTargetARB, InternalFormat, GLintptr, GLsizeiptr, PixelFormat, PixelType, *const c_void);
+    glClearBufferfi(Buffer, GLint, GLfloat, GLint);
+    glClearBufferfv(Buffer, GLint, *const GLfloat);
+    glClearBufferiv(Buffer, GLint, *const GLint);
+    glClearBufferuiv(Buffer, GLint, *const GLuint);
+    glClearColor(GLfloat, GLfloat, GLfloat, GLfloat);
+    glClearDepth(GLdouble);
+    glClearDepthf(GLfloat);
+    glClearNamedBufferData(GLuint, InternalFormat, PixelFormat, PixelType, *const c_void);
+    glClearNamedBufferSubData(GLuint, InternalFormat, GLintptr, GLsizeiptr, PixelFormat, PixelType, *const c_void);
+    glClearNamedFramebufferfi(GLuint, Buffer, GLint, GLfloat, GLint);
+    glClearNamedFramebufferfv(GLuint, Buffer, GLint, *const GLfloat);
+    glClearNamedFramebufferiv(GLuint, Buffer, GLint, *const GLint);
+    glClearNamedFramebufferuiv(GLuint, Buffer, GLint, *const GLuint);
+    glClearStencil(GLint);
+    glClearTexImage(GLuint, GLint, PixelFormat, PixelType, *const c_void);
+    glClearTexSubImage(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, PixelType, *const c_void);
+    glClientWaitSync(GLsync, GLbitfield, GLuint64) -> SyncStatus;
+    glClipControl(ClipControlOrigin, ClipControlDepth);
+    glColorMask(GLboolean, GLboolean, GLboolean, GLboolean);
+    glColorMaski(GLuint, GLboolean, GLboolean, GLboolean, GLboolean);
+    glCompileShader(GLuint);
+    glCompressedTexImage1D(TextureTarget, GLint, InternalFormat, GLsizei, GLint, GLsizei, *const c_void);
+    glCompressedTexImage2D(TextureTarget, GLint, InternalFormat, GLsizei, GLsizei, GLint, GLsizei, *const c_void);
+    glCompressedTexImage3D(TextureTarget, GLint, InternalFormat, GLsizei, GLsizei, GLsizei, GLint, GLsizei, *const c_void);
+    glCompressedTexSubImage1D(TextureTarget, GLint, GLint, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCompressedTexSubImage2D(TextureTarget, GLint, GLint, GLint, GLsizei, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCompressedTexSubImage3D(TextureTarget, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCompressedTextureSubImage1D(GLuint, GLint, GLint, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCompressedTextureSubImage2D(GLuint, GLint, GLint, GLint, GLsizei, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCompressedTextureSubImage3D(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, GLsizei, *const c_void);
+    glCopyBufferSubData(CopyBufferSubDataTarget, CopyBufferSubDataTarget, GLintptr, GLintptr, GLsizeiptr);
+    glCopyImageSubData(GLuint, CopyImageSubDataTarget, GLint, GLint, GLint, GLint, GLuint, CopyImageSubDataTarget, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei);
+    glCopyNamedBufferSubData(GLuint, GLuint, GLintptr, GLintptr, GLsizeiptr);
+    glCopyTexImage1D(TextureTarget, GLint, InternalFormat, GLint, GLint, GLsizei, GLint);
+    glCopyTexImage2D(TextureTarget, GLint, InternalFormat, GLint, GLint, GLsizei, GLsizei, GLint);
+    glCopyTexSubImage1D(TextureTarget, GLint, GLint, GLint, GLint, GLsizei);
+    glCopyTexSubImage2D(TextureTarget, GLint, GLint, GLint, GLint, GLint, GLsizei, GLsizei);
+    glCopyTexSubImage3D(TextureTarget, GLint, GLint, GLint, GLint, GLint, GLint, GLsizei, GLsizei);
+    glCopyTextureSubImage1D(GLuint, GLint, GLint, GLint, GLint, GLsizei);
+    glCopyTextureSubImage2D(GLuint, GLint, GLint, GLint, GLint, GLint, GLsizei, GLsizei);
+    glCopyTextureSubImage3D(GLuint, GLint, GLint, GLint, GLint, GLint, GLint, GLsizei, GLsizei);
+    glCreateBuffers(GLsizei, *mut GLuint);
+    glCreateFramebuffers(GLsizei, *mut GLuint);
+    glCreateProgram() -> GLuint;
+    glCreateProgramPipelines(GLsizei, *mut GLuint);
+    glCreateQueries(QueryTarget, GLsizei, *mut GLuint);
+    glCreateRenderbuffers(GLsizei, *mut GLuint);
+    glCreateSamplers(GLsizei, *mut GLuint);
+    glCreateShader(ShaderType) -> GLuint;
+    glCreateShaderProgramv(ShaderType, GLsizei, *const *const GLchar) -> GLuint;
+    glCreateTextures(TextureTarget, GLsizei, *mut GLuint);
+    glCreateTransformFeedbacks(GLsizei, *mut GLuint);
+    glCreateVertexArrays(GLsizei, *mut GLuint);
+    glCullFace(CullFaceMode);
+    glDebugMessageCallback(GLDEBUGPROC, *const c_void);
+    glDebugMessageControl(DebugSource, DebugType, DebugSeverity, GLsizei, *const GLuint, GLboolean);
+    glDebugMessageInsert(DebugSource, DebugType, GLuint, DebugSeverity, GLsizei, *const GLchar);
+    glDeleteBuffers(GLsizei, *const GLuint);
+    glDeleteFramebuffers(GLsizei, *const GLuint);
+    glDeleteProgram(GLuint);
+    glDeleteProgramPipelines(GLsizei, *const GLuint);
+    glDeleteQueries(GLsizei, *const GLuint);
+    glDeleteRenderbuffers(GLsizei, *const GLuint);
+    glDeleteSamplers(GLsizei, *const GLuint);
+    glDeleteShader(GLuint);
+    glDeleteSync(GLsync);
+    glDeleteTextures(GLsizei, *const GLuint);
+    glDeleteTransformFeedbacks(GLsizei, *const GLuint);
+    glDeleteVertexArrays(GLsizei, *const GLuint);
+    glDepthFunc(DepthFunction);
+    glDepthMask(GLboolean);
+    glDepthRange(GLdouble, GLdouble);
+    glDepthRangeArrayv(GLuint, GLsizei, *const GLdouble);
+    glDepthRangeIndexed(GLuint, GLdouble, GLdouble);
+    glDepthRangef(GLfloat, GLfloat);
+    glDetachShader(GLuint, GLuint);
+    glDisable(EnableCap);
+    glDisableVertexArrayAttrib(GLuint, GLuint);
+    glDisableVertexAttribArray(GLuint);
+    glDisablei(EnableCap, GLuint);
+    glDispatchCompute(GLuint, GLuint, GLuint);
+    glDispatchComputeIndirect(GLintptr);
+    glDrawArrays(PrimitiveType, GLint, GLsizei);
+    glDrawArraysIndirect(PrimitiveType, *const c_void);
+    glDrawArraysInstanced(PrimitiveType, GLint, GLsizei, GLsizei);
+    glDrawArraysInstancedBaseInstance(PrimitiveType, GLint, GLsizei, GLsizei, GLuint);
+    glDrawBuffer(DrawBufferMode);
+    glDrawBuffers(GLsizei, *const DrawBufferMode);
+    glDrawElements(PrimitiveType, GLsizei, DrawElementsType, *const c_void);
+    glDrawElementsBaseVertex(PrimitiveType, GLsizei, DrawElementsType, *const c_void, GLint);
+    glDrawElementsIndirect(PrimitiveType, DrawElementsType, *const c_void);
+    glDrawElementsInstanced(PrimitiveType, GLsizei, DrawElementsType, *const c_void, GLsizei);
+    glDrawElementsInstancedBaseInstance(PrimitiveType, GLsizei, PrimitiveType, *const c_void, GLsizei, GLuint);
+    glDrawElementsInstancedBaseVertex(PrimitiveType, GLsizei, DrawElementsType, *const c_void, GLsizei, GLint);
+    glDrawElementsInstancedBaseVertexBaseInstance(PrimitiveType, GLsizei, DrawElementsType, *const c_void, GLsizei, GLint, GLuint);
+    glDrawRangeElements(PrimitiveType, GLuint, GLuint, GLsizei, DrawElementsType, *const c_void);
+    glDrawRangeElementsBaseVertex(PrimitiveType, GLuint, GLuint, GLsizei, DrawElementsType, *const c_void, GLint);
+    glDrawTransformFeedback(PrimitiveType, GLuint);
+    glDrawTransformFeedbackInstanced(PrimitiveType, GLuint, GLsizei);
+    glDrawTransformFeedbackStream(PrimitiveType, GLuint, GLuint);
+    glDrawTransformFeedbackStreamInstanced(PrimitiveType, GLuint, GLuint, GLsizei);
+    glEnable(EnableCap);
+    glEnableVertexArrayAttrib(GLuint, GLuint);
+    glEnableVertexAttribArray(GLuint);
+    glEnablei(EnableCap, GLuint);
+    glEndConditionalRender();
+    glEndQuery(QueryTarget);
+    glEndQueryIndexed(QueryTarget, GLuint);
+    glEndTransformFeedback();
+    glFenceSync(SyncCondition, GLbitfield) -> GLsync;
+    glFinish();
+    glFlush();
+    glFlushMappedBufferRange(BufferTargetARB, GLintptr, GLsizeiptr);
+    glFlushMappedNamedBufferRange(GLuint, GLintptr, GLsizeiptr);
+    glFramebufferParameteri(FramebufferTarget, FramebufferParameterName, GLint);
+    glFramebufferRenderbuffer(FramebufferTarget, FramebufferAttachment, RenderbufferTarget, GLuint);
+    glFramebufferTexture(FramebufferTarget, FramebufferAttachment, GLuint, GLint);
+    glFramebufferTexture1D(FramebufferTarget, FramebufferAttachment, TextureTarget, GLuint, GLint);
+    glFramebufferTexture2D(FramebufferTarget, FramebufferAttachment, TextureTarget, GLuint, GLint);
+    glFramebufferTexture3D(FramebufferTarget, FramebufferAttachment, TextureTarget, GLuint, GLint, GLint);
+    glFramebufferTextureLayer(FramebufferTarget, FramebufferAttachment, GLuint, GLint, GLint);
+    glFrontFace(FrontFaceDirection);
+    glGenBuffers(GLsizei, *mut GLuint);
+    glGenFramebuffers(GLsizei, *mut GLuint);
+    glGenProgramPipelines(GLsizei, *mut GLuint);
+    glGenQueries(GLsizei, *mut GLuint);
+    glGenRenderbuffers(GLsizei, *mut GLuint);
+    glGenSamplers(GLsizei, *mut GLuint);
+    glGenTextures(GLsizei, *mut GLuint);
+    glGenTransformFeedbacks(GLsizei, *mut GLuint);
+    glGenVertexArrays(GLsizei, *mut GLuint);
+    glGenerateMipmap(TextureTarget);
+    glGenerateTextureMipmap(GLuint);
+    glGetActiveAtomicCounterBufferiv(GLuint, GLuint, AtomicCounterBufferPName, *mut GLint);
+    glGetActiveAttrib(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLint, *mut AttributeType, *mut GLchar);
+    glGetActiveSubroutineName(GLuint, ShaderType, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetActiveSubroutineUniformName(GLuint, ShaderType, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetActiveSubroutineUniformiv(GLuint, ShaderType, GLuint, SubroutineParameterName, *mut GLint);
+    glGetActiveUniform(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLint, *mut UniformType, *mut GLchar);
+    glGetActiveUniformBlockName(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetActiveUniformBlockiv(GLuint, GLuint, UniformBlockPName, *mut GLint);
+    glGetActiveUniformName(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetActiveUniformsiv(GLuint, GLsizei, *const GLuint, UniformPName, *mut GLint);
+    glGetAttachedShaders(GLuint, GLsizei, *mut GLsizei, *mut GLuint);
+    glGetAttribLocation(GLuint, *const GLchar) -> GLint;
+    glGetBooleani_v(BufferTargetARB, GLuint, *mut GLboolean);
+    glGetBooleanv(GetPName, *mut GLboolean);
+    glGetBufferParameteri64v(BufferTargetARB, BufferPNameARB, *mut GLint64);
+    glGetBufferParameteriv(BufferTargetARB, BufferPNameARB, *mut GLint);
+    glGetBufferPointerv(BufferTargetARB, BufferPointerNameARB, *mut *mut c_void);
+    glGetBufferSubData(BufferTargetARB, GLintptr, GLsizeiptr, *mut c_void);
+    glGetCompressedTexImage(TextureTarget, GLint, *mut c_void);
+    glGetCompressedTextureImage(GLuint, GLint, GLsizei, *mut c_void);
+    glGetCompressedTextureSubImage(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, GLsizei, *mut c_void);
+    glGetDebugMessageLog(GLuint, GLsizei, *mut DebugSource, *mut DebugType, *mut GLuint, *mut DebugSeverity, *mut GLsizei, *mut GLchar) -> GLuint;
+    glGetDoublei_v(GetPName, GLuint, *mut GLdouble);
+    glGetDoublev(GetPName, *mut GLdouble);
+    glGetError() -> ErrorCode;
+    glGetFloati_v(GetPName, GLuint, *mut GLfloat);
+    glGetFloatv(GetPName, *mut GLfloat);
+    glGetFragDataIndex(GLuint, *const GLchar) -> GLint;
+    glGetFragDataLocation(GLuint, *const GLchar) -> GLint;
+    glGetFramebufferAttachmentParameteriv(FramebufferTarget, FramebufferAttachment, FramebufferAttachmentParameterName, *mut GLint);
+    glGetFramebufferParameteriv(FramebufferTarget, FramebufferAttachmentParameterName, *mut GLint);
+    glGetGraphicsResetStatus() -> GraphicsResetStatus;
+    glGetImageHandleARB(GLuint, GLint, GLboolean, GLint, PixelFormat) -> GLuint64;
+    glGetInteger64i_v(GetPName, GLuint, *mut GLint64);
+    glGetInteger64v(GetPName, *mut GLint64);
+    glGetIntegeri_v(GetPName, GLuint, *mut GLint);
+    glGetIntegerv(GetPName, *mut GLint);
+    glGetInternalformati64v(TextureTarget, InternalFormat, InternalFormatPName, GLsizei, *mut GLint64);
+    glGetInternalformativ(TextureTarget, InternalFormat, InternalFormatPName, GLsizei, *mut GLint);
+    glGetMultisamplefv(GetMultisamplePNameNV, GLuint, *mut GLfloat);
+    glGetNamedBufferParameteri64v(GLuint, BufferPNameARB, *mut GLint64);
+    glGetNamedBufferParameteriv(GLuint, BufferPNameARB, *mut GLint);
+    glGetNamedBufferPointerv(GLuint, BufferPointerNameARB, *mut *mut c_void);
+    glGetNamedBufferSubData(GLuint, GLintptr, GLsizeiptr, *mut c_void);
+    glGetNamedFramebufferAttachmentParameteriv(GLuint, FramebufferAttachment, FramebufferAttachmentParameterName, *mut GLint);
+    glGetNamedFramebufferParameteriv(GLuint, GetFramebufferParameter, *mut GLint);
+    glGetNamedRenderbufferParameteriv(GLuint, RenderbufferParameterName, *mut GLint);
+    glGetObjectLabel(ObjectIdentifier, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetObjectPtrLabel(*const c_void, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetPointerv(GetPointervPName, *mut *mut c_void);
+    glGetProgramBinary(GLuint, GLsizei, *mut GLsizei, *mut GLenum, *mut c_void);
+    glGetProgramInfoLog(GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetProgramInterfaceiv(GLuint, ProgramInterface, ProgramInterfacePName, *mut GLint);
+    glGetProgramPipelineInfoLog(GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetProgramPipelineiv(GLuint, PipelineParameterName, *mut GLint);
+    glGetProgramResourceIndex(GLuint, ProgramInterface, *const GLchar) -> GLuint;
+    glGetProgramResourceLocation(GLuint, ProgramInterface, *const GLchar) -> GLint;
+    glGetProgramResourceLocationIndex(GLuint, ProgramInterface, *const GLchar) -> GLint;
+    glGetProgramResourceName(GLuint, ProgramInterface, GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetProgramResourceiv(GLuint, ProgramInterface, GLuint, GLsizei, *const ProgramResourceProperty, GLsizei, *mut GLsizei, *mut GLint);
+    glGetProgramStageiv(GLuint, ShaderType, ProgramStagePName, *mut GLint);
+    glGetProgramiv(GLuint, ProgramPropertyARB, *mut GLint);
+    glGetQueryBufferObjecti64v(GLuint, GLuint, QueryObjectParameterName, GLintptr);
+    glGetQueryBufferObjectiv(GLuint, GLuint, QueryObjectParameterName, GLintptr);
+    glGetQueryBufferObjectui64v(GLuint, GLuint, QueryObjectParameterName, GLintptr);
+    glGetQueryBufferObjectuiv(GLuint, GLuint, QueryObjectParameterName, GLintptr);
+    glGetQueryIndexediv(QueryTarget, GLuint, QueryParameterName, *mut GLint);
+    glGetQueryObjecti64v(GLuint, QueryObjectParameterName, *mut GLint64);
+    glGetQueryObjectiv(GLuint, QueryObjectParameterName, *mut GLint);
+    glGetQueryObjectui64v(GLuint, QueryObjectParameterName, *mut GLuint64);
+    glGetQueryObjectuiv(GLuint, QueryObjectParameterName, *mut GLuint);
+    glGetQueryiv(QueryTarget, QueryParameterName, *mut GLint);
+    glGetRenderbufferParameteriv(RenderbufferTarget, RenderbufferParameterName, *mut GLint);
+    glGetSamplerParameterIiv(GLuint, SamplerParameterI, *mut GLint);
+    glGetSamplerParameterIuiv(GLuint, SamplerParameterI, *mut GLuint);
+    glGetSamplerParameterfv(GLuint, SamplerParameterF, *mut GLfloat);
+    glGetSamplerParameteriv(GLuint, SamplerParameterI, *mut GLint);
+    glGetShaderInfoLog(GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetShaderPrecisionFormat(ShaderType, PrecisionType, *mut [GLint; 2], *mut GLint);
+    glGetShaderSource(GLuint, GLsizei, *mut GLsizei, *mut GLchar);
+    glGetShaderiv(GLuint, ShaderParameterName, *mut GLint);
+    glGetString(StringName) -> *const GLubyte;
+    glGetStringi(StringName, GLuint) -> *const GLubyte;
+    glGetSubroutineIndex(GLuint, ShaderType, *const GLchar) -> GLuint;
+    glGetSubroutineUniformLocation(GLuint, ShaderType, *const GLchar) -> GLint;
+    glGetSynciv(GLsync, SyncParameterName, GLsizei, *mut GLsizei, *mut GLint);
+    glGetTexImage(TextureTarget, GLint, PixelFormat, PixelType, *mut c_void);
+    glGetTexLevelParameterfv(TextureTarget, GLint, GetTextureParameter, *mut GLfloat);
+    glGetTexLevelParameteriv(TextureTarget, GLint, GetTextureParameter, *mut GLint);
+    glGetTexParameterIiv(TextureTarget, GetTextureParameter, *mut GLint);
+    glGetTexParameterIuiv(TextureTarget, GetTextureParameter, *mut GLuint);
+    glGetTexParameterfv(TextureTarget, GetTextureParameter, *mut GLfloat);
+    glGetTexParameteriv(TextureTarget, GetTextureParameter, *mut GLint);
+    glGetTextureHandleARB(GLuint) -> GLuint64;
+    glGetTextureImage(GLuint, GLint, PixelFormat, PixelType, GLsizei, *mut c_void);
+    glGetTextureLevelParameterfv(GLuint, GLint, GetTextureParameter, *mut GLfloat);
+    glGetTextureLevelParameteriv(GLuint, GLint, GetTextureParameter, *mut GLint);
+    glGetTextureParameterIiv(GLuint, GetTextureParameter, *mut GLint);
+    glGetTextureParameterIuiv(GLuint, GetTextureParameter, *mut GLuint);
+    glGetTextureParameterfv(GLuint, GetTextureParameter, *mut GLfloat);
+    glGetTextureParameteriv(GLuint, GetTextureParameter, *mut GLint);
+    glGetTextureSamplerHandleARB(GLuint, GLuint) -> GLuint64;
+    glGetTextureSubImage(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, PixelType, GLsizei, *mut c_void);
+    glGetTransformFeedbackVarying(GLuint, GLuint, GLsizei, *mut GLsizei, *mut GLsizei, *mut AttributeType, *mut GLchar);
+    glGetTransformFeedbacki64_v(GLuint, TransformFeedbackPName, GLuint, *mut GLint64);
+    glGetTransformFeedbacki_v(GLuint, TransformFeedbackPName, GLuint, *mut GLint);
+    glGetTransformFeedbackiv(GLuint, TransformFeedbackPName, *mut GLint);
+    glGetUniformBlockIndex(GLuint, *const GLchar) -> GLuint;
+    glGetUniformIndices(GLuint, GLsizei, *const *const GLchar, *mut GLuint);
+    glGetUniformLocation(GLuint, *const GLchar) -> GLint;
+    glGetUniformSubroutineuiv(ShaderType, GLint, *mut GLuint);
+    glGetUniformdv(GLuint, GLint, *mut GLdouble);
+    glGetUniformfv(GLuint, GLint, *mut GLfloat);
+    glGetUniformiv(GLuint, GLint, *mut GLint);
+    glGetUniformuiv(GLuint, GLint, *mut GLuint);
+    glGetVertexArrayIndexed64iv(GLuint, GLuint, VertexArrayPName, *mut GLint64);
+    glGetVertexArrayIndexediv(GLuint, GLuint, VertexArrayPName, *mut GLint);
+    glGetVertexArrayiv(GLuint, VertexArrayPName, *mut GLint);
+    glGetVertexAttribIiv(GLuint, VertexAttribEnum, *mut GLint);
+    glGetVertexAttribIuiv(GLuint, VertexAttribEnum, *mut GLuint);
+    glGetVertexAttribLdv(GLuint, VertexAttribEnum, *mut GLdouble);
+    glGetVertexAttribLui64vARB(GLuint, VertexAttribEnum, *mut GLuint64EXT);
+    glGetVertexAttribPointerv(GLuint, VertexAttribPointerPropertyARB, *mut *mut c_void);
+    glGetVertexAttribdv(GLuint, VertexAttribPropertyARB, *mut [GLdouble; 4]);
+    glGetVertexAttribfv(GLuint, VertexAttribPropertyARB, *mut [GLfloat; 4]);
+    glGetVertexAttribiv(GLuint, VertexAttribPropertyARB, *mut [GLint; 4]);
+    glGetnCompressedTexImage(TextureTarget, GLint, GLsizei, *mut c_void);
+    glGetnTexImage(TextureTarget, GLint, PixelFormat, PixelType, GLsizei, *mut c_void);
+    glGetnUniformdv(GLuint, GLint, GLsizei, *mut GLdouble);
+    glGetnUniformfv(GLuint, GLint, GLsizei, *mut GLfloat);
+    glGetnUniformiv(GLuint, GLint, GLsizei, *mut GLint);
+    glGetnUniformuiv(GLuint, GLint, GLsizei, *mut GLuint);
+    glHint(HintTarget, HintMode);
+    glInvalidateBufferData(GLuint);
+    glInvalidateBufferSubData(GLuint, GLintptr, GLsizeiptr);
+    glInvalidateFramebuffer(FramebufferTarget, GLsizei, *const InvalidateFramebufferAttachment);
+    glInvalidateNamedFramebufferData(GLuint, GLsizei, *const FramebufferAttachment);
+    glInvalidateNamedFramebufferSubData(GLuint, GLsizei, *const FramebufferAttachment, GLint, GLint, GLsizei, GLsizei);
+    glInvalidateSubFramebuffer(FramebufferTarget, GLsizei, *const InvalidateFramebufferAttachment, GLint, GLint, GLsizei, GLsizei);
+    glInvalidateTexImage(GLuint, GLint);
+    glInvalidateTexSubImage(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei);
+    glIsBuffer(GLuint) -> GLboolean;
+    glIsEnabled(EnableCap) -> GLboolean;
+    glIsEnabledi(EnableCap, GLuint) -> GLboolean;
+    glIsFramebuffer(GLuint) -> GLboolean;
+    glIsImageHandleResidentARB(GLuint64) -> GLboolean;
+    glIsProgram(GLuint) -> GLboolean;
+    glIsProgramPipeline(GLuint) -> GLboolean;
+    glIsQuery(GLuint) -> GLboolean;
+    glIsRenderbuffer(GLuint) -> GLboolean;
+    glIsSampler(GLuint) -> GLboolean;
+    glIsShader(GLuint) -> GLboolean;
+    glIsSync(GLsync) -> GLboolean;
+    glIsTexture(GLuint) -> GLboolean;
+    glIsTextureHandleResidentARB(GLuint64) -> GLboolean;
+    glIsTransformFeedback(GLuint) -> GLboolean;
+    glIsVertexArray(GLuint) -> GLboolean;
+    glLineWidth(GLfloat);
+    glLinkProgram(GLuint);
+    glLogicOp(LogicOp);
+    glMakeImageHandleNonResidentARB(GLuint64);
+    glMakeImageHandleResidentARB(GLuint64, GLenum);
+    glMakeTextureHandleNonResidentARB(GLuint64);
+    glMakeTextureHandleResidentARB(GLuint64);
+    glMapBuffer(BufferTargetARB, BufferAccessARB) -> *mut c_void;
+    glMapBufferRange(BufferTargetARB, GLintptr, GLsizeiptr, GLbitfield) -> *mut c_void;
+    glMapNamedBuffer(GLuint, BufferAccessARB) -> *mut c_void;
+    glMapNamedBufferRange(GLuint, GLintptr, GLsizeiptr, GLbitfield) -> *mut c_void;
+    glMemoryBarrier(GLbitfield);
+    glMemoryBarrierByRegion(GLbitfield);
+    glMinSampleShading(GLfloat);
+    glMultiDrawArrays(PrimitiveType, *const GLint, *const GLsizei, GLsizei);
+    glMultiDrawArraysIndirect(PrimitiveType, *const c_void, GLsizei, GLsizei);
+    glMultiDrawArraysIndirectCount(PrimitiveType, *const c_void, GLintptr, GLsizei, GLsizei);
+    glMultiDrawElements(PrimitiveType, *const GLsizei, DrawElementsType, *const *const c_void, GLsizei);
+    glMultiDrawElementsBaseVertex(PrimitiveType, *const GLsizei, DrawElementsType, *const *const c_void, GLsizei, *const GLint);
+    glMultiDrawElementsIndirect(PrimitiveType, DrawElementsType, *const c_void, GLsizei, GLsizei);
+    glMultiDrawElementsIndirectCount(PrimitiveType, DrawElementsType, *const c_void, GLintptr, GLsizei, GLsizei);
+    glNamedBufferData(GLuint, GLsizeiptr, *const c_void, VertexBufferObjectUsage);
+    glNamedBufferStorage(GLuint, GLsizeiptr, *const c_void, GLbitfield);
+    glNamedBufferSubData(GLuint, GLintptr, GLsizeiptr, *const c_void);
+    glNamedFramebufferDrawBuffer(GLuint, ColorBuffer);
+    glNamedFramebufferDrawBuffers(GLuint, GLsizei, *const ColorBuffer);
+    glNamedFramebufferParameteri(GLuint, FramebufferParameterName, GLint);
+    glNamedFramebufferReadBuffer(GLuint, ColorBuffer);
+    glNamedFramebufferRenderbuffer(GLuint, FramebufferAttachment, RenderbufferTarget, GLuint);
+    glNamedFramebufferTexture(GLuint, FramebufferAttachment, GLuint, GLint);
+    glNamedFramebufferTextureLayer(GLuint, FramebufferAttachment, GLuint, GLint, GLint);
+    glNamedRenderbufferStorage(GLuint, InternalFormat, GLsizei, GLsizei);
+    glNamedRenderbufferStorageMultisample(GLuint, GLsizei, InternalFormat, GLsizei, GLsizei);
+    glObjectLabel(ObjectIdentifier, GLuint, GLsizei, *const GLchar);
+    glObjectPtrLabel(*const c_void, GLsizei, *const GLchar);
+    glPatchParameterfv(PatchParameterName, *const GLfloat);
+    glPatchParameteri(PatchParameterName, GLint);
+    glPauseTransformFeedback();
+    glPixelStoref(PixelStoreParameter, GLfloat);
+    glPixelStorei(PixelStoreParameter, GLint);
+    glPointParameterf(PointParameterNameARB, GLfloat);
+    glPointParameterfv(PointParameterNameARB, *const GLfloat);
+    glPointParameteri(PointParameterNameARB, GLint);
+    glPointParameteriv(PointParameterNameARB, *const GLint);
+    glPointSize(GLfloat);
+    glPolygonMode(MaterialFace, PolygonMode);
+    glPolygonOffset(GLfloat, GLfloat);
+    glPolygonOffsetClamp(GLfloat, GLfloat, GLfloat);
+    glPopDebugGroup();
+    glPrimitiveRestartIndex(GLuint);
+    glProgramBinary(GLuint, GLenum, *const c_void, GLsizei);
+    glProgramParameteri(GLuint, ProgramParameterPName, GLint);
+    glProgramUniform1d(GLuint, GLint, GLdouble);
+    glProgramUniform1dv(GLuint, GLint, GLsizei, *const GLdouble);
+    glProgramUniform1f(GLuint, GLint, GLfloat);
+    glProgramUniform1fv(GLuint, GLint, GLsizei, *const GLfloat);
+    glProgramUniform1i(GLuint, GLint, GLint);
+    glProgramUniform1iv(GLuint, GLint, GLsizei, *const GLint);
+    glProgramUniform1ui(GLuint, GLint, GLuint);
+    glProgramUniform1uiv(GLuint, GLint, GLsizei, *const GLuint);
+    glProgramUniform2d(GLuint, GLint, GLdouble, GLdouble);
+    glProgramUniform2dv(GLuint, GLint, GLsizei, *const GLdouble);
+    glProgramUniform2f(GLuint, GLint, GLfloat, GLfloat);
+    glProgramUniform2fv(GLuint, GLint, GLsizei, *const GLfloat);
+    glProgramUniform2i(GLuint, GLint, GLint, GLint);
+    glProgramUniform2iv(GLuint, GLint, GLsizei, *const GLint);
+    glProgramUniform2ui(GLuint, GLint, GLuint, GLuint);
+    glProgramUniform2uiv(GLuint, GLint, GLsizei, *const GLuint);
+    glProgramUniform3d(GLuint, GLint, GLdouble, GLdouble, GLdouble);
+    glProgramUniform3dv(GLuint, GLint, GLsizei, *const GLdouble);
+    glProgramUniform3f(GLuint, GLint, GLfloat, GLfloat, GLfloat);
+    glProgramUniform3fv(GLuint, GLint, GLsizei, *const GLfloat);
+    glProgramUniform3i(GLuint, GLint, GLint, GLint, GLint);
+    glProgramUniform3iv(GLuint, GLint, GLsizei, *const GLint);
+    glProgramUniform3ui(GLuint, GLint, GLuint, GLuint, GLuint);
+    glProgramUniform3uiv(GLuint, GLint, GLsizei, *const GLuint);
+    glProgramUniform4d(GLuint, GLint, GLdouble, GLdouble, GLdouble, GLdouble);
+    glProgramUniform4dv(GLuint, GLint, GLsizei, *const GLdouble);
+    glProgramUniform4f(GLuint, GLint, GLfloat, GLfloat, GLfloat, GLfloat);
+    glProgramUniform4fv(GLuint, GLint, GLsizei, *const GLfloat);
+    glProgramUniform4i(GLuint, GLint, GLint, GLint, GLint, GLint);
+    glProgramUniform4iv(GLuint, GLint, GLsizei, *const GLint);
+    glProgramUniform4ui(GLuint, GLint, GLuint, GLuint, GLuint, GLuint);
+    glProgramUniform4uiv(GLuint, GLint, GLsizei, *const GLuint);
+    glProgramUniformHandleui64ARB(GLuint, GLint, GLuint64);
+    glProgramUniformHandleui64vARB(GLuint, GLint, GLsizei, *const GLuint64);
+    glProgramUniformMatrix2dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix2fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix2x3dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix2x3fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix2x4dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix2x4fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix3dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix3fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix3x2dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix3x2fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix3x4dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix3x4fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix4dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix4fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix4x2dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix4x2fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProgramUniformMatrix4x3dv(GLuint, GLint, GLsizei, GLboolean, *const GLdouble);
+    glProgramUniformMatrix4x3fv(GLuint, GLint, GLsizei, GLboolean, *const GLfloat);
+    glProvokingVertex(VertexProvokingMode);
+    glPushDebugGroup(DebugSource, GLuint, GLsizei, *const GLchar);
+    glQueryCounter(GLuint, QueryCounterTarget);
+    glReadBuffer(ReadBufferMode);
+    glReadPixels(GLint, GLint, GLsizei, GLsizei, PixelFormat, PixelType, *mut c_void);
+    glReadnPixels(GLint, GLint, GLsizei, GLsizei, PixelFormat, PixelType, GLsizei, *mut c_void);
+    glReleaseShaderCompiler();
+    glRenderbufferStorage(RenderbufferTarget, InternalFormat, GLsizei, GLsizei);
+    glRenderbufferStorageMultisample(RenderbufferTarget, GLsizei, InternalFormat, GLsizei, GLsizei);
+    glResumeTransformFeedback();
+    glSampleCoverage(GLfloat, GLboolean);
+    glSampleMaski(GLuint, GLbitfield);
+    glSamplerParameterIiv(GLuint, SamplerParameterI, *const GLint);
+    glSamplerParameterIuiv(GLuint, SamplerParameterI, *const GLuint);
+    glSamplerParameterf(GLuint, SamplerParameterF, GLfloat);
+    glSamplerParameterfv(GLuint, SamplerParameterF, *const GLfloat);
+    glSamplerParameteri(GLuint, SamplerParameterI, GLint);
+    glSamplerParameteriv(GLuint, SamplerParameterI, *const GLint);
+    glScissor(GLint, GLint, GLsizei, GLsizei);
+    glScissorArrayv(GLuint, GLsizei, *const GLint);
+    glScissorIndexed(GLuint, GLint, GLint, GLsizei, GLsizei);
+    glScissorIndexedv(GLuint, *const [GLint; 4]);
+    glShaderBinary(GLsizei, *const GLuint, ShaderBinaryFormat, *const c_void, GLsizei);
+    glShaderSource(GLuint, GLsizei, *const *const GLchar, *const GLint);
+    glShaderStorageBlockBinding(GLuint, GLuint, GLuint);
+    glSpecializeShader(GLuint, *const GLchar, GLuint, *const GLuint, *const GLuint);
+    glStencilFunc(StencilFunction, GLint, GLuint);
+    glStencilFuncSeparate(StencilFaceDirection, StencilFunction, GLint, GLuint);
+    glStencilMask(GLuint);
+    glStencilMaskSeparate(StencilFaceDirection, GLuint);
+    glStencilOp(StencilOp, StencilOp, StencilOp);
+    glStencilOpSeparate(StencilFaceDirection, StencilOp, StencilOp, StencilOp);
+    glTexBuffer(TextureTarget, InternalFormat, GLuint);
+    glTexBufferRange(TextureTarget, InternalFormat, GLuint, GLintptr, GLsizeiptr);
+    glTexImage1D(TextureTarget, GLint, GLint, GLsizei, GLint, PixelFormat, PixelType, *const c_void);
+    glTexImage2D(TextureTarget, GLint, GLint, GLsizei, GLsizei, GLint, PixelFormat, PixelType, *const c_void);
+    glTexImage2DMultisample(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei, GLboolean);
+    glTexImage3D(TextureTarget, GLint, GLint, GLsizei, GLsizei, GLsizei, GLint, PixelFormat, PixelType, *const c_void);
+    glTexImage3DMultisample(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei, GLsizei, GLboolean);
+    glTexPageCommitmentARB(GLenum, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, GLboolean);
+    glTexParameterIiv(TextureTarget, TextureParameterName, *const GLint);
+    glTexParameterIuiv(TextureTarget, TextureParameterName, *const GLuint);
+    glTexParameterf(TextureTarget, TextureParameterName, GLfloat);
+    glTexParameterfv(TextureTarget, TextureParameterName, *const GLfloat);
+    glTexParameteri(TextureTarget, TextureParameterName, GLint);
+    glTexParameteriv(TextureTarget, TextureParameterName, *const GLint);
+    glTexStorage1D(TextureTarget, GLsizei, InternalFormat, GLsizei);
+    glTexStorage2D(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei);
+    glTexStorage2DMultisample(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei, GLboolean);
+    glTexStorage3D(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei, GLsizei);
+    glTexStorage3DMultisample(TextureTarget, GLsizei, InternalFormat, GLsizei, GLsizei, GLsizei, GLboolean);
+    glTexSubImage1D(TextureTarget, GLint, GLint, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTexSubImage2D(TextureTarget, GLint, GLint, GLint, GLsizei, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTexSubImage3D(TextureTarget, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTextureBarrier();
+    glTextureBuffer(GLuint, InternalFormat, GLuint);
+    glTextureBufferRange(GLuint, InternalFormat, GLuint, GLintptr, GLsizeiptr);
+    glTextureParameterIiv(GLuint, TextureParameterName, *const GLint);
+    glTextureParameterIuiv(GLuint, TextureParameterName, *const GLuint);
+    glTextureParameterf(GLuint, TextureParameterName, GLfloat);
+    glTextureParameterfv(GLuint, TextureParameterName, *const GLfloat);
+    glTextureParameteri(GLuint, TextureParameterName, GLint);
+    glTextureParameteriv(GLuint, TextureParameterName, *const GLint);
+    glTextureStorage1D(GLuint, GLsizei, InternalFormat, GLsizei);
+    glTextureStorage2D(GLuint, GLsizei, InternalFormat, GLsizei, GLsizei);
+    glTextureStorage2DMultisample(GLuint, GLsizei, InternalFormat, GLsizei, GLsizei, GLboolean);
+    glTextureStorage3D(GLuint, GLsizei, InternalFormat, GLsizei, GLsizei, GLsizei);
+    glTextureStorage3DMultisample(GLuint, GLsizei, InternalFormat, GLsizei, GLsizei, GLsizei, GLboolean);
+    glTextureSubImage1D(GLuint, GLint, GLint, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTextureSubImage2D(GLuint, GLint, GLint, GLint, GLsizei, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTextureSubImage3D(GLuint, GLint, GLint, GLint, GLint, GLsizei, GLsizei, GLsizei, PixelFormat, PixelType, *const c_void);
+    glTextureView(GLuint, TextureTarget, GLuint, InternalFormat, GLuint, GLuint, GLuint, GLuint);
+    glTransformFeedbackBufferBase(GLuint, GLuint, GLuint);
+    glTransformFeedbackBufferRange(GLuint, GLuint, GLuint, GLintptr, GLsizeiptr);
+    glTransformFeedbackVaryings(GLuint, GLsizei, *const *const GLchar, TransformFeedbackBufferMode);
+    glUniform1d(GLint, GLdouble);
+    glUniform1dv(GLint, GLsizei, *const GLdouble);
+    glUniform1f(GLint, GLfloat);
+    glUniform1fv(GLint, GLsizei, *const GLfloat);
+    glUniform1i(GLint, GLint);
+    glUniform1iv(GLint, GLsizei, *const GLint);
+    glUniform1ui(GLint, GLuint);
+    glUniform1uiv(GLint, GLsizei, *const GLuint);
+    glUniform2d(GLint, GLdouble, GLdouble);
+    glUniform2dv(GLint, GLsizei, *const GLdouble);
+    glUniform2f(GLint, GLfloat, GLfloat);
+    glUniform2fv(GLint, GLsizei, *const GLfloat);
+    glUniform2i(GLint, GLint, GLint);
+    glUniform2iv(GLint, GLsizei, *const GLint);
+    glUniform2ui(GLint, GLuint, GLuint);
+    glUniform2uiv(GLint, GLsizei, *const GLuint);
+    glUniform3d(GLint, GLdouble, GLdouble, GLdouble);
+    glUniform3dv(GLint, GLsizei, *const GLdouble);
+    glUniform3f(GLint, GLfloat, GLfloat, GLfloat);
+    glUniform3fv(GLint, GLsizei, *const GLfloat);
+    glUniform3i(GLint, GLint, GLint, GLint);
+    glUniform3iv(GLint, GLsizei, *const GLint);
+    glUniform3ui(GLint, GLuint, GLuint, GLuint);
+    glUniform3uiv(GLint, GLsizei, *const GLuint);
+    glUniform4d(GLint, GLdouble, GLdouble, GLdouble, GLdouble);
+    glUniform4dv(GLint, GLsizei, *const GLdouble);
+    glUniform4f(GLint, GLfloat, GLfloat, GLfloat, GLfloat);
+    glUniform4fv(GLint, GLsizei, *const GLfloat);
+    glUniform4i(GLint, GLint, GLint, GLint, GLint);
+    glUniform4iv(GLint, GLsizei, *const GLint);
+    glUniform4ui(GLint, GLuint, GLuint, GLuint, GLuint);
+    glUniform4uiv(GLint, GLsizei, *const GLuint);
+    glUniformBlockBinding(GLuint, GLuint, GLuint);
+    glUniformHandleui64ARB(GLint, GLuint64);
+    glUniformHandleui64vARB(GLint, GLsizei, *const GLuint64);
+    glUniformMatrix2dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix2fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix2x3dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix2x3fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix2x4dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix2x4fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix3dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix3fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix3x2dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix3x2fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix3x4dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix3x4fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix4dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix4fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix4x2dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix4x2fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformMatrix4x3dv(GLint, GLsizei, GLboolean, *const GLdouble);
+    glUniformMatrix4x3fv(GLint, GLsizei, GLboolean, *const GLfloat);
+    glUniformSubroutinesuiv(ShaderType, GLsizei, *const GLuint);
+    glUnmapBuffer(BufferTargetARB) -> GLboolean;
+    glUnmapNamedBuffer(GLuint) -> GLboolean;
+    glUseProgram(GLuint);
+    glUseProgramStages(GLuint, GLbitfield, GLuint);
+    glValidateProgram(GLuint);
+    glValidateProgramPipeline(GLuint);
+    glVertexArrayAttribBinding(GLuint, GLuint, GLuint);
+    glVertexArrayAttribFormat(GLuint, GLuint, GLint, VertexAttribType, GLboolean, GLuint);
+    glVertexArrayAttribIFormat(GLuint, GLuint, GLint, VertexAttribIType, GLuint);
+    glVertexArrayAttribLFormat(GLuint, GLuint, GLint, VertexAttribLType, GLuint);
+    glVertexArrayBindingDivisor(GLuint, GLuint, GLuint);
+    glVertexArrayElementBuffer(GLuint, GLuint);
+    glVertexArrayVertexBuffer(GLuint, GLuint, GLuint, GLintptr, GLsizei);
+    glVertexArrayVertexBuffers(GLuint, GLuint, GLsizei, *const GLuint, *const GLintptr, *const GLsizei);
+    glVertexAttrib1d(GLuint, GLdouble);
+    glVertexAttrib1dv(GLuint, *const GLdouble);
+    glVertexAttrib1f(GLuint, GLfloat);
+    glVertexAttrib1fv(GLuint, *const GLfloat);
+    glVertexAttrib1s(GLuint, GLshort);
+    glVertexAttrib1sv(GLuint, *const GLshort);
+    glVertexAttrib2d(GLuint, GLdouble, GLdouble);
+    glVertexAttrib2dv(GLuint, *const [GLdouble; 2]);
+    glVertexAttrib2f(GLuint, GLfloat, GLfloat);
+    glVertexAttrib2fv(GLuint, *const [GLfloat; 2]);
+    glVertexAttrib2s(GLuint, GLshort, GLshort);
+    glVertexAttrib2sv(GLuint, *const [GLshort; 2]);
+    glVertexAttrib3d(GLuint, GLdouble, GLdouble, GLdouble);
+    glVertexAttrib3dv(GLuint, *const [GLdouble; 3]);
+    glVertexAttrib3f(GLuint, GLfloat, GLfloat, GLfloat);
+    glVertexAttrib3fv(GLuint, *const [GLfloat; 3]);
+    glVertexAttrib3s(GLuint, GLshort, GLshort, GLshort);
+    glVertexAttrib3sv(GLuint, *const [GLshort; 3]);
+    glVertexAttrib4Nbv(GLuint, *const [GLbyte; 4]);
+    glVertexAttrib4Niv(GLuint, *const [GLint; 4]);
+    glVertexAttrib4Nsv(GLuint, *const [GLshort; 4]);
+    glVertexAttrib4Nub(GLuint, GLubyte, GLubyte, GLubyte, GLubyte);
+    glVertexAttrib4Nubv(GLuint, *const [GLubyte; 4]);
+    glVertexAttrib4Nuiv(GLuint, *const [GLuint; 4]);
+    glVertexAttrib4Nusv(GLuint, *const [GLushort; 4]);
+    glVertexAttrib4bv(GLuint, *const [GLbyte; 4]);
+    glVertexAttrib4d(GLuint, GLdouble, GLdouble, GLdouble, GLdouble);
+    glVertexAttrib4dv(GLuint, *const [GLdouble; 4]);
+    glVertexAttrib4f(GLuint, GLfloat, GLfloat, GLfloat, GLfloat);
+    glVertexAttrib4fv(GLuint, *const [GLfloat; 4]);
+    glVertexAttrib4iv(GLuint, *const [GLint; 4]);
+    glVertexAttrib4s(GLuint, GLshort, GLshort, GLshort, GLshort);
+    glVertexAttrib4sv(GLuint, *const [GLshort; 4]);
+    glVertexAttrib4ubv(GLuint, *const [GLubyte; 4]);
+    glVertexAttrib4uiv(GLuint, *const [GLuint; 4]);
+    glVertexAttrib4usv(GLuint, *const [GLushort; 4]);
+    glVertexAttribBinding(GLuint, GLuint);
+    glVertexAttribDivisor(GLuint, GLuint);
+    glVertexAttribFormat(GLuint, GLint, VertexAttribType, GLboolean, GLuint);
+    glVertexAttribI1i(GLuint, GLint);
+    glVertexAttribI1iv(GLuint, *const GLint);
+    glVertexAttribI1ui(GLuint, GLuint);
+    glVertexAttribI1uiv(GLuint, *const GLuint);
+    glVertexAttribI2i(GLuint, GLint, GLint);
+    glVertexAttribI2iv(GLuint, *const [GLint; 2]);
+    glVertexAttribI2ui(GLuint, GLuint, GLuint);
+    glVertexAttribI2uiv(GLuint, *const [GLuint; 2]);
+    glVertexAttribI3i(GLuint, GLint, GLint, GLint);
+    glVertexAttribI3iv(GLuint, *const [GLint; 3]);
+    glVertexAttribI3ui(GLuint, GLuint, GLuint, GLuint);
+    glVertexAttribI3uiv(GLuint, *const [GLuint; 3]);
+    glVertexAttribI4bv(GLuint, *const [GLbyte; 4]);
+    glVertexAttribI4i(GLuint, GLint, GLint, GLint, GLint);
+    glVertexAttribI4iv(GLuint, *const [GLint; 4]);
+    glVertexAttribI4sv(GLuint, *const [GLshort; 4]);
+    glVertexAttribI4ubv(GLuint, *const [GLubyte; 4]);
+    glVertexAttribI4ui(GLuint, GLuint, GLuint, GLuint, GLuint);
+    glVertexAttribI4uiv(GLuint, *const [GLuint; 4]);
+    glVertexAttribI4usv(GLuint, *const [GLushort; 4]);
+    glVertexAttribIFormat(GLuint, GLint, VertexAttribIType, GLuint);
+    glVertexAttribIPointer(GLuint, GLint, VertexAttribIType, GLsizei, *const c_void);
+    glVertexAttribL1d(GLuint, GLdouble);
+    glVertexAttribL1dv(GLuint, *const GLdouble);
+    glVertexAttribL1ui64ARB(GLuint, GLuint64EXT);
+    glVertexAttribL1ui64vARB(GLuint, *const GLuint64EXT);
+    glVertexAttribL2d(GLuint, GLdouble, GLdouble);
+    glVertexAttribL2dv(GLuint, *const [GLdouble; 2]);
+    glVertexAttribL3d(GLuint, GLdouble, GLdouble, GLdouble);
+    glVertexAttribL3dv(GLuint, *const [GLdouble; 3]);
+    glVertexAttribL4d(GLuint, GLdouble, GLdouble, GLdouble, GLdouble);
+    glVertexAttribL4dv(GLuint, *const [GLdouble; 4]);
+    glVertexAttribLFormat(GLuint, GLint, VertexAttribLType, GLuint);
+    glVertexAttribLPointer(GLuint, GLint, VertexAttribLType, GLsizei, *const c_void);
+    glVertexAttribP1ui(GLuint, VertexAttribPointerType, GLboolean, GLuint);
+    glVertexAttribP1uiv(GLuint, VertexAttribPointerType, GLboolean, *const GLuint);
+    glVertexAttribP2ui(GLuint, VertexAttribPointerType, GLboolean, GLuint);
+    glVertexAttribP2uiv(GLuint, VertexAttribPointerType, GLboolean, *const GLuint);
+    glVertexAttribP3ui(GLuint, VertexAttribPointerType, GLboolean, GLuint);
+    glVertexAttribP3uiv(GLuint, VertexAttribPointerType, GLboolean, *const GLuint);
+    glVertexAttribP4ui(GLuint, VertexAttribPointerType, GLboolean, GLuint);
+    glVertexAttribP4uiv(GLuint, VertexAttribPointerType, GLboolean, *const GLuint);
+    glVertexAttribPointer(GLuint, GLint, VertexAttribPointerType, GLboolean, GLsizei, *const c_void);
+    glVertexBindingDivisor(GLuint, GLuint);
+    glViewport(GLint, GLint, GLsizei, GLsizei);
+    glViewportArrayv(GLuint, GLsizei, *const GLfloat);
+    glViewportIndexedf(GLuint, GLfloat, GLfloat, GLfloat, GLfloat);
+    glViewportIndexedfv(GLuint, *const [GLfloat; 4]);
+    glWaitSync(GLsync, GLbitfield, GLuint64);
+}
@@ -0,0 +1,202 @@
+use super::{gl, ShaderProgram};
+
+/// Program interfaces that can be enumerated after linking.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgramInterface {
+    Uniform = gl46::GL_UNIFORM.0,
+    UniformBlock = gl46::GL_UNIFORM_BLOCK.0,
+    ShaderStorageBlock = gl46::GL_SHADER_STORAGE_BLOCK.0,
+    ProgramInput = gl46::GL_PROGRAM_INPUT.0,
+    ProgramOutput = gl46::GL_PROGRAM_OUTPUT.0,
+}
+
+impl ProgramInterface {
+    /// The properties [`ShaderProgram::resources`] queries for this interface.
+    fn properties(self) -> &'static [ResourceProperty] {
+        match self {
+            ProgramInterface::Uniform => &[
+                ResourceProperty::Type,
+                ResourceProperty::ArraySize,
+                ResourceProperty::Location,
+                ResourceProperty::BlockIndex,
+            ],
+            ProgramInterface::UniformBlock | ProgramInterface::ShaderStorageBlock => &[
+                ResourceProperty::BufferBinding,
+                ResourceProperty::BufferDataSize,
+            ],
+            ProgramInterface::ProgramInput | ProgramInterface::ProgramOutput => &[
+                ResourceProperty::Type,
+                ResourceProperty::ArraySize,
+                ResourceProperty::Location,
+            ],
+        }
+    }
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceProperty {
+    Type = gl46::GL_TYPE.0,
+    ArraySize = gl46::GL_ARRAY_SIZE.0,
+    Location = gl46::GL_LOCATION.0,
+    BlockIndex = gl46::GL_BLOCK_INDEX.0,
+    Offset = gl46::GL_OFFSET.0,
+    BufferBinding = gl46::GL_BUFFER_BINDING.0,
+    BufferDataSize = gl46::GL_BUFFER_DATA_SIZE.0,
+}
+
+/// An active resource of a linked program. Properties that don't apply to the
+/// interface it was queried from are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramResource {
+    pub index: u32,
+    pub name: String,
+    /// A `GL_FLOAT_VEC3`-style type enum.
+    pub r#type: Option<u32>,
+    pub array_size: Option<i32>,
+    /// `-1` for built-ins and uniforms inside blocks.
+    pub location: Option<i32>,
+    /// `-1` for uniforms outside of blocks.
+    pub block_index: Option<i32>,
+    pub buffer_binding: Option<i32>,
+    pub buffer_data_size: Option<i32>,
+}
+
+/// Component type of a scalar or vector resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarType {
+    Bool,
+    Int,
+    UnsignedInt,
+    Float,
+    Double,
+}
+
+impl ProgramResource {
+    /// The component type and count of a scalar or vector resource, `None` for
+    /// matrices, samplers and blocks.
+    pub fn vector_type(&self) -> Option<(ScalarType, u32)> {
+        use ScalarType::*;
+        let vector = match gl46::GLenum(self.r#type?) {
+            gl46::GL_BOOL => (Bool, 1),
+            gl46::GL_BOOL_VEC2 => (Bool, 2),
+            gl46::GL_BOOL_VEC3 => (Bool, 3),
+            gl46::GL_BOOL_VEC4 => (Bool, 4),
+            gl46::GL_INT => (Int, 1),
+            gl46::GL_INT_VEC2 => (Int, 2),
+            gl46::GL_INT_VEC3 => (Int, 3),
+            gl46::GL_INT_VEC4 => (Int, 4),
+            gl46::GL_UNSIGNED_INT => (UnsignedInt, 1),
+            gl46::GL_UNSIGNED_INT_VEC2 => (UnsignedInt, 2),
+            gl46::GL_UNSIGNED_INT_VEC3 => (UnsignedInt, 3),
+            gl46::GL_UNSIGNED_INT_VEC4 => (UnsignedInt, 4),
+            gl46::GL_FLOAT => (Float, 1),
+            gl46::GL_FLOAT_VEC2 => (Float, 2),
+            gl46::GL_FLOAT_VEC3 => (Float, 3),
+            gl46::GL_FLOAT_VEC4 => (Float, 4),
+            gl46::GL_DOUBLE => (Double, 1),
+            gl46::GL_DOUBLE_VEC2 => (Double, 2),
+            gl46::GL_DOUBLE_VEC3 => (Double, 3),
+            gl46::GL_DOUBLE_VEC4 => (Double, 4),
+            _ => return None,
+        };
+        Some(vector)
+    }
+}
+
+impl ShaderProgram {
+    /// Number of active resources in `interface`.
+    #[inline]
+    pub fn active_resources(&self, interface: ProgramInterface) -> u32 {
+        self.get_interface_iv(interface, gl46::GL_ACTIVE_RESOURCES.0) as u32
+    }
+
+    #[inline]
+    pub fn get_interface_iv(&self, interface: ProgramInterface, pname: u32) -> i32 {
+        let mut value = 0;
+        unsafe {
+            gl().GetProgramInterfaceiv(
+                self.id(),
+                gl46::GLenum(interface as u32),
+                gl46::GLenum(pname),
+                &mut value,
+            );
+        }
+        value
+    }
+
+    /// Returns one value per entry of `properties`, in order.
+    pub fn get_resource_iv(
+        &self,
+        interface: ProgramInterface,
+        index: u32,
+        properties: &[ResourceProperty],
+    ) -> Vec<i32> {
+        let props: Vec<gl46::GLenum> = properties
+            .iter()
+            .map(|property| gl46::GLenum(*property as u32))
+            .collect();
+        let mut values = vec![0; props.len()];
+        let mut len = 0;
+        unsafe {
+            gl().GetProgramResourceiv(
+                self.id(),
+                gl46::GLenum(interface as u32),
+                index,
+                props.len() as i32,
+                props.as_ptr(),
+                values.len() as i32,
+                &mut len,
+                values.as_mut_ptr(),
+            );
+        }
+        values.truncate(len.max(0) as usize);
+        values
+    }
+
+    pub fn get_resource_name(&self, interface: ProgramInterface, index: u32) -> String {
+        let max_len = self.get_interface_iv(interface, gl46::GL_MAX_NAME_LENGTH.0);
+        let mut buffer = vec![0u8; max_len.max(1) as usize];
+        let mut len = 0;
+        unsafe {
+            gl().GetProgramResourceName(
+                self.id(),
+                gl46::GLenum(interface as u32),
+                index,
+                buffer.len() as i32,
+                &mut len,
+                buffer.as_mut_ptr(),
+            );
+        }
+        buffer.truncate(len.max(0) as usize);
+        String::from_utf8_lossy(&buffer).into_owned()
+    }
+
+    /// Every active resource of `interface` with the properties that apply to it.
+    pub fn resources(&self, interface: ProgramInterface) -> Vec<ProgramResource> {
+        let properties = interface.properties();
+        (0..self.active_resources(interface))
+            .map(|index| {
+                let values = self.get_resource_iv(interface, index, properties);
+                let get = |property| {
+                    properties
+                        .iter()
+                        .position(|p| *p == property)
+                        .and_then(|i| values.get(i).copied())
+                };
+
+                ProgramResource {
+                    index,
+                    name: self.get_resource_name(interface, index),
+                    r#type: get(ResourceProperty::Type).map(|value| value as u32),
+                    array_size: get(ResourceProperty::ArraySize),
+                    location: get(ResourceProperty::Location),
+                    block_index: get(ResourceProperty::BlockIndex),
+                    buffer_binding: get(ResourceProperty::BufferBinding),
+                    buffer_data_size: get(ResourceProperty::BufferDataSize),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,155 @@
+use std::ops::{BitOr, BitOrAssign};
+
+use super::{gl, tracking, ObjectKind, ProgramError, ShaderProgram, ShaderType};
+
+/// Bitmask of the stages a separable program provides to a pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProgramStages(pub u32);
+impl ProgramStages {
+    pub const VERTEX: Self = Self(gl46::GL_VERTEX_SHADER_BIT.0);
+    pub const TESS_CONTROL: Self = Self(gl46::GL_TESS_CONTROL_SHADER_BIT.0);
+    pub const TESS_EVALUATION: Self = Self(gl46::GL_TESS_EVALUATION_SHADER_BIT.0);
+    pub const GEOMETRY: Self = Self(gl46::GL_GEOMETRY_SHADER_BIT.0);
+    pub const FRAGMENT: Self = Self(gl46::GL_FRAGMENT_SHADER_BIT.0);
+    pub const COMPUTE: Self = Self(gl46::GL_COMPUTE_SHADER_BIT.0);
+    pub const ALL: Self = Self(gl46::GL_ALL_SHADER_BITS.0);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ProgramStages {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ProgramStages {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl From<ShaderType> for ProgramStages {
+    fn from(shader_type: ShaderType) -> Self {
+        match shader_type {
+            ShaderType::Compute => Self::COMPUTE,
+            ShaderType::Vertex => Self::VERTEX,
+            ShaderType::TessControl => Self::TESS_CONTROL,
+            ShaderType::TessEvaluation => Self::TESS_EVALUATION,
+            ShaderType::Geometry => Self::GEOMETRY,
+            ShaderType::Fragment => Self::FRAGMENT,
+        }
+    }
+}
+
+/// Combines stages from separable [`ShaderProgram`]s without relinking them.
+///
+/// The game doesn't use pipelines yet: its programs are built from one vertex
+/// and one fragment shader, and each combination is linked and cached as a
+/// whole. Pipelines are for swapping single stages, for example one vertex
+/// shader shared by many fragment shaders.
+pub struct ProgramPipeline(u32);
+
+impl ProgramPipeline {
+    #[inline]
+    pub fn create1() -> Self {
+        let mut pipeline = 0;
+        unsafe {
+            gl().CreateProgramPipelines(1, &mut pipeline);
+        }
+        tracking::created(ObjectKind::ProgramPipeline, pipeline);
+        ProgramPipeline(pipeline)
+    }
+
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::ProgramPipeline, self.id(), label);
+    }
+
+    /// Uses `stages` of `program`, which must have been linked as separable.
+    #[inline]
+    pub fn use_program_stages(&mut self, stages: ProgramStages, program: &ShaderProgram) {
+        unsafe {
+            gl().UseProgramStages(self.id(), gl46::GLbitfield(stages.0), program.id());
+        }
+    }
+
+    /// Clears `stages`, leaving them without a program.
+    #[inline]
+    pub fn clear_program_stages(&mut self, stages: ProgramStages) {
+        unsafe {
+            gl().UseProgramStages(self.id(), gl46::GLbitfield(stages.0), 0);
+        }
+    }
+
+    /// Makes `program` the target of the non-DSA `glUniform*` calls.
+    #[inline]
+    pub fn active_shader_program(&mut self, program: &ShaderProgram) {
+        unsafe {
+            gl().ActiveShaderProgram(self.id(), program.id());
+        }
+    }
+
+    /// Only takes effect while no program is bound with [`ShaderProgram::use`].
+    #[inline]
+    pub fn bind(&self) {
+        unsafe {
+            gl().BindProgramPipeline(self.id());
+        }
+    }
+
+    /// Checks that the stages can execute together in the current GL state.
+    pub fn validate(&self) -> Result<(), ProgramError> {
+        unsafe {
+            gl().ValidateProgramPipeline(self.id());
+        }
+
+        if self.get_iv(gl46::GL_VALIDATE_STATUS.0) == 0 {
+            return Err(ProgramError::Validation(self.get_info_log()));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_iv(&self, pname: u32) -> i32 {
+        let mut value = 0;
+        unsafe {
+            gl().GetProgramPipelineiv(self.id(), gl46::GLenum(pname), &mut value);
+        }
+        value
+    }
+
+    #[inline]
+    pub fn get_info_log(&self) -> String {
+        const BUFFER_SIZE: usize = 1024;
+        let gl = gl();
+        let mut len = 0;
+        let mut buffer = [0u8; BUFFER_SIZE];
+        unsafe {
+            gl.GetProgramPipelineInfoLog(
+                self.id(),
+                BUFFER_SIZE as i32,
+                &mut len,
+                &mut buffer as *mut _,
+            )
+        };
+        String::from_utf8_lossy(&buffer[..len as usize]).to_string()
+    }
+
+    #[inline]
+    pub fn delete(self) {}
+}
+
+impl Drop for ProgramPipeline {
+    fn drop(&mut self) {
+        tracking::dropped(ObjectKind::ProgramPipeline, self.id());
+    }
+}
@@ -1,13 +1,32 @@
-use std::ffi::CString;
+use std::{error::Error, ffi::CString, fmt::Display};
 
-use gl46::{GLenum, GL_SHADER_BINARY_FORMAT_SPIR_V, GL_SPIR_V_BINARY};
+use gl46::{GLenum, GL_SHADER_BINARY_FORMAT_SPIR_V};
 
-use super::gl;
+use super::{gl, tracking, ObjectKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderError {
+    Compile(String),
+    Specialization(String),
+}
+
+impl Display for ShaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderError::Compile(log) => write!(f, "Shader compilation failed: {}", log),
+            ShaderError::Specialization(log) => {
+                write!(f, "Shader specialization failed: {}", log)
+            }
+        }
+    }
+}
+
+impl Error for ShaderError {}
 
 pub struct Shader(u32);
 
 #[repr(u32)]
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ShaderType {
     Compute = gl46::GL_COMPUTE_SHADER.0,
     Vertex = gl46::GL_VERTEX_SHADER.0,
@@ -21,6 +40,7 @@ impl Shader {
     #[inline]
     pub fn create(shader_type: ShaderType) -> Self {
         let shader_id = gl().CreateShader(GLenum(shader_type as _));
+        tracking::created(ObjectKind::Shader, shader_id);
         Self(shader_id)
     }
 
@@ -29,16 +49,20 @@ impl Shader {
         self.0
     }
 
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::Shader, self.id(), label);
+    }
+
     pub fn source(&mut self, strings: &[&str]) {
-        let lengths: Vec<_> = strings.iter().map(|s| s.len()).collect();
-        let c_strings: Vec<_> = strings.iter().map(|s| s.as_ptr() as *const u8).collect();
+        let lengths: Vec<i32> = strings.iter().map(|s| s.len() as i32).collect();
+        let c_strings: Vec<_> = strings.iter().map(|s| s.as_ptr()).collect();
 
         unsafe {
             gl().ShaderSource(
                 self.id(),
                 c_strings.len() as i32,
                 c_strings.as_ptr(),
-                lengths.as_ptr() as *const i32,
+                lengths.as_ptr(),
             )
         };
     }
@@ -57,17 +81,21 @@ impl Shader {
         };
     }
 
-    pub fn compile(&mut self) {
+    pub fn compile(&mut self) -> Result<(), ShaderError> {
         let gl = gl();
         gl.CompileShader(self.id());
 
         if self.get_iv(gl46::GL_COMPILE_STATUS.0) == 0 {
-            let log = self.get_info_log();
-            panic!("Shader compilation failed: {}", log);
+            return Err(ShaderError::Compile(self.get_info_log()));
         }
+        Ok(())
     }
 
-    pub fn specialize(&mut self, entry_point: &str, specialization_constants: &[(u32, u32)]) {
+    pub fn specialize(
+        &mut self,
+        entry_point: &str,
+        specialization_constants: &[(u32, u32)],
+    ) -> Result<(), ShaderError> {
         let gl = gl();
 
         let c_entry = CString::new(entry_point)
@@ -94,10 +122,10 @@ impl Shader {
             );
         };
 
-        if self.get_iv(GL_SPIR_V_BINARY.0) == 0 {
-            let log = self.get_info_log();
-            panic!("Shader specialization failed: {}", log);
+        if self.get_iv(gl46::GL_COMPILE_STATUS.0) == 0 {
+            return Err(ShaderError::Specialization(self.get_info_log()));
         }
+        Ok(())
     }
 
     #[inline]
@@ -131,6 +159,6 @@ impl Shader {
 
 impl Drop for Shader {
     fn drop(&mut self) {
-        gl().DeleteShader(self.id());
+        tracking::dropped(ObjectKind::Shader, self.id());
     }
 }
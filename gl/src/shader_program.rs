@@ -1,8 +1,39 @@
-use std::ffi::CString;
+use std::{error::Error, ffi::CString, fmt::Display};
 
 use nalgebra_glm::Mat4;
 
-use super::gl;
+use super::{gl, tracking, ObjectKind};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgramError {
+    Link(String),
+    Validation(String),
+    /// The driver refused a binary from [`ShaderProgram::get_program_binary`],
+    /// usually because it was produced by another driver version.
+    BinaryRejected(String),
+}
+
+impl Display for ProgramError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProgramError::Link(log) => write!(f, "Shader Program linking failed: {}", log),
+            ProgramError::Validation(log) => write!(f, "Shader Program validation failed: {}", log),
+            ProgramError::BinaryRejected(log) => {
+                write!(f, "Shader Program binary was rejected: {}", log)
+            }
+        }
+    }
+}
+
+impl Error for ProgramError {}
+
+/// A linked program in the driver's own format, only valid for the driver that
+/// produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgramBinary {
+    pub format: u32,
+    pub data: Vec<u8>,
+}
 
 pub struct ShaderProgram(u32);
 
@@ -15,22 +46,96 @@ impl ShaderProgram {
     #[inline]
     pub fn create() -> Self {
         let program_id = gl().CreateProgram();
+        tracking::created(ObjectKind::ShaderProgram, program_id);
         ShaderProgram(program_id)
     }
 
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::ShaderProgram, self.id(), label);
+    }
+
+    /// The shader can be dropped once the program is linked.
     #[inline]
-    pub fn attach_shader(&mut self, shader: super::Shader) {
+    pub fn attach_shader(&mut self, shader: &super::Shader) {
         gl().AttachShader(self.id(), shader.id());
     }
 
-    pub fn link(&mut self) {
+    #[inline]
+    pub fn detach_shader(&mut self, shader: &super::Shader) {
+        unsafe {
+            gl().DetachShader(self.id(), shader.id());
+        }
+    }
+
+    /// Marks the program as usable in a [`crate::ProgramPipeline`]. Must be
+    /// set before linking.
+    #[inline]
+    pub fn set_separable(&mut self, separable: bool) {
+        unsafe {
+            gl().ProgramParameteri(self.id(), gl46::GL_PROGRAM_SEPARABLE, separable as i32);
+        }
+    }
+
+    /// Asks the driver to keep the binary around after linking. Must be set
+    /// before linking.
+    #[inline]
+    pub fn set_binary_retrievable(&mut self, retrievable: bool) {
+        unsafe {
+            gl().ProgramParameteri(
+                self.id(),
+                gl46::GL_PROGRAM_BINARY_RETRIEVABLE_HINT,
+                retrievable as i32,
+            );
+        }
+    }
+
+    pub fn get_program_binary(&self) -> ProgramBinary {
+        let length = self.get_iv(gl46::GL_PROGRAM_BINARY_LENGTH.0);
+        let mut data = vec![0u8; length.max(0) as usize];
+        let mut written = 0;
+        let mut format = gl46::GLenum(0);
+        unsafe {
+            gl().GetProgramBinary(
+                self.id(),
+                length,
+                &mut written,
+                &mut format,
+                data.as_mut_ptr().cast(),
+            );
+        }
+        data.truncate(written.max(0) as usize);
+
+        ProgramBinary {
+            format: format.0,
+            data,
+        }
+    }
+
+    /// Loads a previously retrieved binary in place of compiling and linking.
+    pub fn program_binary(&mut self, binary: &ProgramBinary) -> Result<(), ProgramError> {
+        unsafe {
+            gl().ProgramBinary(
+                self.id(),
+                gl46::GLenum(binary.format),
+                binary.data.as_ptr().cast(),
+                binary.data.len() as i32,
+            );
+        }
+
+        if self.get_iv(gl46::GL_LINK_STATUS.0) == 0 {
+            return Err(ProgramError::BinaryRejected(self.get_info_log()));
+        }
+        Ok(())
+    }
+
+    pub fn link(&mut self) -> Result<(), ProgramError> {
         let gl = gl();
         gl.LinkProgram(self.id());
 
         if self.get_iv(gl46::GL_LINK_STATUS.0) == 0 {
-            let log = self.get_info_log();
-            panic!("Shader Program linking failed: {}", log);
+            return Err(ProgramError::Link(self.get_info_log()));
         }
+        Ok(())
     }
 
     pub fn r#use(&self) {
@@ -51,6 +156,15 @@ impl ShaderProgram {
         }
     }
 
+    /// Sets a uniform without binding the program, as needed for pipelines.
+    #[inline]
+    pub fn program_uniform_mat_4(&self, location: i32, value: Mat4) {
+        let ptr = &value as *const Mat4 as *const f32;
+        unsafe {
+            gl().ProgramUniformMatrix4fv(self.id(), location, 1, 0, ptr);
+        }
+    }
+
     #[inline]
     pub fn get_iv(&self, pname: u32) -> i32 {
         let mut value = 0;
@@ -83,6 +197,6 @@ impl ShaderProgram {
 
 impl Drop for ShaderProgram {
     fn drop(&mut self) {
-        gl().DeleteProgram(self.id());
+        tracking::dropped(ObjectKind::ShaderProgram, self.id());
     }
 }
@@ -1,6 +1,6 @@
 use gl46::GLenum;
 
-use super::gl;
+use super::{gl, tracking, ObjectKind};
 
 pub struct Texture(u32);
 
@@ -20,18 +20,27 @@ impl From<TextureTarget> for GLenum {
 }
 
 impl Texture {
+    fn tracked(id: u32) -> Self {
+        tracking::created(ObjectKind::Texture, id);
+        Texture(id)
+    }
+
     #[inline]
     pub fn id(&self) -> u32 {
         self.0
     }
 
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::Texture, self.id(), label);
+    }
+
     #[inline]
     pub fn r#gen(count: isize) -> Vec<Self> {
         let mut textures = vec![0; count as usize];
         unsafe {
             gl().GenTextures(count as _, textures.as_mut_ptr());
         }
-        textures.into_iter().map(Texture).collect()
+        textures.into_iter().map(Texture::tracked).collect()
     }
 
     #[inline]
@@ -40,7 +49,7 @@ impl Texture {
         unsafe {
             gl().CreateTextures(target.into(), count as _, textures.as_mut_ptr());
         }
-        textures.into_iter().map(Texture).collect()
+        textures.into_iter().map(Texture::tracked).collect()
     }
 
     #[inline]
@@ -49,7 +58,7 @@ impl Texture {
         unsafe {
             gl().CreateTextures(target.into(), 1, &mut texture);
         }
-        Texture(texture)
+        Texture::tracked(texture)
     }
 
     #[inline]
@@ -58,7 +67,7 @@ impl Texture {
         unsafe {
             gl().GenTextures(1, &mut texture);
         }
-        Texture(texture)
+        Texture::tracked(texture)
     }
 
     #[inline]
@@ -185,9 +194,7 @@ impl Texture {
 impl Drop for Texture {
     #[inline]
     fn drop(&mut self) {
-        unsafe {
-            gl().DeleteTextures(1, &self.0);
-        }
+        tracking::dropped(ObjectKind::Texture, self.0);
     }
 }
 
@@ -0,0 +1,265 @@
+use std::{
+    backtrace::Backtrace,
+    collections::BTreeMap,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread::{self, ThreadId},
+};
+
+use super::gl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ObjectKind {
+    Buffer,
+    VertexArray,
+    Texture,
+    Shader,
+    ShaderProgram,
+    ProgramPipeline,
+}
+
+impl ObjectKind {
+    fn identifier(self) -> gl46::GLenum {
+        match self {
+            ObjectKind::Buffer => gl46::GL_BUFFER,
+            ObjectKind::VertexArray => gl46::GL_VERTEX_ARRAY,
+            ObjectKind::Texture => gl46::GL_TEXTURE,
+            ObjectKind::Shader => gl46::GL_SHADER,
+            ObjectKind::ShaderProgram => gl46::GL_PROGRAM,
+            ObjectKind::ProgramPipeline => gl46::GL_PROGRAM_PIPELINE,
+        }
+    }
+
+    fn delete(self, id: u32) {
+        let gl = gl();
+        unsafe {
+            match self {
+                ObjectKind::Buffer => gl.DeleteBuffers(1, &id),
+                ObjectKind::VertexArray => gl.DeleteVertexArrays(1, &id),
+                ObjectKind::Texture => gl.DeleteTextures(1, &id),
+                ObjectKind::Shader => gl.DeleteShader(id),
+                ObjectKind::ShaderProgram => gl.DeleteProgram(id),
+                ObjectKind::ProgramPipeline => gl.DeleteProgramPipelines(1, &id),
+            }
+        }
+    }
+}
+
+impl Display for ObjectKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ObjectKind::Buffer => "Buffer",
+            ObjectKind::VertexArray => "VertexArray",
+            ObjectKind::Texture => "Texture",
+            ObjectKind::Shader => "Shader",
+            ObjectKind::ShaderProgram => "ShaderProgram",
+            ObjectKind::ProgramPipeline => "ProgramPipeline",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A tracked object that was still alive when it was looked up.
+#[derive(Debug)]
+pub struct LiveObject {
+    pub kind: ObjectKind,
+    pub id: u32,
+    pub label: Option<String>,
+    pub backtrace: String,
+}
+
+struct Entry {
+    label: Option<String>,
+    backtrace: Backtrace,
+}
+
+struct ContextState {
+    /// Thread the context is current on. `None` lets any thread delete objects.
+    thread: Option<ThreadId>,
+}
+
+static CONTEXT_ALIVE: AtomicBool = AtomicBool::new(false);
+static CONTEXT: Mutex<ContextState> = Mutex::new(ContextState { thread: None });
+static DEFERRED: Mutex<Vec<(ObjectKind, u32)>> = Mutex::new(Vec::new());
+
+type Registry = BTreeMap<(ObjectKind, u32), Entry>;
+
+#[cfg(not(feature = "mock"))]
+static REGISTRY: Mutex<Registry> = Mutex::new(BTreeMap::new());
+
+#[cfg(not(feature = "mock"))]
+fn with_registry<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    f(&mut REGISTRY.lock().unwrap())
+}
+
+// The mock hands out object names per thread, so its objects are tracked per
+// thread too. Otherwise parallel tests would overwrite each other's entries.
+#[cfg(feature = "mock")]
+thread_local! {
+    static REGISTRY: std::cell::RefCell<Registry> = const { std::cell::RefCell::new(BTreeMap::new()) };
+}
+
+#[cfg(feature = "mock")]
+fn with_registry<R>(f: impl FnOnce(&mut Registry) -> R) -> R {
+    REGISTRY.with(|registry| f(&mut registry.borrow_mut()))
+}
+
+/// Forgets this thread's objects when the mock state is reset.
+#[cfg(feature = "mock")]
+pub(crate) fn reset_registry() {
+    with_registry(Registry::clear);
+}
+
+pub(crate) fn context_created(thread: Option<ThreadId>) {
+    CONTEXT.lock().unwrap().thread = thread;
+    CONTEXT_ALIVE.store(true, Ordering::Release);
+}
+
+fn on_context_thread() -> bool {
+    match CONTEXT.lock().unwrap().thread {
+        Some(thread) => thread == thread::current().id(),
+        None => true,
+    }
+}
+
+pub(crate) fn created(kind: ObjectKind, id: u32) {
+    if !cfg!(debug_assertions) || id == 0 {
+        return;
+    }
+
+    let entry = Entry {
+        label: None,
+        backtrace: Backtrace::capture(),
+    };
+    with_registry(|registry| registry.insert((kind, id), entry));
+}
+
+/// Called from the wrappers' `Drop` impls instead of deleting directly.
+///
+/// Objects dropped on the context's thread are deleted right away. Objects
+/// dropped elsewhere are queued until [`flush_deferred_deletes`] runs on the
+/// context's thread, and objects that outlive the context are reported.
+pub(crate) fn dropped(kind: ObjectKind, id: u32) {
+    if id == 0 {
+        return;
+    }
+
+    if !CONTEXT_ALIVE.load(Ordering::Acquire) {
+        if cfg!(debug_assertions) {
+            let entry = with_registry(|registry| registry.remove(&(kind, id)));
+            let label = entry.and_then(|entry| entry.label);
+            eprintln!(
+                "{kind} {id} ({}) dropped after the OpenGL context was destroyed.",
+                label.as_deref().unwrap_or("unlabeled")
+            );
+        }
+        return;
+    }
+
+    if !on_context_thread() {
+        DEFERRED.lock().unwrap().push((kind, id));
+        return;
+    }
+
+    kind.delete(id);
+    if cfg!(debug_assertions) {
+        with_registry(|registry| registry.remove(&(kind, id)));
+    }
+}
+
+pub(crate) fn object_label(kind: ObjectKind, id: u32, label: &str) {
+    unsafe {
+        gl().ObjectLabel(kind.identifier(), id, label.len() as i32, label.as_ptr());
+    }
+
+    if cfg!(debug_assertions) {
+        with_registry(|registry| {
+            if let Some(entry) = registry.get_mut(&(kind, id)) {
+                entry.label = Some(label.to_string());
+            }
+        });
+    }
+}
+
+/// Deletes the objects that were dropped away from the context's thread.
+///
+/// Must be called on the thread the context is current on, typically once per
+/// frame.
+pub fn flush_deferred_deletes() {
+    if !CONTEXT_ALIVE.load(Ordering::Acquire) || !on_context_thread() {
+        return;
+    }
+
+    let deferred = std::mem::take(&mut *DEFERRED.lock().unwrap());
+    for (kind, id) in deferred {
+        kind.delete(id);
+        if cfg!(debug_assertions) {
+            with_registry(|registry| registry.remove(&(kind, id)));
+        }
+    }
+}
+
+/// Number of live objects per kind. Always empty in release builds.
+pub fn live_object_counts() -> BTreeMap<ObjectKind, usize> {
+    let mut counts = BTreeMap::new();
+    with_registry(|registry| {
+        for (kind, _) in registry.keys() {
+            *counts.entry(*kind).or_insert(0) += 1;
+        }
+    });
+    counts
+}
+
+/// Every live object with its label and creation backtrace. Always empty in
+/// release builds.
+pub fn live_objects() -> Vec<LiveObject> {
+    with_registry(|registry| {
+        registry
+            .iter()
+            .map(|((kind, id), entry)| LiveObject {
+                kind: *kind,
+                id: *id,
+                label: entry.label.clone(),
+                backtrace: entry.backtrace.to_string(),
+            })
+            .collect()
+    })
+}
+
+/// Must be called while the context is still current, right before it is
+/// destroyed. Flushes deferred deletions and, in debug builds, prints every
+/// object that is still alive. Objects dropped afterwards are reported instead
+/// of being deleted.
+pub fn context_destroyed() {
+    flush_deferred_deletes();
+    CONTEXT_ALIVE.store(false, Ordering::Release);
+
+    if !cfg!(debug_assertions) {
+        return;
+    }
+
+    let leaks = live_objects();
+    if leaks.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "{} OpenGL object(s) alive at context destruction:",
+        leaks.len()
+    );
+    for (kind, count) in live_object_counts() {
+        eprintln!("  {kind}: {count}");
+    }
+    for leak in leaks {
+        eprintln!(
+            "  {} {} ({}) created at:\n{}",
+            leak.kind,
+            leak.id,
+            leak.label.as_deref().unwrap_or("unlabeled"),
+            leak.backtrace
+        );
+    }
+}
@@ -2,10 +2,15 @@ use std::ffi::c_void;
 
 use gl46::GLenum;
 
-use super::gl;
+use super::{gl, tracking, ObjectKind, VertexAttribFormat, VertexLayout};
 pub struct VertexArray(u32);
 
 impl VertexArray {
+    fn tracked(id: u32) -> Self {
+        tracking::created(ObjectKind::VertexArray, id);
+        VertexArray(id)
+    }
+
     #[inline]
     pub fn new() -> Self {
         Self::create1()
@@ -17,7 +22,7 @@ impl VertexArray {
         unsafe {
             gl().GenVertexArrays(n as _, arrays.as_mut_ptr());
         }
-        arrays.into_iter().map(VertexArray).collect()
+        arrays.into_iter().map(VertexArray::tracked).collect()
     }
 
     #[inline]
@@ -26,7 +31,7 @@ impl VertexArray {
         unsafe {
             gl().GenVertexArrays(1, &mut array);
         }
-        VertexArray(array)
+        VertexArray::tracked(array)
     }
 
     #[inline]
@@ -35,7 +40,7 @@ impl VertexArray {
         unsafe {
             gl().CreateVertexArrays(n as _, arrays.as_mut_ptr());
         }
-        arrays.into_iter().map(VertexArray).collect()
+        arrays.into_iter().map(VertexArray::tracked).collect()
     }
 
     #[inline]
@@ -44,7 +49,7 @@ impl VertexArray {
         unsafe {
             gl().CreateVertexArrays(1, &mut array);
         }
-        VertexArray(array)
+        VertexArray::tracked(array)
     }
 
     #[inline]
@@ -52,6 +57,10 @@ impl VertexArray {
         self.0
     }
 
+    pub fn label(&mut self, label: &str) {
+        tracking::object_label(ObjectKind::VertexArray, self.id(), label);
+    }
+
     #[inline]
     pub fn vertex_buffer(
         &mut self,
@@ -85,6 +94,7 @@ impl VertexArray {
 
     #[inline]
     pub fn enable_attrib(&mut self, index: u32) {
+        validate_location(index);
         unsafe {
             gl().EnableVertexArrayAttrib(self.id(), index);
         }
@@ -99,6 +109,8 @@ impl VertexArray {
         normalized: bool,
         relativeoffset: u32,
     ) {
+        validate_location(attrib_index);
+        validate_components(size, r#type);
         unsafe {
             gl().VertexArrayAttribFormat(
                 self.id(),
@@ -111,6 +123,69 @@ impl VertexArray {
         }
     }
 
+    /// Integer attribute that the shader reads as `int`/`uint` vectors.
+    #[inline]
+    pub fn attrib_i_format(
+        &mut self,
+        attrib_index: u32,
+        size: i32,
+        r#type: VertexAttribIType,
+        relativeoffset: u32,
+    ) {
+        validate_location(attrib_index);
+        validate_component_count(size);
+        unsafe {
+            gl().VertexArrayAttribIFormat(
+                self.id(),
+                attrib_index,
+                size,
+                GLenum(r#type as u32),
+                relativeoffset,
+            );
+        }
+    }
+
+    /// Double precision attribute that the shader reads as `double` vectors.
+    #[inline]
+    pub fn attrib_l_format(&mut self, attrib_index: u32, size: i32, relativeoffset: u32) {
+        validate_location(attrib_index);
+        validate_component_count(size);
+        unsafe {
+            gl().VertexArrayAttribLFormat(
+                self.id(),
+                attrib_index,
+                size,
+                gl46::GL_DOUBLE,
+                relativeoffset,
+            );
+        }
+    }
+
+    /// Enables and formats every attribute of `V` and sources them from
+    /// `binding`. The buffer itself is attached with [`Self::vertex_buffer`],
+    /// using `V::stride()`.
+    pub fn apply_layout<V: VertexLayout>(&mut self, binding: u32) {
+        for attribute in V::attributes() {
+            let location = attribute.location;
+            let offset = attribute.offset;
+            self.enable_attrib(location);
+            match attribute.format {
+                VertexAttribFormat::Float {
+                    components,
+                    r#type,
+                    normalized,
+                } => self.attrib_format(location, components, r#type, normalized, offset),
+                VertexAttribFormat::Integer { components, r#type } => {
+                    self.attrib_i_format(location, components, r#type, offset)
+                }
+                VertexAttribFormat::Double { components } => {
+                    self.attrib_l_format(location, components, offset)
+                }
+            }
+            self.attrib_binding(location, binding);
+        }
+    }
+
     #[inline]
     pub fn disable_attrib(&self, index: u32) {
         unsafe {
@@ -124,29 +199,67 @@ impl VertexArray {
 
 impl Drop for VertexArray {
     fn drop(&mut self) {
-        unsafe {
-            gl().DeleteVertexArrays(1, &self.0);
+        tracking::dropped(ObjectKind::VertexArray, self.0);
+    }
+}
+
+fn validate_location(location: u32) {
+    let max = crate::max_vertex_attribs();
+    if location >= max {
+        panic!("attribute location {location} exceeds GL_MAX_VERTEX_ATTRIBS ({max})");
+    }
+}
+
+fn validate_component_count(components: i32) {
+    if !(1..=4).contains(&components) {
+        panic!("components must be between 1 and 4");
+    }
+}
+
+fn validate_components(components: i32, r#type: VertexAttribPointerType) {
+    match r#type {
+        VertexAttribPointerType::Int2101010Rev | VertexAttribPointerType::UnsignedInt2101010Rev
+            if components != 4 =>
+        {
+            panic!("packed 2_10_10_10 attributes must have 4 components");
+        }
+        VertexAttribPointerType::UnsignedInt10F11F11FRev if components != 3 => {
+            panic!("packed 10F_11F_11F attributes must have 3 components");
         }
+        _ => validate_component_count(components),
     }
 }
 
 pub fn enable_vertex_attrib_array(index: u32) {
+    validate_location(index);
     unsafe {
         gl().EnableVertexAttribArray(index);
     }
 }
 
+pub fn disable_vertex_attrib_array(index: u32) {
+    validate_location(index);
+    unsafe {
+        gl().DisableVertexAttribArray(index);
+    }
+}
+
 #[repr(u32)]
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VertexAttribPointerType {
     Float = gl46::GL_FLOAT.0,
+    HalfFloat = gl46::GL_HALF_FLOAT.0,
     Double = gl46::GL_DOUBLE.0,
+    Fixed = gl46::GL_FIXED.0,
     Int = gl46::GL_INT.0,
     UnsignedInt = gl46::GL_UNSIGNED_INT.0,
     Short = gl46::GL_SHORT.0,
     UnsignedShort = gl46::GL_UNSIGNED_SHORT.0,
     Byte = gl46::GL_BYTE.0,
     UnsignedByte = gl46::GL_UNSIGNED_BYTE.0,
+    Int2101010Rev = gl46::GL_INT_2_10_10_10_REV.0,
+    UnsignedInt2101010Rev = gl46::GL_UNSIGNED_INT_2_10_10_10_REV.0,
+    UnsignedInt10F11F11FRev = gl46::GL_UNSIGNED_INT_10F_11F_11F_REV.0,
 }
 
 impl From<VertexAttribPointerType> for u32 {
@@ -155,6 +268,29 @@ impl From<VertexAttribPointerType> for u32 {
     }
 }
 
+/// Component types accepted by integer attributes.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexAttribIType {
+    Byte = gl46::GL_BYTE.0,
+    UnsignedByte = gl46::GL_UNSIGNED_BYTE.0,
+    Short = gl46::GL_SHORT.0,
+    UnsignedShort = gl46::GL_UNSIGNED_SHORT.0,
+    Int = gl46::GL_INT.0,
+    UnsignedInt = gl46::GL_UNSIGNED_INT.0,
+}
+
+impl From<VertexAttribIType> for u32 {
+    fn from(value: VertexAttribIType) -> Self {
+        value as u32
+    }
+}
+
+/// Sources `location` from the buffer bound to `GL_ARRAY_BUFFER`, converting
+/// the data to floats.
+///
+/// # Safety
+/// `offset` and `stride` must describe data inside the bound array buffer.
 pub unsafe fn vertex_attrib_pointer(
     location: u32,
     components: i32,
@@ -163,15 +299,61 @@ pub unsafe fn vertex_attrib_pointer(
     stride: usize,
     offset: usize,
 ) {
-    if components < 1 || components > 4 {
-        panic!("components must be between 1 and 4");
-    }
+    validate_location(location);
+    validate_components(components, r#type);
     unsafe {
         gl().VertexAttribPointer(
             location,
             components,
             gl46::GLenum(r#type.into()),
-            if normalized { 0 } else { 1 },
+            normalized as u8,
+            stride as i32,
+            offset as *const c_void,
+        );
+    }
+}
+
+/// Like [`vertex_attrib_pointer`], but the shader reads the data as integers.
+///
+/// # Safety
+/// `offset` and `stride` must describe data inside the bound array buffer.
+pub unsafe fn vertex_attrib_i_pointer(
+    location: u32,
+    components: i32,
+    r#type: VertexAttribIType,
+    stride: usize,
+    offset: usize,
+) {
+    validate_location(location);
+    validate_component_count(components);
+    unsafe {
+        gl().VertexAttribIPointer(
+            location,
+            components,
+            gl46::GLenum(r#type.into()),
+            stride as i32,
+            offset as *const c_void,
+        );
+    }
+}
+
+/// Like [`vertex_attrib_pointer`], but the shader reads the data as doubles.
+///
+/// # Safety
+/// `offset` and `stride` must describe data inside the bound array buffer.
+pub unsafe fn vertex_attrib_l_pointer(
+    location: u32,
+    components: i32,
+    stride: usize,
+    offset: usize,
+) {
+    validate_location(location);
+    validate_component_count(components);
+    unsafe {
+        gl().VertexAttribLPointer(
+            location,
+            components,
+            gl46::GL_DOUBLE,
             stride as i32,
             offset as *const c_void,
         );
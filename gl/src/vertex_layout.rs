@@ -0,0 +1,226 @@
+use crate::{VertexAttribIType, VertexAttribPointerType};
+
+/// How one attribute is laid out in the vertex buffer and read by the shader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexAttribFormat {
+    /// Read as `float` vectors, converting (and optionally normalizing) the data.
+    Float {
+        components: i32,
+        r#type: VertexAttribPointerType,
+        normalized: bool,
+    },
+    /// Read as `int`/`uint` vectors.
+    Integer {
+        components: i32,
+        r#type: VertexAttribIType,
+    },
+    /// Read as `double` vectors.
+    Double { components: i32 },
+}
+
+impl VertexAttribFormat {
+    pub const fn float(components: i32, r#type: VertexAttribPointerType) -> Self {
+        Self::Float {
+            components,
+            r#type,
+            normalized: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertexAttributeDescription {
+    pub location: u32,
+    pub format: VertexAttribFormat,
+    pub offset: u32,
+}
+
+/// Field types that can be fed to a vertex attribute.
+///
+/// Integer types without a wrapper are converted to floats as-is; wrap them in
+/// [`Normalized`] to map them to `[0, 1]` or `[-1, 1]`, or in [`Integer`] to
+/// keep them as integers. `f64` fields are read as doubles.
+pub trait VertexAttribute {
+    const FORMAT: VertexAttribFormat;
+}
+
+/// Marker for integer attribute types, which can be normalized or kept as
+/// integers.
+pub trait IntegerAttribute: VertexAttribute {
+    const I_TYPE: VertexAttribIType;
+}
+
+/// Marker for packed attribute types whose values can be normalized.
+pub trait Normalizable: VertexAttribute {}
+
+impl<T: IntegerAttribute> Normalizable for T {}
+
+/// An integer attribute the shader reads as a normalized float.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalized<T>(pub T);
+
+impl<T: Normalizable> VertexAttribute for Normalized<T> {
+    const FORMAT: VertexAttribFormat = match T::FORMAT {
+        VertexAttribFormat::Float {
+            components, r#type, ..
+        } => VertexAttribFormat::Float {
+            components,
+            r#type,
+            normalized: true,
+        },
+        _ => panic!("Only float-converted attributes can be normalized."),
+    };
+}
+
+/// An integer attribute the shader reads as `int`/`uint` vectors.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Integer<T>(pub T);
+
+impl<T: IntegerAttribute> VertexAttribute for Integer<T> {
+    const FORMAT: VertexAttribFormat = match T::FORMAT {
+        VertexAttribFormat::Float { components, .. } => VertexAttribFormat::Integer {
+            components,
+            r#type: T::I_TYPE,
+        },
+        _ => panic!("Only float-converted attributes can be read as integers."),
+    };
+}
+
+/// Four signed components packed as 10, 10, 10 and 2 bits, `w` in the top bits.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Int2101010Rev(pub u32);
+
+/// Four unsigned components packed as 10, 10, 10 and 2 bits, `w` in the top bits.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnsignedInt2101010Rev(pub u32);
+
+/// Three unsigned floats packed as 11, 11 and 10 bits, `z` in the top bits.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnsignedInt10F11F11FRev(pub u32);
+
+impl VertexAttribute for Int2101010Rev {
+    const FORMAT: VertexAttribFormat =
+        VertexAttribFormat::float(4, VertexAttribPointerType::Int2101010Rev);
+}
+
+impl VertexAttribute for UnsignedInt2101010Rev {
+    const FORMAT: VertexAttribFormat =
+        VertexAttribFormat::float(4, VertexAttribPointerType::UnsignedInt2101010Rev);
+}
+
+impl VertexAttribute for UnsignedInt10F11F11FRev {
+    const FORMAT: VertexAttribFormat =
+        VertexAttribFormat::float(3, VertexAttribPointerType::UnsignedInt10F11F11FRev);
+}
+
+impl Normalizable for Int2101010Rev {}
+impl Normalizable for UnsignedInt2101010Rev {}
+
+macro_rules! impl_vertex_attribute {
+    ($ty:ty => Integer($attrib_type:ident)) => {
+        impl_vertex_attribute!($ty => Float($attrib_type));
+
+        impl IntegerAttribute for $ty {
+            const I_TYPE: VertexAttribIType = VertexAttribIType::$attrib_type;
+        }
+
+        impl<const N: usize> IntegerAttribute for [$ty; N] {
+            const I_TYPE: VertexAttribIType = VertexAttribIType::$attrib_type;
+        }
+    };
+    ($ty:ty => $format:ident($attrib_type:ident)) => {
+        impl VertexAttribute for $ty {
+            const FORMAT: VertexAttribFormat = impl_vertex_attribute!(@format $format, 1, $attrib_type);
+        }
+
+        impl<const N: usize> VertexAttribute for [$ty; N] {
+            const FORMAT: VertexAttribFormat = {
+                assert!(N >= 1 && N <= 4, "Vertex attributes have 1 to 4 components.");
+                impl_vertex_attribute!(@format $format, N as i32, $attrib_type)
+            };
+        }
+    };
+    (@format Float, $components:expr, $attrib_type:ident) => {
+        VertexAttribFormat::float($components, VertexAttribPointerType::$attrib_type)
+    };
+    (@format Double, $components:expr, $attrib_type:ident) => {
+        VertexAttribFormat::Double {
+            components: $components,
+        }
+    };
+}
+
+impl_vertex_attribute!(f32 => Float(Float));
+impl_vertex_attribute!(f64 => Double(Double));
+impl_vertex_attribute!(i8 => Integer(Byte));
+impl_vertex_attribute!(u8 => Integer(UnsignedByte));
+impl_vertex_attribute!(i16 => Integer(Short));
+impl_vertex_attribute!(u16 => Integer(UnsignedShort));
+impl_vertex_attribute!(i32 => Integer(Int));
+impl_vertex_attribute!(u32 => Integer(UnsignedInt));
+
+/// A vertex type whose attributes can be applied to a [`crate::VertexArray`]
+/// binding. Implement it with [`vertex_layout!`](crate::vertex_layout).
+pub trait VertexLayout: Sized {
+    fn attributes() -> Vec<VertexAttributeDescription>;
+
+    fn stride() -> usize {
+        size_of::<Self>()
+    }
+}
+
+/// Declares a `#[repr(C)]` vertex struct and implements [`VertexLayout`] for
+/// it. Every field needs a `#[location(n)]` matching the shader input; the
+/// format is derived from the field's [`VertexAttribute`] type.
+///
+/// ```
+/// use gl::VertexLayout;
+///
+/// gl::vertex_layout! {
+///     #[derive(Clone, Copy)]
+///     pub struct Vertex {
+///         #[location(0)]
+///         pub position: [f32; 3],
+///         #[location(1)]
+///         pub color: gl::Normalized<[u8; 4]>,
+///     }
+/// }
+///
+/// assert_eq!(Vertex::stride(), 16);
+/// assert_eq!(Vertex::attributes()[1].offset, 12);
+/// ```
+#[macro_export]
+macro_rules! vertex_layout {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                #[location($location:literal)]
+                $field_vis:vis $field:ident : $field_ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(C)]
+        $vis struct $name {
+            $($field_vis $field: $field_ty),*
+        }
+
+        impl $crate::VertexLayout for $name {
+            fn attributes() -> ::std::vec::Vec<$crate::VertexAttributeDescription> {
+                ::std::vec![
+                    $($crate::VertexAttributeDescription {
+                        location: $location,
+                        format: <$field_ty as $crate::VertexAttribute>::FORMAT,
+                        offset: ::std::mem::offset_of!($name, $field) as u32,
+                    }),*
+                ]
+            }
+        }
+    };
+}
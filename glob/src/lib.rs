@@ -0,0 +1,115 @@
+//! Path globbing shared by the game's archive and the pack tool.
+//!
+//! Patterns and paths are split into `/`-separated segments by the caller,
+//! after normalizing them the same way. `*` and `?` match within a segment
+//! and a `**` segment matches any number of segments.
+
+/// Whether the segments of `path` match the segments of `pattern`.
+///
+/// Runs in time proportional to the product of the two lengths, however many
+/// `**` and `*` the pattern has.
+pub fn matches(pattern: &[&str], path: &[&str]) -> bool {
+    let path: Vec<Vec<char>> = path.iter().map(|name| name.chars().collect()).collect();
+
+    // `rest[j]` is whether the pattern segments after the current one match
+    // `path[j..]`, filled in from the last pattern segment backwards.
+    let mut rest = vec![false; path.len() + 1];
+    rest[path.len()] = true;
+    for segment in pattern.iter().rev() {
+        let mut current = vec![false; path.len() + 1];
+        if *segment == "**" {
+            // Either `**` matches nothing more, or it also takes `path[j]`.
+            current[path.len()] = rest[path.len()];
+            for j in (0..path.len()).rev() {
+                current[j] = rest[j] || current[j + 1];
+            }
+        } else {
+            let segment: Vec<char> = segment.chars().collect();
+            for (j, name) in path.iter().enumerate() {
+                current[j] = rest[j + 1] && matches_segment(&segment, name);
+            }
+        }
+        rest = current;
+    }
+    rest[0]
+}
+
+/// Matches `*` and `?` by backtracking to the last `*` only, which is enough
+/// because an earlier `*` can never need to take more characters.
+fn matches_segment(pattern: &[char], name: &[char]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    // The position after the last `*` and how much of `name` it has taken.
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after_star, taken)) => {
+                    star = Some((after_star, taken + 1));
+                    p = after_star;
+                    n = taken + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, path: &str) -> bool {
+        let pattern: Vec<&str> = pattern.split('/').collect();
+        matches(&pattern, &path.split('/').collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn wildcards_stay_within_a_segment() {
+        assert!(glob("*.png", "wall.png"));
+        assert!(!glob("*.png", "textures/wall.png"));
+        assert!(glob("textures/wall?.png", "textures/wall2.png"));
+        assert!(!glob("textures/wall?.png", "textures/wall.png"));
+        assert!(glob("*", "ünïcode"));
+        assert!(glob("?", "ü"));
+    }
+
+    #[test]
+    fn double_star_matches_any_number_of_segments() {
+        assert!(glob("**/*.png", "wall.png"));
+        assert!(glob("**/*.png", "textures/a/b/wall.png"));
+        assert!(glob("textures/**", "textures/a/b/wall.png"));
+        assert!(glob("a/**/b/*", "a/b/c"));
+        assert!(glob("a/**/b/*", "a/x/y/b/c"));
+        assert!(!glob("a/**/b/*", "a/x/y/c"));
+    }
+
+    #[test]
+    fn stars_and_question_marks_backtrack() {
+        assert!(glob("*a*b", "xaybab"));
+        assert!(glob("a*", "a"));
+        assert!(glob("*?", "x"));
+        assert!(!glob("*?", ""));
+        assert!(!glob("a*b", "acbx"));
+        assert!(glob("**", ""));
+        assert!(glob("**/**", "a"));
+    }
+
+    #[test]
+    fn many_wildcards_stay_fast() {
+        let name = "a".repeat(64);
+        assert!(!glob(&format!("{}b", "a*".repeat(32)), &name));
+
+        let path = vec!["a"; 64].join("/");
+        assert!(!glob(&format!("{}b", "**/".repeat(32)), &path));
+        assert!(glob(&format!("{}a", "**/".repeat(32)), &path));
+    }
+}
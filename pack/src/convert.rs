@@ -0,0 +1,91 @@
+//! Optional conversions applied while packing.
+
+use anyhow::{Context, Result, bail};
+use std::{
+    fs,
+    io::{Cursor, ErrorKind},
+    path::Path,
+    process::Command,
+};
+
+/// Texture formats re-encoded as PNG with `--convert-textures`.
+const TEXTURE_EXTENSIONS: [&str; 2] = ["tga", "bmp"];
+
+/// Decodes `bytes` and re-encodes them as PNG, if `name` is a texture format
+/// that gets converted. Returns the new name and contents.
+pub fn texture(name: &str, bytes: &[u8]) -> Result<Option<(String, Vec<u8>)>> {
+    let Some((stem, extension)) = name.rsplit_once('.') else {
+        return Ok(None);
+    };
+    if !TEXTURE_EXTENSIONS
+        .iter()
+        .any(|valid| extension.eq_ignore_ascii_case(valid))
+    {
+        return Ok(None);
+    }
+
+    let image =
+        image::load_from_memory(bytes).with_context(|| format!("Failed to decode {name}"))?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .with_context(|| format!("Failed to encode {name} as PNG"))?;
+    Ok(Some((format!("{stem}.png"), png)))
+}
+
+/// Compiles `source` to SPIR-V with the same compilers and flags as
+/// `glslc.sh` and `slangc.sh`, if it is a shader. The result is named like
+/// their output: `glsl_<stem>.spv` or `slang_<stem>.spv` next to the source.
+pub fn shader(source: &Path, name: &str) -> Result<Option<(String, Vec<u8>)>> {
+    let (directory, file_name) = match name.rsplit_once('/') {
+        Some((directory, file_name)) => (format!("{directory}/"), file_name),
+        None => (String::new(), name),
+    };
+    let Some((stem, extension)) = file_name.rsplit_once('.') else {
+        return Ok(None);
+    };
+
+    let (prefix, mut command) = match extension {
+        "vert" | "frag" => {
+            let mut command = Command::new("glslc");
+            command
+                .arg("--target-env=opengl4.5")
+                .arg(format!("-fshader-stage={extension}"))
+                .arg(source);
+            ("glsl", command)
+        }
+        "slang" => {
+            let mut command = Command::new("slangc");
+            command
+                .arg(source)
+                .args(["-target", "spirv"])
+                .args(["-profile", "spirv_1_4"])
+                .arg("-emit-spirv-directly")
+                .arg("-fvk-use-entrypoint-name")
+                .args(["-entry", "main"]);
+            ("slang", command)
+        }
+        _ => return Ok(None),
+    };
+
+    let output =
+        std::env::temp_dir().join(format!("pack-{}-{prefix}_{stem}.spv", std::process::id()));
+    command.arg("-o").arg(&output);
+    let result = match command.output() {
+        Ok(result) => result,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            bail!("{:?} is not on PATH", command.get_program())
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if !result.status.success() {
+        bail!(
+            "Failed to compile {name}:\n{}",
+            String::from_utf8_lossy(&result.stderr)
+        );
+    }
+
+    let spirv = fs::read(&output);
+    let _ = fs::remove_file(&output);
+    Ok(Some((format!("{directory}{prefix}_{stem}.spv"), spirv?)))
+}
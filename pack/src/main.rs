@@ -0,0 +1,185 @@
+//! Builds a `.pk3` from a content directory.
+//!
+//! ```text
+//! pack <content dir> <output.pk3> [--include <pattern>]... [--exclude <pattern>]...
+//!      [--rules <file>] [--convert-textures] [--compile-shaders]
+//! ```
+//!
+//! The archive is reproducible: entries are sorted by name and every entry has
+//! the same timestamp and permissions, so the same content always produces the
+//! same bytes, and the same checksum in the game's pack manifest. Formats that
+//! are already compressed are stored as is.
+
+use anyhow::{Context, Result, anyhow, bail};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use zip::{CompressionMethod, DateTime, ZipWriter, write::SimpleFileOptions};
+
+mod convert;
+mod rules;
+
+use rules::Rules;
+
+/// Stored without recompression.
+const STORED_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "ogg", "ktx2"];
+
+struct Options {
+    content: PathBuf,
+    output: PathBuf,
+    rules: Rules,
+    convert_textures: bool,
+    compile_shaders: bool,
+}
+
+fn main() -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+
+    let mut files = Vec::new();
+    collect_files(&options.content, "", &mut files)
+        .with_context(|| format!("Failed to read {}", options.content.display()))?;
+    files.sort();
+
+    // The output may be written inside the content directory.
+    let output = options.output.canonicalize().ok();
+    let mut entries = BTreeMap::new();
+    for (name, path) in files {
+        if !options.rules.matches(&name) || path.canonicalize().ok() == output {
+            continue;
+        }
+        let bytes =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+        if options.compile_shaders
+            && let Some((spirv_name, spirv)) = convert::shader(&path, &name)?
+        {
+            add_entry(&mut entries, spirv_name, spirv)?;
+        }
+        let converted = if options.convert_textures {
+            convert::texture(&name, &bytes)?
+        } else {
+            None
+        };
+        let (name, bytes) = converted.unwrap_or((name, bytes));
+        add_entry(&mut entries, name, bytes)?;
+    }
+    if entries.is_empty() {
+        bail!("No files in {} match the rules", options.content.display());
+    }
+
+    write_archive(&options.output, &entries)
+        .with_context(|| format!("Failed to write {}", options.output.display()))?;
+    println!(
+        "Packed {} files into {}",
+        entries.len(),
+        options.output.display()
+    );
+    Ok(())
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options> {
+    let mut positional = Vec::new();
+    let mut rules = Rules::default();
+    let mut convert_textures = false;
+    let mut compile_shaders = false;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow!("{arg} needs a value"));
+        match arg.as_str() {
+            "--include" => rules.include(&value()?),
+            "--exclude" => rules.exclude(&value()?),
+            "--rules" => {
+                let path = value()?;
+                let text =
+                    fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
+                rules
+                    .parse(&text)
+                    .with_context(|| format!("Invalid rules in {path}"))?;
+            }
+            "--convert-textures" => convert_textures = true,
+            "--compile-shaders" => compile_shaders = true,
+            _ if arg.starts_with("--") => bail!("Unknown argument {arg}"),
+            _ => positional.push(PathBuf::from(arg)),
+        }
+    }
+
+    let [content, output] = <[PathBuf; 2]>::try_from(positional).map_err(|_| {
+        anyhow!(
+            "Usage: pack <content dir> <output.pk3> [--include <pattern>]... \
+             [--exclude <pattern>]... [--rules <file>] [--convert-textures] [--compile-shaders]"
+        )
+    })?;
+    Ok(Options {
+        content,
+        output,
+        rules,
+        convert_textures,
+        compile_shaders,
+    })
+}
+
+/// Adds every file under `dir` as (`/`-separated name relative to the content
+/// directory, path).
+fn collect_files(dir: &Path, relative: &str, files: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry
+            .file_name()
+            .into_string()
+            .map_err(|name| anyhow!("{name:?} is not valid UTF-8"))?;
+        let relative = if relative.is_empty() {
+            name
+        } else {
+            format!("{relative}/{name}")
+        };
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &relative, files)?;
+        } else if file_type.is_file() {
+            files.push((relative, entry.path()));
+        }
+    }
+    Ok(())
+}
+
+/// The game looks names up case-insensitively, so names that only differ in
+/// case would shadow each other.
+fn add_entry(entries: &mut BTreeMap<String, Vec<u8>>, name: String, bytes: Vec<u8>) -> Result<()> {
+    if let Some(existing) = entries
+        .keys()
+        .find(|existing| existing.eq_ignore_ascii_case(&name))
+    {
+        bail!("{name} and {existing} map to the same archive entry");
+    }
+    entries.insert(name, bytes);
+    Ok(())
+}
+
+fn write_archive(path: &Path, entries: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+    let mut writer = ZipWriter::new(fs::File::create(path)?);
+    let options = SimpleFileOptions::default()
+        .last_modified_time(DateTime::default())
+        .unix_permissions(0o644);
+
+    for (name, bytes) in entries {
+        let stored = name.rsplit_once('.').is_some_and(|(_, extension)| {
+            STORED_EXTENSIONS
+                .iter()
+                .any(|stored| extension.eq_ignore_ascii_case(stored))
+        });
+        let method = if stored {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        };
+
+        writer.start_file(name, options.compression_method(method))?;
+        writer.write_all(bytes)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! Include and exclude rules, matched against paths relative to the content
+//! directory.
+//!
+//! A rules file holds one rule per line:
+//!
+//! ```text
+//! # Comment
+//! include textures/**
+//! include **/*.glsl
+//! exclude **/*.psd
+//! ```
+//!
+//! `*` and `?` match within a path segment and `**` matches any number of
+//! segments. Matching ignores case, like the game's archive lookups.
+
+use anyhow::{Result, bail};
+
+#[derive(Debug, Default)]
+pub struct Rules {
+    include: Vec<Vec<String>>,
+    exclude: Vec<Vec<String>>,
+}
+
+impl Rules {
+    pub fn include(&mut self, pattern: &str) {
+        self.include.push(split(pattern));
+    }
+
+    pub fn exclude(&mut self, pattern: &str) {
+        self.exclude.push(split(pattern));
+    }
+
+    pub fn parse(&mut self, text: &str) -> Result<()> {
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(char::is_whitespace) {
+                Some(("include", pattern)) => self.include(pattern.trim()),
+                Some(("exclude", pattern)) => self.exclude(pattern.trim()),
+                _ => bail!(
+                    "line {}: expected `include <pattern>` or `exclude <pattern>`",
+                    index + 1
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `path` matches an include rule, or there are none, and no
+    /// exclude rule.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = split(path);
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        let matches_any = |patterns: &[Vec<String>]| {
+            patterns.iter().any(|pattern| {
+                let pattern: Vec<&str> = pattern.iter().map(String::as_str).collect();
+                glob::matches(&pattern, &path)
+            })
+        };
+        (self.include.is_empty() || matches_any(&self.include)) && !matches_any(&self.exclude)
+    }
+}
+
+fn split(path: &str) -> Vec<String> {
+    path.replace('\\', "/")
+        .to_lowercase()
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(text: &str) -> Rules {
+        let mut rules = Rules::default();
+        rules.parse(text).unwrap();
+        rules
+    }
+
+    #[test]
+    fn everything_matches_without_includes() {
+        let rules = rules("# nothing but a comment\n\n");
+        assert!(rules.matches("textures/wall.png"));
+        assert!(rules.matches("readme.txt"));
+    }
+
+    #[test]
+    fn double_star_includes_nested_paths() {
+        let rules = rules("include textures/**\ninclude **/*.glsl\n");
+        assert!(rules.matches("textures/wall.png"));
+        assert!(rules.matches("textures/sky/day/east.png"));
+        assert!(rules.matches("shader.glsl"));
+        assert!(rules.matches("shaders/post/bloom.glsl"));
+        assert!(!rules.matches("maps/e1m1.bsp"));
+        assert!(!rules.matches("shaders/bloom.glsl.bak"));
+    }
+
+    #[test]
+    fn excludes_win_over_includes() {
+        let rules = rules("exclude **/*.psd\ninclude textures/**\n  exclude   textures/wip/**\n");
+        assert!(rules.matches("textures/wall.png"));
+        assert!(!rules.matches("textures/wall.psd"));
+        assert!(!rules.matches("textures/wip/wall.png"));
+        assert!(!rules.matches("maps/e1m1.bsp"));
+    }
+
+    #[test]
+    fn matching_ignores_case_and_separators() {
+        let rules = rules("include Textures/*.PNG\nexclude textures\\secret.png\n");
+        assert!(rules.matches("TEXTURES\\Wall.png"));
+        assert!(rules.matches("./textures//wall.png"));
+        assert!(!rules.matches("Textures/Secret.PNG"));
+    }
+
+    #[test]
+    fn unknown_rules_name_the_line() {
+        let error = Rules::default()
+            .parse("include **\n\ninclde textures/**\n")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3: expected `include <pattern>` or `exclude <pattern>`"
+        );
+        assert!(Rules::default().parse("include").is_err());
+    }
+}